@@ -1,12 +1,19 @@
 //! Intermediate AST representation for parsed data structures
 
+use crate::error::{AlchemistError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+
 /// Represents a complete schema with multiple type definitions
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Schema {
     /// The root type name
     pub root_name: String,
     /// All type definitions in the schema
     pub types: Vec<TypeDef>,
+    /// Warnings about lossy or uncertain inference decisions
+    pub warnings: Vec<String>,
 }
 
 impl Schema {
@@ -15,6 +22,7 @@ impl Schema {
         Self {
             root_name: root_name.into(),
             types: Vec::new(),
+            warnings: Vec::new(),
         }
     }
 
@@ -27,10 +35,505 @@ impl Schema {
     pub fn root_type(&self) -> Option<&TypeDef> {
         self.types.iter().find(|t| t.name == self.root_name)
     }
+
+    /// Check that the schema is consistent enough to generate code from
+    ///
+    /// The root type must exist, type names must be unique and every
+    /// reference must name a type. All problems are reported together.
+    pub fn validate(&self) -> Result<()> {
+        let mut problems = Vec::new();
+        if self.root_type().is_none() {
+            problems.push(format!("root type '{}' is missing", self.root_name));
+        }
+
+        let mut names = HashSet::new();
+        for type_def in &self.types {
+            if !names.insert(type_def.name.as_str()) {
+                problems.push(format!(
+                    "type '{}' is defined more than once",
+                    type_def.name
+                ));
+            }
+        }
+
+        for type_def in &self.types {
+            for field in &type_def.fields {
+                for name in field.field_type.referenced_types() {
+                    if !names.contains(name) {
+                        problems.push(format!(
+                            "field '{}.{}' refers to undefined type '{}'",
+                            type_def.name, field.name, name
+                        ));
+                    }
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(AlchemistError::InvalidSchema(problems))
+        }
+    }
+
+    /// Remove type definitions that cannot be reached from the root
+    ///
+    /// Reachability follows references through arrays, optionals, maps and
+    /// unions. Wrappers of a root array are kept even when they are not the
+    /// root, as the list alias next to an `item_as_root` root is. Returns the
+    /// names of the removed types in declaration order.
+    pub fn prune_unreachable(&mut self) -> Vec<String> {
+        let mut reachable: HashSet<String> = HashSet::new();
+        let mut pending: Vec<String> = self
+            .types
+            .iter()
+            .filter(|t| t.origin == TypeOrigin::ArrayWrapper)
+            .map(|t| t.name.clone())
+            .collect();
+        pending.push(self.root_name.clone());
+
+        while let Some(name) = pending.pop() {
+            if !reachable.insert(name.clone()) {
+                continue;
+            }
+            if let Some(type_def) = self.types.iter().find(|t| t.name == name) {
+                for field in &type_def.fields {
+                    pending.extend(
+                        field
+                            .field_type
+                            .referenced_types()
+                            .into_iter()
+                            .map(str::to_string),
+                    );
+                }
+            }
+        }
+
+        let mut removed = Vec::new();
+        self.types.retain(|t| {
+            let keep = reachable.contains(&t.name);
+            if !keep {
+                removed.push(t.name.clone());
+            }
+            keep
+        });
+        removed
+    }
+
+    /// Make the named type the root and drop every type it does not depend
+    /// on, transitively
+    ///
+    /// Fails, listing the available names, when no type has that name.
+    pub fn retain_type(&mut self, name: &str) -> Result<()> {
+        if !self.types.iter().any(|t| t.name == name) {
+            let mut names: Vec<&str> = self.types.iter().map(|t| t.name.as_str()).collect();
+            names.sort_unstable();
+            return Err(AlchemistError::InvalidStructure(format!(
+                "No type named '{}'; available types: {}",
+                name,
+                names.join(", ")
+            )));
+        }
+        self.root_name = name.to_string();
+        self.prune_unreachable();
+        Ok(())
+    }
+
+    /// Dotted paths of all fields reachable from the root, e.g. `owner.id`
+    ///
+    /// Array elements share the path of their array and map values add a `*`
+    /// segment, so `users.*.name` is a field of every value of `users`. Each
+    /// type is visited once, under the first path that reaches it. Returns the
+    /// path, owning type name and field index of every field.
+    pub fn field_paths(&self) -> Vec<(String, &str, usize)> {
+        let mut paths = Vec::new();
+        let mut visited: HashSet<&str> = HashSet::new();
+        let mut pending = VecDeque::from([(String::new(), self.root_name.as_str())]);
+
+        while let Some((prefix, name)) = pending.pop_front() {
+            if !visited.insert(name) {
+                continue;
+            }
+            let Some(type_def) = self.types.iter().find(|t| t.name == name) else {
+                continue;
+            };
+            for (index, field) in type_def.fields.iter().enumerate() {
+                let path = if prefix.is_empty() {
+                    field.name.clone()
+                } else {
+                    format!("{}.{}", prefix, field.name)
+                };
+                let mut nested = Vec::new();
+                field.field_type.nested_type_paths(&path, &mut nested);
+                pending.extend(nested);
+                paths.push((path, type_def.name.as_str(), index));
+            }
+        }
+        paths
+    }
+
+    /// Rename type definitions and every reference to them
+    ///
+    /// Names missing from `renames` are kept.
+    pub fn rename_types(&mut self, renames: &HashMap<String, String>) {
+        if let Some(root_name) = renames.get(&self.root_name) {
+            self.root_name = root_name.clone();
+        }
+        for type_def in &mut self.types {
+            if let Some(name) = renames.get(&type_def.name) {
+                // Keep the default doc in step with the name
+                if type_def.doc == Some(format!("Auto-generated {} type", type_def.name)) {
+                    type_def.doc = Some(format!("Auto-generated {} type", name));
+                }
+                type_def.name = name.clone();
+            }
+        }
+        walk_schema_mut(&mut TypeRenamer { renames }, self);
+    }
+
+    /// Add a `NamePartial` copy of every type with all fields optional, e.g.
+    /// for PATCH request bodies
+    ///
+    /// Partial types reference the full nested types. A name already taken
+    /// gets a numeric suffix.
+    pub fn add_partial_types(&mut self) {
+        let mut names: HashSet<String> = self.types.iter().map(|t| t.name.clone()).collect();
+        let partials: Vec<TypeDef> = self
+            .types
+            .iter()
+            .map(|type_def| {
+                let base = format!("{}Partial", type_def.name);
+                let mut name = base.clone();
+                let mut counter = 1;
+                while names.contains(&name) {
+                    counter += 1;
+                    name = format!("{}{}", base, counter);
+                }
+                names.insert(name.clone());
+
+                TypeDef {
+                    name,
+                    doc: Some(format!(
+                        "{} with every field optional, for partial updates",
+                        type_def.name
+                    )),
+                    fields: type_def
+                        .fields
+                        .iter()
+                        .cloned()
+                        .map(Field::optional)
+                        .collect(),
+                    origin: type_def.origin,
+                    tag: None,
+                }
+            })
+            .collect();
+        self.types.extend(partials);
+    }
+
+    /// Dotted paths of the fields whose type is or contains `Any`
+    pub fn any_field_paths(&self) -> Vec<String> {
+        self.field_paths()
+            .into_iter()
+            .filter(|(_, name, index)| {
+                self.types
+                    .iter()
+                    .find(|t| t.name == *name)
+                    .is_some_and(|t| t.fields[*index].field_type.contains_any())
+            })
+            .map(|(path, _, _)| path)
+            .collect()
+    }
+
+    /// Attach descriptions, keyed by dotted field path, as field docs
+    ///
+    /// Paths that match no field are ignored.
+    pub fn describe_fields(&mut self, descriptions: &HashMap<String, String>) {
+        if descriptions.is_empty() {
+            return;
+        }
+        let described: Vec<(String, usize, String)> = self
+            .field_paths()
+            .into_iter()
+            .filter_map(|(path, name, index)| {
+                descriptions
+                    .get(&path)
+                    .map(|doc| (name.to_string(), index, doc.clone()))
+            })
+            .collect();
+
+        for (name, index, doc) in described {
+            if let Some(type_def) = self.types.iter_mut().find(|t| t.name == name) {
+                type_def.fields[index].doc = Some(doc);
+            }
+        }
+    }
+
+    /// Pin the type and optionality of fields by dotted path, as in
+    /// [`Schema::describe_fields`], replacing what inference decided
+    ///
+    /// Paths that match no field are kept as warnings. A type that names
+    /// neither a primitive nor a type in the schema is an error.
+    ///
+    /// Identical objects share one deduplicated type, which is reached only
+    /// through the first path to it, see [`Schema::field_paths`]. Overriding
+    /// a field of such a type changes it everywhere the type is used, and a
+    /// warning names the type.
+    pub fn apply_overrides(&mut self, overrides: &HashMap<String, FieldOverride>) -> Result<()> {
+        if overrides.is_empty() {
+            return Ok(());
+        }
+        let mut references = ReferenceCounter::default();
+        walk_schema(&mut references, self);
+        let mut shared_warnings = Vec::new();
+        let mut matched = HashSet::new();
+        let mut pinned = Vec::new();
+        for (path, name, index) in self.field_paths() {
+            let Some(field_override) = overrides.get(&path) else {
+                continue;
+            };
+            let field_type = match field_override.type_name() {
+                Some(type_name) => Some(self.parse_type_name(type_name).ok_or_else(|| {
+                    AlchemistError::InvalidStructure(format!(
+                        "Unknown type '{}' in override for '{}'",
+                        type_name, path
+                    ))
+                })?),
+                None => None,
+            };
+            if references.counts.get(name).is_some_and(|&count| count > 1) {
+                shared_warnings.push(format!(
+                    "Override for '{}' also changes the other uses of type '{}'",
+                    path, name
+                ));
+            }
+            pinned.push((
+                name.to_string(),
+                index,
+                field_type,
+                field_override.optional(),
+            ));
+            matched.insert(path);
+        }
+
+        for (name, index, field_type, optional) in pinned {
+            if let Some(type_def) = self.types.iter_mut().find(|t| t.name == name) {
+                let field = &mut type_def.fields[index];
+                if let Some(field_type) = field_type {
+                    field.field_type = field_type;
+                    field.constraints = Constraints::default();
+                }
+                if let Some(optional) = optional {
+                    field.optional = optional;
+                }
+            }
+        }
+
+        let mut unmatched: Vec<&String> = overrides
+            .keys()
+            .filter(|path| !matched.contains(*path))
+            .collect();
+        unmatched.sort();
+        shared_warnings.sort();
+        self.warnings.extend(shared_warnings);
+        self.warnings.extend(
+            unmatched
+                .into_iter()
+                .map(|path| format!("Override for '{}' matches no field", path)),
+        );
+        Ok(())
+    }
+
+    /// Parse an override type: a primitive or type name, followed by any
+    /// number of `[]` (array) and `?` (nullable) suffixes
+    fn parse_type_name(&self, type_name: &str) -> Option<FieldType> {
+        let type_name = type_name.trim();
+        if let Some(inner) = type_name.strip_suffix('?') {
+            return Some(FieldType::Optional(Box::new(self.parse_type_name(inner)?)));
+        }
+        if let Some(inner) = type_name.strip_suffix("[]") {
+            return Some(FieldType::Array(Box::new(self.parse_type_name(inner)?)));
+        }
+        match type_name {
+            "string" => Some(FieldType::String),
+            "integer" => Some(FieldType::Integer),
+            "float" => Some(FieldType::Float),
+            "boolean" => Some(FieldType::Boolean),
+            "decimal" => Some(FieldType::Decimal),
+            "uuid" => Some(FieldType::Uuid),
+            "any" => Some(FieldType::Any),
+            name if self.types.iter().any(|t| t.name == name) => {
+                Some(FieldType::Reference(name.to_string()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Differences from this schema to `new`, matching types and fields by name
+    ///
+    /// Removed types and fields are listed in this schema's order, then added
+    /// ones in the order of `new`. Docs and constraints are not compared.
+    pub fn diff(&self, new: &Schema) -> Vec<SchemaChange> {
+        let mut changes = Vec::new();
+
+        for old_type in &self.types {
+            let Some(new_type) = new.types.iter().find(|t| t.name == old_type.name) else {
+                changes.push(SchemaChange::TypeRemoved(old_type.name.clone()));
+                continue;
+            };
+            for old_field in &old_type.fields {
+                let Some(new_field) = new_type.fields.iter().find(|f| f.name == old_field.name)
+                else {
+                    changes.push(SchemaChange::FieldRemoved {
+                        type_name: old_type.name.clone(),
+                        field: old_field.name.clone(),
+                    });
+                    continue;
+                };
+                if new_field.field_type != old_field.field_type {
+                    changes.push(SchemaChange::FieldRetyped {
+                        type_name: old_type.name.clone(),
+                        field: old_field.name.clone(),
+                        old: old_field.field_type.clone(),
+                        new: new_field.field_type.clone(),
+                    });
+                }
+                if new_field.optional != old_field.optional {
+                    changes.push(SchemaChange::OptionalityChanged {
+                        type_name: old_type.name.clone(),
+                        field: old_field.name.clone(),
+                        optional: new_field.optional,
+                    });
+                }
+            }
+            for new_field in &new_type.fields {
+                if !old_type.fields.iter().any(|f| f.name == new_field.name) {
+                    changes.push(SchemaChange::FieldAdded {
+                        type_name: new_type.name.clone(),
+                        field: new_field.name.clone(),
+                        field_type: new_field.field_type.clone(),
+                        optional: new_field.optional,
+                    });
+                }
+            }
+        }
+
+        for new_type in &new.types {
+            if !self.types.iter().any(|t| t.name == new_type.name) {
+                changes.push(SchemaChange::TypeAdded(new_type.name.clone()));
+            }
+        }
+        changes
+    }
+
+    /// Iterate over all types in dependency order
+    ///
+    /// Every type is yielded after the types it references, so generators that
+    /// need forward declarations can emit definitions in this order. The root
+    /// type is yielded last, or first when `root_first` is set. Without
+    /// `root_first`, a root array wrapper is yielded last and its element type,
+    /// the root, just before it. Reference cycles are broken deterministically
+    /// by skipping the edge that closes the cycle, visiting types from the root
+    /// and then in declaration order.
+    pub fn iter_topological(&self, root_first: bool) -> impl Iterator<Item = &TypeDef> {
+        self.topological_order(root_first)
+            .into_iter()
+            .map(move |i| &self.types[i])
+    }
+
+    /// Reorder the types themselves as `iter_topological(true)` yields them:
+    /// the root first, then every other type after the types it references
+    ///
+    /// Makes the declaration order deterministic for every generator, not
+    /// only those that ask for dependency order.
+    pub fn topological_sort(&mut self) {
+        let order = self.topological_order(true);
+        let mut types: Vec<Option<TypeDef>> = self.types.drain(..).map(Some).collect();
+        self.types = order.into_iter().filter_map(|i| types[i].take()).collect();
+    }
+
+    /// Indices of the types in dependency order, see `iter_topological`
+    fn topological_order(&self, root_first: bool) -> Vec<usize> {
+        let index: HashMap<&str, usize> = self
+            .types
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (t.name.as_str(), i))
+            .collect();
+        let root_index = index.get(self.root_name.as_str()).copied();
+
+        let mut visited = vec![Visit::New; self.types.len()];
+        let mut order = Vec::with_capacity(self.types.len());
+
+        for start in root_index.into_iter().chain(0..self.types.len()) {
+            self.visit_topological(start, &index, &mut visited, &mut order);
+        }
+
+        if let Some(root) = root_index {
+            order.retain(|&i| i != root);
+            if root_first {
+                order.insert(0, root);
+            } else {
+                // The list alias of an `item_as_root` root refers to it
+                let (wrappers, rest): (Vec<usize>, Vec<usize>) = order
+                    .into_iter()
+                    .partition(|&i| self.types[i].origin == TypeOrigin::ArrayWrapper);
+                order = rest;
+                order.push(root);
+                order.extend(wrappers);
+            }
+        }
+
+        order
+    }
+
+    /// Depth-first post-order visit used by `topological_order`
+    fn visit_topological(
+        &self,
+        current: usize,
+        index: &HashMap<&str, usize>,
+        visited: &mut [Visit],
+        order: &mut Vec<usize>,
+    ) {
+        if visited[current] != Visit::New {
+            return;
+        }
+        // Marking before descending is what breaks cycles: a back edge finds
+        // the type in progress and is skipped.
+        visited[current] = Visit::InProgress;
+
+        for field in &self.types[current].fields {
+            for name in field.field_type.referenced_types() {
+                let Some(&next) = index.get(name) else {
+                    continue;
+                };
+                if visited[next] == Visit::InProgress {
+                    log::debug!(
+                        "reference cycle broken at {}.{} -> {}",
+                        self.types[current].name,
+                        field.name,
+                        name
+                    );
+                }
+                self.visit_topological(next, index, visited, order);
+            }
+        }
+
+        visited[current] = Visit::Done;
+        order.push(current);
+    }
+}
+
+/// State of a type during the depth-first visit of `Schema::topological_order`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Visit {
+    New,
+    InProgress,
+    Done,
 }
 
 /// Represents a type definition (struct/interface)
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TypeDef {
     /// Name of the type
     pub name: String,
@@ -38,6 +541,11 @@ pub struct TypeDef {
     pub doc: Option<String>,
     /// Fields of the type
     pub fields: Vec<Field>,
+    /// Input shape the type was inferred from
+    pub origin: TypeOrigin,
+    /// Field whose value tells this type apart from the other variants of a
+    /// tagged union, if it is one
+    pub tag: Option<String>,
 }
 
 impl TypeDef {
@@ -47,6 +555,8 @@ impl TypeDef {
             name: name.into(),
             doc: None,
             fields: Vec::new(),
+            origin: TypeOrigin::default(),
+            tag: None,
         }
     }
 
@@ -62,8 +572,22 @@ impl TypeDef {
     }
 }
 
+/// Input shape a type definition was inferred from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TypeOrigin {
+    /// A single object
+    #[default]
+    Object,
+    /// Objects merged from the elements of an array
+    MergedArray,
+    /// Objects merged from the values of a detected map
+    Map,
+    /// The wrapper around a root array, with the array as its `items` field
+    ArrayWrapper,
+}
+
 /// Represents a field in a type definition
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Field {
     /// Field name (original from JSON/YAML)
     pub name: String,
@@ -75,6 +599,29 @@ pub struct Field {
     pub optional: bool,
     /// Documentation comment
     pub doc: Option<String>,
+    /// Bounds observed in the samples, when constraint inference is enabled
+    #[serde(default)]
+    pub constraints: Constraints,
+}
+
+/// Value bounds of a field, taken from the smallest and largest samples
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Constraints {
+    /// Smallest number seen
+    pub min: Option<f64>,
+    /// Largest number seen
+    pub max: Option<f64>,
+    /// Length of the shortest string seen, in characters
+    pub min_length: Option<usize>,
+    /// Length of the longest string seen, in characters
+    pub max_length: Option<usize>,
+}
+
+impl Constraints {
+    /// Whether no bound is set
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 impl Field {
@@ -86,6 +633,7 @@ impl Field {
             field_type,
             optional: false,
             doc: None,
+            constraints: Constraints::default(),
         }
     }
 
@@ -95,6 +643,12 @@ impl Field {
         self
     }
 
+    /// Add a documentation comment
+    pub fn with_doc(mut self, doc: impl Into<String>) -> Self {
+        self.doc = Some(doc.into());
+        self
+    }
+
     /// Set a safe name for the field
     pub fn with_safe_name(mut self, safe_name: impl Into<String>) -> Self {
         self.safe_name = Some(safe_name.into());
@@ -107,8 +661,112 @@ impl Field {
     }
 }
 
-/// Represents the type of a field
+/// Type and optionality pinned for one field, see [`Schema::apply_overrides`]
+///
+/// Either a bare type such as `"decimal"`, `"string?"` or `"Owner[]"`, or an
+/// object with an optional `type` and `optional` flag.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum FieldOverride {
+    /// Replace the field type only
+    Type(String),
+    /// Replace the field type, optionality or both
+    Spec {
+        #[serde(rename = "type")]
+        field_type: Option<String>,
+        optional: Option<bool>,
+    },
+}
+
+impl FieldOverride {
+    /// The pinned type, if any
+    pub fn type_name(&self) -> Option<&str> {
+        match self {
+            FieldOverride::Type(type_name) => Some(type_name),
+            FieldOverride::Spec { field_type, .. } => field_type.as_deref(),
+        }
+    }
+
+    /// The pinned optionality, if any
+    pub fn optional(&self) -> Option<bool> {
+        match self {
+            FieldOverride::Type(_) => None,
+            FieldOverride::Spec { optional, .. } => *optional,
+        }
+    }
+}
+
+/// One difference between two schemas, see [`Schema::diff`]
+///
+/// Displays as a line starting with `+` for additions, `-` for removals and
+/// `~` for changes, e.g. `~ User.id: integer -> string`.
 #[derive(Debug, Clone, PartialEq)]
+pub enum SchemaChange {
+    /// A type only in the new schema
+    TypeAdded(String),
+    /// A type only in the old schema
+    TypeRemoved(String),
+    /// A field only in the new schema's version of a type
+    FieldAdded {
+        type_name: String,
+        field: String,
+        field_type: FieldType,
+        optional: bool,
+    },
+    /// A field only in the old schema's version of a type
+    FieldRemoved { type_name: String, field: String },
+    /// A field whose type differs
+    FieldRetyped {
+        type_name: String,
+        field: String,
+        old: FieldType,
+        new: FieldType,
+    },
+    /// A field that became optional, or required
+    OptionalityChanged {
+        type_name: String,
+        field: String,
+        optional: bool,
+    },
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaChange::TypeAdded(name) => write!(f, "+ type {}", name),
+            SchemaChange::TypeRemoved(name) => write!(f, "- type {}", name),
+            SchemaChange::FieldAdded {
+                type_name,
+                field,
+                field_type,
+                optional,
+            } => {
+                let marker = if *optional { " (optional)" } else { "" };
+                write!(f, "+ {}.{}: {}{}", type_name, field, field_type, marker)
+            }
+            SchemaChange::FieldRemoved { type_name, field } => {
+                write!(f, "- {}.{}", type_name, field)
+            }
+            SchemaChange::FieldRetyped {
+                type_name,
+                field,
+                old,
+                new,
+            } => write!(f, "~ {}.{}: {} -> {}", type_name, field, old, new),
+            SchemaChange::OptionalityChanged {
+                type_name,
+                field,
+                optional,
+            } => {
+                let now = if *optional { "optional" } else { "required" };
+                write!(f, "~ {}.{}: now {}", type_name, field, now)
+            }
+        }
+    }
+}
+
+/// Represents the type of a field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum FieldType {
     /// String type
     String,
@@ -122,6 +780,8 @@ pub enum FieldType {
     Null,
     /// Array of a specific type
     Array(Box<FieldType>),
+    /// Array of unique values of a specific type
+    Set(Box<FieldType>),
     /// Optional/nullable type
     Optional(Box<FieldType>),
     /// Reference to another type definition
@@ -132,6 +792,156 @@ pub enum FieldType {
     Any,
     /// Map/Record type
     Map(Box<FieldType>, Box<FieldType>),
+    /// Point in time, with the encoding used in the source data
+    DateTime(DateTimeFormat),
+    /// Exact decimal number, e.g. a monetary amount
+    Decimal,
+    /// String restricted to a few values, in the order they were first seen
+    Enum(Vec<String>),
+    /// UUID string in the canonical 8-4-4-4-12 hex form
+    Uuid,
+}
+
+/// Written in the notation of `--schema-overrides`: `integer`, `string[]`,
+/// `User?`, extended with `set<T>`, `map<K, V>`, `A | B`, `"a" | "b"` and
+/// `datetime (<encoding>)`
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Unions are parenthesized where a suffix would otherwise bind to
+        // their last member
+        let suffixed = |f: &mut fmt::Formatter<'_>, inner: &FieldType, suffix: &str| {
+            if matches!(inner, FieldType::Union(_) | FieldType::Enum(_)) {
+                write!(f, "({}){}", inner, suffix)
+            } else {
+                write!(f, "{}{}", inner, suffix)
+            }
+        };
+        match self {
+            FieldType::String => write!(f, "string"),
+            FieldType::Integer => write!(f, "integer"),
+            FieldType::Float => write!(f, "float"),
+            FieldType::Boolean => write!(f, "boolean"),
+            FieldType::Null => write!(f, "null"),
+            FieldType::Any => write!(f, "any"),
+            FieldType::Decimal => write!(f, "decimal"),
+            FieldType::Uuid => write!(f, "uuid"),
+            FieldType::Array(inner) => suffixed(f, inner, "[]"),
+            FieldType::Optional(inner) => suffixed(f, inner, "?"),
+            FieldType::Set(inner) => write!(f, "set<{}>", inner),
+            FieldType::Map(key, value) => write!(f, "map<{}, {}>", key, value),
+            FieldType::Reference(name) => write!(f, "{}", name),
+            FieldType::DateTime(format) => write!(f, "datetime ({})", format.description()),
+            FieldType::Union(members) => {
+                let members: Vec<String> = members.iter().map(ToString::to_string).collect();
+                write!(f, "{}", members.join(" | "))
+            }
+            FieldType::Enum(values) => {
+                let values: Vec<String> = values.iter().map(|v| format!("{:?}", v)).collect();
+                write!(f, "{}", values.join(" | "))
+            }
+        }
+    }
+}
+
+/// How a `FieldType::DateTime` value is encoded in the data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DateTimeFormat {
+    /// Integer Unix timestamp in seconds
+    EpochSeconds,
+    /// Integer Unix timestamp in milliseconds
+    EpochMillis,
+    /// ISO 8601 / RFC 3339 date and time string with a UTC offset, e.g.
+    /// `2024-01-02T15:04:05Z`
+    Rfc3339,
+    /// ISO 8601 date and time string without an offset, e.g. `2024-01-02T15:04:05`
+    LocalDateTime,
+    /// ISO 8601 calendar date string, e.g. `2024-01-02`
+    Date,
+}
+
+impl DateTimeFormat {
+    /// Short description of the encoding, used in generated doc comments
+    pub fn description(self) -> &'static str {
+        match self {
+            DateTimeFormat::EpochSeconds => "Unix timestamp in seconds",
+            DateTimeFormat::EpochMillis => "Unix timestamp in milliseconds",
+            DateTimeFormat::Rfc3339 => "ISO 8601 date and time with offset",
+            DateTimeFormat::LocalDateTime => "ISO 8601 date and time without offset",
+            DateTimeFormat::Date => "ISO 8601 date",
+        }
+    }
+
+    /// Whether the value is an integer timestamp rather than a string
+    pub fn is_epoch(self) -> bool {
+        matches!(
+            self,
+            DateTimeFormat::EpochSeconds | DateTimeFormat::EpochMillis
+        )
+    }
+
+    /// Format of an ISO 8601 date or date-time string, if it is one
+    ///
+    /// Only complete values match: a `YYYY-MM-DD` date, optionally followed
+    /// by `T`, `HH:MM:SS`, a fraction of a second and an offset. Partial
+    /// dates such as `2024` or `2024-01` and plain numbers do not.
+    pub fn of_iso_string(value: &str) -> Option<Self> {
+        fn number(text: &str, range: std::ops::RangeInclusive<u32>) -> bool {
+            text.bytes().all(|b| b.is_ascii_digit())
+                && text.parse::<u32>().is_ok_and(|n| range.contains(&n))
+        }
+        fn date(text: &str) -> bool {
+            let bytes = text.as_bytes();
+            bytes.len() == 10
+                && bytes[4] == b'-'
+                && bytes[7] == b'-'
+                && number(&text[..4], 0..=9999)
+                && number(&text[5..7], 1..=12)
+                && number(&text[8..10], 1..=31)
+        }
+        fn time(text: &str) -> bool {
+            let bytes = text.as_bytes();
+            bytes.len() == 8
+                && bytes[2] == b':'
+                && bytes[5] == b':'
+                && number(&text[..2], 0..=23)
+                && number(&text[3..5], 0..=59)
+                && number(&text[6..8], 0..=60)
+        }
+
+        if !value.is_ascii() || value.len() < 10 || !date(&value[..10]) {
+            return None;
+        }
+        let rest = &value[10..];
+        if rest.is_empty() {
+            return Some(DateTimeFormat::Date);
+        }
+        let rest = rest.strip_prefix(['T', 't'])?;
+        if rest.len() < 8 || !time(&rest[..8]) {
+            return None;
+        }
+        let mut rest = &rest[8..];
+        if let Some(fraction) = rest.strip_prefix('.') {
+            let digits = fraction.bytes().take_while(u8::is_ascii_digit).count();
+            if digits == 0 {
+                return None;
+            }
+            rest = &fraction[digits..];
+        }
+        match rest {
+            "" => Some(DateTimeFormat::LocalDateTime),
+            "Z" | "z" => Some(DateTimeFormat::Rfc3339),
+            offset => {
+                let sign = offset.starts_with(['+', '-']);
+                let bytes = offset.as_bytes();
+                (sign
+                    && bytes.len() == 6
+                    && bytes[3] == b':'
+                    && number(&offset[1..3], 0..=23)
+                    && number(&offset[4..6], 0..=59))
+                .then_some(DateTimeFormat::Rfc3339)
+            }
+        }
+    }
 }
 
 impl FieldType {
@@ -144,27 +954,387 @@ impl FieldType {
                 | FieldType::Float
                 | FieldType::Boolean
                 | FieldType::Null
+                | FieldType::DateTime(_)
+                | FieldType::Decimal
+                | FieldType::Enum(_)
+                | FieldType::Uuid
         )
     }
 
+    /// Check if the type admits `null`
+    pub fn is_nullable(&self) -> bool {
+        match self {
+            FieldType::Null | FieldType::Optional(_) | FieldType::Any => true,
+            FieldType::Union(types) => types.iter().any(FieldType::is_nullable),
+            _ => false,
+        }
+    }
+
     /// Check if the type is a reference to another type
     pub fn is_reference(&self) -> bool {
         matches!(self, FieldType::Reference(_))
     }
 
-    /// Get the inner type for arrays and optionals
+    /// Collect the names of all types referenced by this type, recursively
+    pub fn referenced_types(&self) -> Vec<&str> {
+        match self {
+            FieldType::Reference(name) => vec![name.as_str()],
+            FieldType::Array(inner) | FieldType::Set(inner) | FieldType::Optional(inner) => {
+                inner.referenced_types()
+            }
+            FieldType::Map(key, value) => {
+                let mut names = key.referenced_types();
+                names.extend(value.referenced_types());
+                names
+            }
+            FieldType::Union(types) => types.iter().flat_map(|t| t.referenced_types()).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Collect the types referenced by this type along with the field path
+    /// their fields are nested under, see `Schema::field_paths`
+    fn nested_type_paths<'a>(&'a self, path: &str, out: &mut Vec<(String, &'a str)>) {
+        match self {
+            FieldType::Reference(name) => out.push((path.to_string(), name.as_str())),
+            FieldType::Array(inner) | FieldType::Set(inner) | FieldType::Optional(inner) => {
+                inner.nested_type_paths(path, out)
+            }
+            FieldType::Map(_, value) => value.nested_type_paths(&format!("{}.*", path), out),
+            FieldType::Union(types) => {
+                for member in types {
+                    member.nested_type_paths(path, out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Check whether this type or any type nested in it matches `predicate`
+    pub fn any_nested(&self, predicate: impl Fn(&FieldType) -> bool) -> bool {
+        let mut finder = NestedTypeFinder {
+            predicate,
+            found: false,
+        };
+        finder.visit_field_type(self);
+        finder.found
+    }
+
+    /// Check whether this type is or contains `Any`
+    pub fn contains_any(&self) -> bool {
+        self.any_nested(|t| matches!(t, FieldType::Any))
+    }
+
+    /// Check whether this type is or contains a date/time
+    pub fn contains_datetime(&self) -> bool {
+        self.any_nested(|t| matches!(t, FieldType::DateTime(_)))
+    }
+
+    /// Formats of the date/times this type is or contains
+    pub fn datetime_formats(&self) -> Vec<DateTimeFormat> {
+        let mut collector = DateTimeFormatCollector::default();
+        collector.visit_field_type(self);
+        collector.formats
+    }
+
+    /// Check whether this type is or contains a decimal
+    pub fn contains_decimal(&self) -> bool {
+        self.any_nested(|t| matches!(t, FieldType::Decimal))
+    }
+
+    /// Check whether this type is or contains a UUID
+    pub fn contains_uuid(&self) -> bool {
+        self.any_nested(|t| matches!(t, FieldType::Uuid))
+    }
+
+    /// Check whether a string enum appears anywhere in this type
+    pub fn contains_enum(&self) -> bool {
+        self.any_nested(|t| matches!(t, FieldType::Enum(_)))
+    }
+
+    /// Replace numbers with decimals, looking through arrays and optionals
+    ///
+    /// Returns `None` if the type holds no number to replace.
+    pub fn to_decimal(&self) -> Option<FieldType> {
+        match self {
+            FieldType::Integer | FieldType::Float => Some(FieldType::Decimal),
+            FieldType::Array(inner) => inner.to_decimal().map(|t| FieldType::Array(Box::new(t))),
+            FieldType::Set(inner) => inner.to_decimal().map(|t| FieldType::Set(Box::new(t))),
+            FieldType::Optional(inner) => {
+                inner.to_decimal().map(|t| FieldType::Optional(Box::new(t)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the inner type for arrays, sets and optionals
     pub fn inner_type(&self) -> Option<&FieldType> {
         match self {
-            FieldType::Array(inner) | FieldType::Optional(inner) => Some(inner),
+            FieldType::Array(inner) | FieldType::Set(inner) | FieldType::Optional(inner) => {
+                Some(inner)
+            }
             _ => None,
         }
     }
 }
 
+/// Read-only walk over a schema's types, fields and field types
+///
+/// Every method defaults to visiting the children of its node, so an
+/// implementation overrides only the nodes it cares about and calls the
+/// matching `walk_*` function to keep descending.
+pub trait SchemaVisitor {
+    /// Visit a type definition and, by default, its fields
+    fn visit_type_def(&mut self, type_def: &TypeDef) {
+        walk_type_def(self, type_def);
+    }
+
+    /// Visit a field of `type_def` and, by default, its type
+    fn visit_field(&mut self, type_def: &TypeDef, field: &Field) {
+        let _ = type_def;
+        self.visit_field_type(&field.field_type);
+    }
+
+    /// Visit a field type and, by default, the types nested in it
+    fn visit_field_type(&mut self, field_type: &FieldType) {
+        walk_field_type(self, field_type);
+    }
+}
+
+/// Visit every type definition of a schema in declaration order
+pub fn walk_schema<V: SchemaVisitor + ?Sized>(visitor: &mut V, schema: &Schema) {
+    for type_def in &schema.types {
+        visitor.visit_type_def(type_def);
+    }
+}
+
+/// Visit every field of a type definition
+pub fn walk_type_def<V: SchemaVisitor + ?Sized>(visitor: &mut V, type_def: &TypeDef) {
+    for field in &type_def.fields {
+        visitor.visit_field(type_def, field);
+    }
+}
+
+/// Visit the types directly nested in a field type
+pub fn walk_field_type<V: SchemaVisitor + ?Sized>(visitor: &mut V, field_type: &FieldType) {
+    match field_type {
+        FieldType::Array(inner) | FieldType::Set(inner) | FieldType::Optional(inner) => {
+            visitor.visit_field_type(inner)
+        }
+        FieldType::Map(key, value) => {
+            visitor.visit_field_type(key);
+            visitor.visit_field_type(value);
+        }
+        FieldType::Union(types) => {
+            for member in types {
+                visitor.visit_field_type(member);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// In-place rewrite of the field types of a schema
+pub trait FieldTypeVisitor {
+    /// Visit a field type and, by default, the types nested in it
+    fn visit_field_type(&mut self, field_type: &mut FieldType) {
+        walk_field_type_mut(self, field_type);
+    }
+}
+
+/// Visit the type of every field of a schema
+pub fn walk_schema_mut<V: FieldTypeVisitor + ?Sized>(visitor: &mut V, schema: &mut Schema) {
+    for field in schema.types.iter_mut().flat_map(|t| &mut t.fields) {
+        visitor.visit_field_type(&mut field.field_type);
+    }
+}
+
+/// Visit the types directly nested in a field type, allowing them to change
+pub fn walk_field_type_mut<V: FieldTypeVisitor + ?Sized>(
+    visitor: &mut V,
+    field_type: &mut FieldType,
+) {
+    match field_type {
+        FieldType::Array(inner) | FieldType::Set(inner) | FieldType::Optional(inner) => {
+            visitor.visit_field_type(inner)
+        }
+        FieldType::Map(key, value) => {
+            visitor.visit_field_type(key);
+            visitor.visit_field_type(value);
+        }
+        FieldType::Union(types) => {
+            for member in types {
+                visitor.visit_field_type(member);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Counts the references to each type, e.g. to find types used only once
+#[derive(Debug, Default)]
+pub struct ReferenceCounter {
+    /// Number of references by type name
+    pub counts: HashMap<String, usize>,
+}
+
+impl SchemaVisitor for ReferenceCounter {
+    fn visit_field_type(&mut self, field_type: &FieldType) {
+        if let FieldType::Reference(name) = field_type {
+            *self.counts.entry(name.clone()).or_default() += 1;
+        }
+        walk_field_type(self, field_type);
+    }
+}
+
+/// Stops at the first field type matching a predicate, see
+/// `FieldType::any_nested`
+struct NestedTypeFinder<F> {
+    predicate: F,
+    found: bool,
+}
+
+impl<F: Fn(&FieldType) -> bool> SchemaVisitor for NestedTypeFinder<F> {
+    fn visit_field_type(&mut self, field_type: &FieldType) {
+        if self.found {
+            return;
+        }
+        if (self.predicate)(field_type) {
+            self.found = true;
+        } else {
+            walk_field_type(self, field_type);
+        }
+    }
+}
+
+/// Collects the formats of the date/times in a field type, see
+/// `FieldType::datetime_formats`
+#[derive(Default)]
+struct DateTimeFormatCollector {
+    formats: Vec<DateTimeFormat>,
+}
+
+impl SchemaVisitor for DateTimeFormatCollector {
+    fn visit_field_type(&mut self, field_type: &FieldType) {
+        if let FieldType::DateTime(format) = field_type {
+            self.formats.push(*format);
+        }
+        walk_field_type(self, field_type);
+    }
+}
+
+/// Points references at new type names, see `Schema::rename_types`
+pub struct TypeRenamer<'a> {
+    /// New names by old name; names missing from the map are kept
+    pub renames: &'a HashMap<String, String>,
+}
+
+impl FieldTypeVisitor for TypeRenamer<'_> {
+    fn visit_field_type(&mut self, field_type: &mut FieldType) {
+        if let FieldType::Reference(name) = field_type {
+            if let Some(renamed) = self.renames.get(name) {
+                *name = renamed.clone();
+            }
+        }
+        walk_field_type_mut(self, field_type);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_iso_datetime_formats() {
+        let cases = [
+            ("2024-01-02", Some(DateTimeFormat::Date)),
+            ("2024-01-02T15:04:05Z", Some(DateTimeFormat::Rfc3339)),
+            (
+                "2024-01-02T15:04:05.123+02:00",
+                Some(DateTimeFormat::Rfc3339),
+            ),
+            ("2024-01-02T15:04:05", Some(DateTimeFormat::LocalDateTime)),
+            ("2024", None),
+            ("2024-01", None),
+            ("20240102", None),
+            ("2024-13-02", None),
+            ("2024-01-02T15:04", None),
+            ("2024-01-02T15:04:05+0200", None),
+            ("2024-01-02 is a date", None),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(DateTimeFormat::of_iso_string(value), expected, "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_schema_diff() {
+        let mut user = TypeDef::new("User");
+        user.add_field(Field::new("id", FieldType::Integer));
+        user.add_field(Field::new("name", FieldType::String));
+        user.add_field(Field::new("email", FieldType::String).optional());
+        let mut old = Schema::new("User");
+        old.add_type(user.clone());
+        old.add_type(TypeDef::new("Legacy"));
+
+        user.fields.retain(|f| f.name != "name");
+        user.fields[0].field_type = FieldType::String;
+        user.fields[1].optional = false;
+        user.add_field(Field::new(
+            "tags",
+            FieldType::Array(Box::new(FieldType::Union(vec![
+                FieldType::String,
+                FieldType::Integer,
+            ]))),
+        ));
+        user.add_field(Field::new("team", FieldType::Reference("Team".into())).optional());
+        let mut new = Schema::new("User");
+        new.add_type(user);
+        new.add_type(TypeDef::new("Team"));
+
+        let lines: Vec<String> = old.diff(&new).iter().map(ToString::to_string).collect();
+        assert_eq!(
+            lines,
+            [
+                "~ User.id: integer -> string",
+                "- User.name",
+                "~ User.email: now required",
+                "+ User.tags: (string | integer)[]",
+                "+ User.team: Team (optional)",
+                "- type Legacy",
+                "+ type Team",
+            ]
+        );
+        assert!(new.diff(&new).is_empty());
+    }
+
+    #[test]
+    fn test_validate_reports_dangling_references() {
+        let mut schema = Schema::new("Root");
+        let mut root = TypeDef::new("Root");
+        root.add_field(Field::new("owner", FieldType::Reference("Owner".into())));
+        root.add_field(Field::new(
+            "tags",
+            FieldType::Array(Box::new(FieldType::Reference("Tag".into()))),
+        ));
+        schema.add_type(root);
+        schema.add_type(TypeDef::new("Tag"));
+        assert_eq!(
+            schema.validate().unwrap_err().to_string(),
+            "Invalid schema: field 'Root.owner' refers to undefined type 'Owner'"
+        );
+
+        schema.add_type(TypeDef::new("Owner"));
+        assert!(schema.validate().is_ok());
+
+        schema.add_type(TypeDef::new("Tag"));
+        schema.root_name = "User".to_string();
+        assert_eq!(
+            schema.validate().unwrap_err().to_string(),
+            "Invalid schema: root type 'User' is missing; type 'Tag' is defined more than once"
+        );
+    }
+
     #[test]
     fn test_schema_creation() {
         let mut schema = Schema::new("User");
@@ -187,4 +1357,329 @@ mod tests {
         assert!(FieldType::Boolean.is_primitive());
         assert!(!FieldType::Array(Box::new(FieldType::String)).is_primitive());
     }
+
+    fn type_with_refs(name: &str, refs: &[&str]) -> TypeDef {
+        let mut type_def = TypeDef::new(name);
+        for r in refs {
+            type_def.add_field(Field::new(
+                r.to_lowercase(),
+                FieldType::Array(Box::new(FieldType::Reference(r.to_string()))),
+            ));
+        }
+        type_def
+    }
+
+    fn topological_names(schema: &Schema, root_first: bool) -> Vec<&str> {
+        schema
+            .iter_topological(root_first)
+            .map(|t| t.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_iter_topological_orders_dependencies_first() {
+        // Declaration order as produced by the parser: root first, then
+        // dependents before some of their dependencies.
+        let mut schema = Schema::new("Root");
+        schema.add_type(type_with_refs("Root", &["User"]));
+        schema.add_type(type_with_refs("User", &["Address", "Tag"]));
+        schema.add_type(type_with_refs("Address", &["Geo"]));
+        schema.add_type(type_with_refs("Tag", &[]));
+        schema.add_type(type_with_refs("Geo", &[]));
+
+        assert_eq!(
+            topological_names(&schema, false),
+            vec!["Geo", "Address", "Tag", "User", "Root"]
+        );
+        assert_eq!(
+            topological_names(&schema, true),
+            vec!["Root", "Geo", "Address", "Tag", "User"]
+        );
+    }
+
+    #[test]
+    fn test_prune_unreachable() {
+        let mut schema = Schema::new("Root");
+        schema.add_type(type_with_refs("Root", &["User"]));
+        schema.add_type(type_with_refs("Dead", &["User"]));
+        schema.add_type(type_with_refs("User", &[]));
+
+        let removed = schema.prune_unreachable();
+
+        assert_eq!(removed, vec!["Dead"]);
+        assert_eq!(topological_names(&schema, false), vec!["User", "Root"]);
+    }
+
+    #[test]
+    fn test_retain_type_keeps_dependencies() {
+        let mut schema = Schema::new("Root");
+        schema.add_type(type_with_refs("Root", &["User", "Meta"]));
+        schema.add_type(type_with_refs("User", &["Address"]));
+        schema.add_type(type_with_refs("Address", &[]));
+        schema.add_type(type_with_refs("Meta", &[]));
+
+        schema.retain_type("User").unwrap();
+
+        assert_eq!(schema.root_name, "User");
+        assert_eq!(topological_names(&schema, false), vec!["Address", "User"]);
+
+        let err = schema.retain_type("Missing").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input structure: No type named 'Missing'; available types: Address, User"
+        );
+    }
+
+    #[test]
+    fn test_iter_topological_breaks_cycles_deterministically() {
+        let mut schema = Schema::new("Root");
+        schema.add_type(type_with_refs("Root", &["A"]));
+        schema.add_type(type_with_refs("A", &["B"]));
+        schema.add_type(type_with_refs("B", &["A"]));
+        schema.add_type(type_with_refs("Orphan", &["B"]));
+
+        let order = topological_names(&schema, false);
+        assert_eq!(order, vec!["B", "A", "Orphan", "Root"]);
+        assert_eq!(order, topological_names(&schema, false));
+    }
+
+    #[test]
+    fn test_topological_sort_reorders_types() {
+        let mut schema = Schema::new("Root");
+        schema.add_type(type_with_refs("User", &["Address", "Root"]));
+        schema.add_type(type_with_refs("Root", &["User"]));
+        schema.add_type(type_with_refs("Address", &[]));
+
+        schema.topological_sort();
+
+        let names: Vec<&str> = schema.types.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["Root", "Address", "User"]);
+        // Sorting again changes nothing
+        let sorted = schema.clone();
+        schema.topological_sort();
+        assert_eq!(schema, sorted);
+    }
+
+    #[test]
+    fn test_describe_fields_by_path() {
+        let mut schema = Schema::new("Root");
+        let mut root = TypeDef::new("Root");
+        root.add_field(Field::new(
+            "owner",
+            FieldType::Reference("Owner".to_string()),
+        ));
+        root.add_field(Field::new(
+            "users",
+            FieldType::Map(
+                Box::new(FieldType::String),
+                Box::new(FieldType::Reference("User".to_string())),
+            ),
+        ));
+        schema.add_type(root);
+        let mut owner = TypeDef::new("Owner");
+        owner.add_field(Field::new("id", FieldType::Integer));
+        schema.add_type(owner);
+        let mut user = TypeDef::new("User");
+        user.add_field(Field::new("name", FieldType::String));
+        schema.add_type(user);
+
+        let paths: Vec<String> = schema.field_paths().into_iter().map(|p| p.0).collect();
+        assert_eq!(paths, vec!["owner", "users", "owner.id", "users.*.name"]);
+
+        let descriptions = HashMap::from([
+            ("owner.id".to_string(), "Account number".to_string()),
+            ("users.*.name".to_string(), "Display name".to_string()),
+            ("missing.path".to_string(), "Ignored".to_string()),
+        ]);
+        schema.describe_fields(&descriptions);
+
+        assert_eq!(
+            schema.types[1].fields[0].doc.as_deref(),
+            Some("Account number")
+        );
+        assert_eq!(
+            schema.types[2].fields[0].doc.as_deref(),
+            Some("Display name")
+        );
+        assert_eq!(schema.types[0].fields[0].doc, None);
+    }
+
+    #[test]
+    fn test_apply_overrides_by_path() {
+        let mut schema = Schema::new("Root");
+        let mut root = TypeDef::new("Root");
+        root.add_field(Field::new("price", FieldType::Float));
+        root.add_field(Field::new("tags", FieldType::Any).optional());
+        root.add_field(Field::new("owner", FieldType::Any));
+        schema.add_type(root);
+        let mut owner = TypeDef::new("Owner");
+        owner.add_field(Field::new("id", FieldType::Integer));
+        schema.add_type(owner);
+
+        let overrides: HashMap<String, FieldOverride> = serde_json::from_str(
+            r#"{
+                "price": "decimal",
+                "tags": {"type": "string[]", "optional": false},
+                "owner": "Owner?",
+                "missing": {"optional": true}
+            }"#,
+        )
+        .unwrap();
+        schema.apply_overrides(&overrides).unwrap();
+
+        let fields = &schema.types[0].fields;
+        assert_eq!(fields[0].field_type, FieldType::Decimal);
+        assert_eq!(
+            fields[1].field_type,
+            FieldType::Array(Box::new(FieldType::String))
+        );
+        assert!(!fields[1].optional);
+        assert_eq!(
+            fields[2].field_type,
+            FieldType::Optional(Box::new(FieldType::Reference("Owner".to_string())))
+        );
+        assert_eq!(
+            schema.warnings,
+            vec!["Override for 'missing' matches no field"]
+        );
+
+        let unknown = HashMap::from([(
+            "price".to_string(),
+            FieldOverride::Type("Money".to_string()),
+        )]);
+        assert!(schema.apply_overrides(&unknown).is_err());
+    }
+
+    #[test]
+    fn test_override_of_shared_type_applies_everywhere() {
+        let mut schema = Schema::new("Root");
+        let mut root = TypeDef::new("Root");
+        root.add_field(Field::new(
+            "home",
+            FieldType::Reference("Address".to_string()),
+        ));
+        root.add_field(Field::new(
+            "work",
+            FieldType::Reference("Address".to_string()),
+        ));
+        schema.add_type(root);
+        let mut address = TypeDef::new("Address");
+        address.add_field(Field::new("zip", FieldType::Integer));
+        schema.add_type(address);
+
+        let overrides = HashMap::from([(
+            "home.zip".to_string(),
+            FieldOverride::Type("string".to_string()),
+        )]);
+        schema.apply_overrides(&overrides).unwrap();
+
+        // `work` references the same type, so its zip changes too
+        assert_eq!(schema.types[1].fields[0].field_type, FieldType::String);
+        assert_eq!(
+            schema.warnings,
+            vec!["Override for 'home.zip' also changes the other uses of type 'Address'"]
+        );
+
+        // Only the first path reaches the shared type
+        schema.warnings.clear();
+        let overrides = HashMap::from([(
+            "work.zip".to_string(),
+            FieldOverride::Type("integer".to_string()),
+        )]);
+        schema.apply_overrides(&overrides).unwrap();
+        assert_eq!(
+            schema.warnings,
+            vec!["Override for 'work.zip' matches no field"]
+        );
+    }
+
+    #[test]
+    fn test_any_field_paths() {
+        let mut schema = Schema::new("Root");
+        let mut root = TypeDef::new("Root");
+        root.add_field(Field::new("id", FieldType::Integer));
+        root.add_field(Field::new("extra", FieldType::Any));
+        root.add_field(Field::new(
+            "owner",
+            FieldType::Reference("Owner".to_string()),
+        ));
+        schema.add_type(root);
+        let mut owner = TypeDef::new("Owner");
+        owner.add_field(Field::new(
+            "tags",
+            FieldType::Optional(Box::new(FieldType::Array(Box::new(FieldType::Any)))),
+        ));
+        schema.add_type(owner);
+
+        assert_eq!(schema.any_field_paths(), vec!["extra", "owner.tags"]);
+    }
+
+    #[test]
+    fn test_add_partial_types() {
+        let mut schema = Schema::new("User");
+        let mut user = TypeDef::new("User");
+        user.add_field(Field::new("id", FieldType::Integer));
+        user.add_field(Field::new("owner", FieldType::Reference("Owner".to_string())).optional());
+        schema.add_type(user);
+        schema.add_type(TypeDef::new("Owner"));
+        schema.add_type(TypeDef::new("OwnerPartial"));
+
+        schema.add_partial_types();
+        let names: Vec<&str> = schema.types.iter().map(|t| t.name.as_str()).collect();
+
+        assert_eq!(
+            names,
+            [
+                "User",
+                "Owner",
+                "OwnerPartial",
+                "UserPartial",
+                "OwnerPartial2",
+                "OwnerPartialPartial"
+            ]
+        );
+        let partial = &schema.types[3];
+        assert!(partial.fields.iter().all(|f| f.optional));
+        // Nested types stay the full types
+        assert_eq!(
+            partial.fields[1].field_type,
+            FieldType::Reference("Owner".to_string())
+        );
+        assert!(!schema.root_type().unwrap().fields[0].optional);
+    }
+
+    #[test]
+    fn test_visitors_walk_nested_field_types() {
+        let mut schema = Schema::new("Root");
+        let mut root = TypeDef::new("Root");
+        root.add_field(Field::new(
+            "users",
+            FieldType::Optional(Box::new(FieldType::Array(Box::new(FieldType::Reference(
+                "User".to_string(),
+            ))))),
+        ));
+        root.add_field(Field::new(
+            "by_id",
+            FieldType::Map(
+                Box::new(FieldType::String),
+                Box::new(FieldType::Union(vec![
+                    FieldType::Integer,
+                    FieldType::Reference("User".to_string()),
+                ])),
+            ),
+        ));
+        schema.add_type(root);
+        schema.add_type(TypeDef::new("User"));
+
+        let mut counter = ReferenceCounter::default();
+        walk_schema(&mut counter, &schema);
+        assert_eq!(counter.counts, HashMap::from([("User".to_string(), 2)]));
+
+        let renames = HashMap::from([("User".to_string(), "Account".to_string())]);
+        schema.rename_types(&renames);
+        let mut counter = ReferenceCounter::default();
+        walk_schema(&mut counter, &schema);
+        assert_eq!(counter.counts, HashMap::from([("Account".to_string(), 2)]));
+        assert_eq!(schema.types[1].name, "Account");
+    }
 }
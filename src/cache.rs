@@ -0,0 +1,144 @@
+//! On-disk cache of inferred schemas, keyed by input content and options
+
+use alchemist::ast::Schema;
+use alchemist::formats::InputFormat;
+use alchemist::generators::GeneratorOptions;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Directory of cached schemas, one JSON file per cache key
+pub struct SchemaCache {
+    dir: PathBuf,
+}
+
+impl SchemaCache {
+    /// Create a cache rooted at the given directory
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Cache key for the inputs of a run and the options that shape their
+    /// schema
+    ///
+    /// Every option is part of the key, so changing any flag misses the cache
+    /// rather than reusing a schema inferred under different settings.
+    pub fn key(inputs: &[(&str, InputFormat)], options: &GeneratorOptions) -> String {
+        // Map iteration order is unspecified, so hash descriptions sorted
+        let mut descriptions: Vec<_> = options.descriptions.iter().collect();
+        descriptions.sort();
+        let options = GeneratorOptions {
+            descriptions: Default::default(),
+            ..options.clone()
+        };
+
+        let mut hash = Fnv1a::new();
+        hash.write(env!("CARGO_PKG_VERSION").as_bytes());
+        hash.write(format!("{:?}{:?}", options, descriptions).as_bytes());
+        for (input, format) in inputs {
+            // The length keeps one input's end from passing for the next one's start
+            hash.write(format!("{}:{}:", format, input.len()).as_bytes());
+            hash.write(input.as_bytes());
+        }
+        format!("{:016x}", hash.finish())
+    }
+
+    /// Load a cached schema, treating unreadable or stale entries as misses
+    pub fn load(&self, key: &str) -> Option<Schema> {
+        let content = fs::read_to_string(self.path(key)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Store a schema under a key, creating the cache directory if needed
+    pub fn store(&self, key: &str, schema: &Schema) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.path(key), serde_json::to_string(schema)?)
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+}
+
+/// 64-bit FNV-1a, used because its output is stable across Rust releases
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+        // Separate consecutive writes so ("ab", "c") and ("a", "bc") differ
+        self.0 ^= 0xff;
+        self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alchemist::parser::parse_json;
+
+    #[test]
+    fn test_key_depends_on_input_and_options() {
+        let options = GeneratorOptions::default();
+        let key = SchemaCache::key(&[(r#"{"a": 1}"#, InputFormat::Json)], &options);
+
+        assert_eq!(
+            key,
+            SchemaCache::key(&[(r#"{"a": 1}"#, InputFormat::Json)], &options)
+        );
+        assert_ne!(
+            key,
+            SchemaCache::key(&[(r#"{"a": 2}"#, InputFormat::Json)], &options)
+        );
+        assert_ne!(
+            key,
+            SchemaCache::key(&[(r#"{"a": 1}"#, InputFormat::Yaml)], &options)
+        );
+        let renamed = GeneratorOptions {
+            root_name: "Other".to_string(),
+            ..Default::default()
+        };
+        assert_ne!(
+            key,
+            SchemaCache::key(&[(r#"{"a": 1}"#, InputFormat::Json)], &renamed)
+        );
+        assert_ne!(
+            SchemaCache::key(
+                &[("ab", InputFormat::Json), ("c", InputFormat::Json)],
+                &options
+            ),
+            SchemaCache::key(
+                &[("a", InputFormat::Json), ("bc", InputFormat::Json)],
+                &options
+            )
+        );
+    }
+
+    #[test]
+    fn test_store_and_load_round_trip() {
+        let dir = std::env::temp_dir().join(format!("alchemist-cache-{}", std::process::id()));
+        let cache = SchemaCache::new(&dir);
+        let schema = parse_json(
+            r#"{"id": 1, "tags": ["a"], "owner": {"name": "x"}}"#,
+            &GeneratorOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(cache.load("missing"), None);
+        cache.store("entry", &schema).unwrap();
+        assert_eq!(cache.load("entry"), Some(schema));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,11 +1,26 @@
 //! CLI argument definitions using clap
 
-use crate::formats::{InputFormat, OutputFormat};
-use crate::generators::GeneratorOptions;
-use clap::{CommandFactory, Parser};
+use crate::reporter::ReportTheme;
+use alchemist::ast::FieldOverride;
+use alchemist::error::Result;
+use alchemist::formats::{InputFormat, OutputFormat};
+use alchemist::generators::clojure::{ClojureOptions, ClojureStyle};
+use alchemist::generators::csharp::{CSharpOptions, CSharpStyle};
+use alchemist::generators::fsharp::FSharpOptions;
+use alchemist::generators::go::{GoOptional, GoOptions};
+use alchemist::generators::php::PhpOptions;
+use alchemist::generators::python::{PydanticVersion, PythonOptions, PythonStyle, PythonTarget};
+use alchemist::generators::ruby::{RubyOptions, RubyStyle};
+use alchemist::generators::rust::{RustArrayType, RustOptions, RustStringType, UnionRepr};
+use alchemist::generators::typescript::{TsDecimal, TsOptions, TsStyle};
+use alchemist::generators::{self, GeneratorOptions, Indent, RenameAll, SharedOptions};
+use alchemist::parser::{FieldOrdering, MixedArrayRepr, NullOnlyType, NullPolicy};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, ValueEnum};
 use clap_complete::{generate, Shell};
+use std::collections::HashMap;
 use std::io::{self, Read};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Alchemist - Transform JSON/YAML/TOML into type-safe code
 ///
@@ -17,33 +32,86 @@ use std::path::PathBuf;
 #[command(version)]
 #[command(about = "Transform JSON/YAML/TOML into Rust, TypeScript, Zod, or Python code")]
 #[command(
-    long_about = "Alchemist is a blazingly fast CLI tool that converts JSON, YAML, or TOML data into type-safe code structures.\n\nSupported outputs:\n  • Rust structs with serde derive macros\n  • TypeScript interfaces\n  • Zod validation schemas\n  • Python Pydantic models\n\nExamples:\n  alchemist -i data.json\n  alchemist -i config.yaml -f yaml -t rust\n  cat data.json | alchemist -t python\n  alchemist --completions bash > ~/.local/share/bash-completion/completions/alchemist"
+    long_about = "Alchemist is a blazingly fast CLI tool that converts JSON, YAML, or TOML data into type-safe code structures.\n\nSupported outputs:\n  • Rust structs with serde derive macros\n  • TypeScript interfaces\n  • Zod validation schemas\n  • Python Pydantic models, dataclasses or TypedDicts\n  • Nim object types\n  • PHP 8 classes\n  • Ruby Struct or dry-struct classes\n  • F# record types\n  • Go structs with json tags\n  • Kotlin data classes for kotlinx.serialization\n  • Swift structs conforming to Codable\n  • C# records or classes for System.Text.Json\n  • C structs for embedded and FFI use\n  • Clojure Malli schemas or clojure.spec specs\n  • JSON Type Definition (RFC 8927) schemas\n  • JSON Schema (draft-07) documents\n  • GraphQL SDL object types\n  • Apache Thrift IDL structs\n  • Language-neutral pseudocode\n\nExamples:\n  alchemist -i data.json\n  alchemist -i config.yaml -f yaml -t rust\n  cat data.json | alchemist -t python\n  alchemist --completions bash > ~/.local/share/bash-completion/completions/alchemist"
 )]
 pub struct Cli {
-    /// Input file path. Use '-' or omit to read from stdin
+    /// Input file path, repeat to merge the schemas of several samples. Use '-' or omit to read from stdin
     #[arg(short, long)]
-    pub input: Option<PathBuf>,
+    pub input: Vec<PathBuf>,
 
     /// Output file path (prints to stdout if not provided)
     #[arg(short, long)]
     pub output: Option<PathBuf>,
 
-    /// Input format (auto-detected from extension if not specified)
-    #[arg(short = 'f', long, default_value = "json")]
-    pub input_format: InputFormat,
+    /// Encoding of the output file: utf8, or utf8-bom for tools that expect a byte order mark
+    #[arg(long, value_name = "ENCODING", default_value = "utf8")]
+    pub output_encoding: OutputEncoding,
 
-    /// Output format
-    #[arg(short = 't', long, default_value = "typescript")]
-    pub output_format: OutputFormat,
+    /// Convert several input files, writing each to <name>.<ext> in the -o directory or next to the input
+    #[arg(long, value_name = "FILES", num_args = 1.., conflicts_with = "input")]
+    pub batch: Vec<PathBuf>,
 
-    /// Root type name for the generated code
-    #[arg(short = 'n', long, default_value = "Root")]
-    pub root_name: String,
+    /// Write a JSON array summarizing each --batch file's result to this path
+    #[arg(long, value_name = "PATH", requires = "batch")]
+    pub summary_json: Option<PathBuf>,
+
+    /// Stop a --batch run at the first file that fails instead of continuing
+    #[arg(long, requires = "batch")]
+    pub fail_fast: bool,
+
+    /// Keep running and regenerate the -o file whenever an input file changes, until Ctrl-C
+    #[arg(
+        long,
+        requires_all = ["input", "output"],
+        conflicts_with_all = ["batch", "diff_schema", "interactive"]
+    )]
+    pub watch: bool,
+
+    /// Input format (auto-detected from extension if not specified, otherwise JSON)
+    #[arg(short = 'f', long)]
+    pub input_format: Option<InputFormat>,
+
+    /// Output format (detected from the -o extension if not specified, otherwise TypeScript)
+    #[arg(short = 't', long)]
+    pub output_format: Option<OutputFormat>,
+
+    /// Root type name for the generated code [default: Root]
+    #[arg(short = 'n', long)]
+    pub root_name: Option<String>,
+
+    /// Root type name when reading from stdin, where there is no file to name it after
+    /// (ignored with -n or an input file) [default: Root]
+    #[arg(long, value_name = "NAME")]
+    pub stdin_name: Option<String>,
+
+    /// Name of the wrapper type for a root array; the element type then takes --root-name
+    #[arg(long, value_name = "NAME")]
+    pub wrapper_name: Option<String>,
+
+    /// For a root array of objects, make the element type the root (named by --root-name) with no wrapper; with --flatten, also emit a `{Root}List` alias of the array
+    #[arg(long, conflicts_with = "wrapper_name")]
+    pub item_as_root: bool,
+
+    /// Emit the wrapper around a root array as a type alias, e.g. `type Root = Vec<String>;` (for Rust, TypeScript, Zod, Python, Go)
+    #[arg(long, visible_alias = "flatten-single-field")]
+    pub flatten: bool,
+
+    /// Name every type below the root T1, T2, ... in declaration order; JSON keys are unchanged
+    #[arg(long)]
+    pub minify_identifiers: bool,
+
+    /// Also emit a NamePartial copy of every type with all fields optional, e.g. for PATCH bodies
+    #[arg(long)]
+    pub emit_partial: bool,
 
     /// Generate optional fields (for TypeScript/Python)
     #[arg(long)]
     pub optional_fields: bool,
 
+    /// Keep every field required even when samples omit it or set it to null, making null values nullable types instead
+    #[arg(long, conflicts_with = "optional_fields")]
+    pub no_optional_inference: bool,
+
     /// Use readonly modifier (for TypeScript)
     #[arg(long)]
     pub readonly: bool,
@@ -56,46 +124,418 @@ pub struct Cli {
     #[arg(long, default_value = "true")]
     pub public_fields: bool,
 
+    /// Add a catch-all member to unions and string enums so unexpected values still deserialize (for Rust, TypeScript, Zod, Python)
+    #[arg(long)]
+    pub enum_catchall: bool,
+
+    /// Indentation of the generated code: a width in spaces (1-8) or "tab" [default: the language's convention]
+    #[arg(long, value_name = "WIDTH|tab")]
+    pub indent: Option<Indent>,
+
+    /// Generate TryFrom<serde_json::Value> impls with per-field checks (for Rust)
+    #[arg(long)]
+    pub with_tryfrom: bool,
+
+    /// Add #[serde(deny_unknown_fields)] to structs not merged from arrays or maps (for Rust)
+    #[arg(long)]
+    pub deny_unknown: bool,
+
+    /// Emit single-field types as #[serde(transparent)] newtype structs, which (de)serialize as the bare field value (for Rust)
+    #[arg(long)]
+    pub newtype_wrappers: bool,
+
+    /// Declare PHP properties as promoted constructor parameters (for PHP)
+    #[arg(long)]
+    pub php_promoted: bool,
+
+    /// Ruby class style: struct (Struct.new) or dry-struct (for Ruby)
+    #[arg(long, value_name = "STYLE", default_value = "struct")]
+    pub ruby_style: RubyStyle,
+
+    /// C# type style: record (positional records) or class (classes with required properties)
+    #[arg(long, value_name = "STYLE", default_value = "record")]
+    pub csharp_style: CSharpStyle,
+
+    /// Mark records [<CLIMutable>] for serializers that need a default constructor (for F#)
+    #[arg(long)]
+    pub cli_mutable: bool,
+
+    /// Optional fields as pointer types or value types with omitempty (for Go)
+    #[arg(long, value_name = "MODE", default_value = "pointer")]
+    pub go_optional: GoOptional,
+
+    /// Package name of the generated file (for Go)
+    #[arg(long, value_name = "NAME", default_value = "models")]
+    pub go_package: String,
+
+    /// Clojure schema library: malli or spec (clojure.spec.alpha) (for Clojure)
+    #[arg(long, value_name = "STYLE", default_value = "malli")]
+    pub clojure_style: ClojureStyle,
+
+    /// Case convention for field names: Rust adds #[serde(rename_all)] to every struct, renaming only fields it misses; TypeScript renames properties, noting the original key
+    #[arg(long, value_name = "CASE")]
+    pub rename_all: Option<RenameAll>,
+
+    /// Rust type for unions: enum (untagged enum) or value (serde_json::Value)
+    #[arg(long, value_name = "REPR", default_value = "enum")]
+    pub union_repr: UnionRepr,
+
+    /// Rust type for arrays: vec, boxed-slice, or smallvec (needs smallvec with its serde feature)
+    #[arg(long, value_name = "TYPE", default_value = "vec")]
+    pub rust_array_type: RustArrayType,
+
+    /// Rust type for strings: string, or cow (Cow<'a, str> with an 'a lifetime on types holding strings) (for Rust)
+    #[arg(long, value_name = "TYPE", default_value = "string")]
+    pub rust_string_type: RustStringType,
+
+    /// TypeScript declaration style: interfaces, JSDoc typedefs for plain JS, or ambient .d.ts declarations
+    #[arg(long, value_name = "STYLE", default_value = "interface")]
+    pub ts_style: TsStyle,
+
+    /// Wrap ambient declarations in `declare namespace NAME` (for --ts-style dts)
+    #[arg(long, value_name = "NAME")]
+    pub namespace: Option<String>,
+
+    /// TypeScript/Zod type for decimal fields: number or string
+    #[arg(long, value_name = "TYPE", default_value = "number")]
+    pub ts_decimal: TsDecimal,
+
+    /// Write nullable unknown fields as `name?: unknown` instead of `name: unknown | null` (for TypeScript)
+    #[arg(long)]
+    pub collapse_optional_any: bool,
+
+    /// Python version for typing syntax: 3.8, 3.9, 3.10 or 3.12
+    #[arg(long, value_name = "VERSION", default_value = "3.8")]
+    pub python_target: PythonTarget,
+
+    /// Pydantic major version, which decides how constraints are written: 1 or 2
+    #[arg(long, value_name = "VERSION", default_value = "2")]
+    pub pydantic_version: PydanticVersion,
+
+    /// Python class style: Pydantic models, standard dataclasses, or TypedDicts with no runtime cost
+    #[arg(long, value_name = "STYLE", default_value = "pydantic")]
+    pub python_style: PythonStyle,
+
+    /// Abort if a single type would have more than N fields
+    #[arg(long, value_name = "N")]
+    pub max_fields: Option<usize>,
+
+    /// Treat integers in fields named like `*_at`, `*At` or `timestamp` as epoch date-times
+    #[arg(long)]
+    pub epoch_dates: bool,
+
+    /// Keep ISO 8601 date and date-time strings as plain strings
+    #[arg(long)]
+    pub no_date_inference: bool,
+
+    /// Keep UUID strings as plain strings
+    #[arg(long)]
+    pub no_uuid_inference: bool,
+
+    /// Most distinct values a repeated string field can have to become an enum, 0 to disable
+    #[arg(long, value_name = "N", default_value = "10")]
+    pub enum_threshold: usize,
+
+    /// Use integer key types for detected maps whose keys are all integers
+    #[arg(long)]
+    pub typed_map_keys: bool,
+
+    /// Type plural fields whose elements are distinct strings or integers as sets
+    #[arg(long)]
+    pub detect_sets: bool,
+
+    /// Name the element types of arrays and values of maps after the singular of the field name, e.g. servers: Vec<Server>
+    #[arg(long)]
+    pub singular_names: bool,
+
+    /// Record the number ranges and string lengths seen in the samples as constraints (for Python, and int32 in JTD)
+    #[arg(long)]
+    pub infer_constraints: bool,
+
+    /// Type values nested more than N levels below the root as Any instead of generating types
+    #[arg(long, value_name = "N")]
+    pub max_depth: Option<usize>,
+
+    /// Fail on input nesting arrays and objects (or GraphQL list types) more than N levels deep, instead of overflowing the stack. The JSON, YAML and TOML parsers stop at about 128 levels on their own, so higher values only apply to GraphQL SDL
+    #[arg(long, value_name = "N", default_value = "128")]
+    pub recursion_limit: usize,
+
+    /// Type unions of more than N members as Any
+    #[arg(long, value_name = "N", default_value = "8")]
+    pub max_union_members: usize,
+
+    /// Split arrays of objects by the string value of FIELD into one type per value, as a tagged union (Rust enum, TypeScript discriminated union)
+    #[arg(long, value_name = "FIELD")]
+    pub tagged_union: Option<String>,
+
+    /// Generate nested objects with identical fields as one shared type instead of one type each
+    #[arg(long)]
+    pub share_types: bool,
+
+    /// How to type fields that are only ever null: any, optional-any, or skip
+    #[arg(long, value_name = "MODE", default_value = "optional-any")]
+    pub null_only_type: NullOnlyType,
+
+    /// How to type fields that are null in some samples: auto (nullable, optional only if also missing), optional (field?), nullable (T | null), or both
+    #[arg(long, value_name = "POLICY", default_value = "auto")]
+    pub null_policy: NullPolicy,
+
+    /// How to type arrays mixing primitives with objects or arrays: union or any
+    #[arg(long, value_name = "REPR", default_value = "union")]
+    pub mixed_array_repr: MixedArrayRepr,
+
+    /// Order of fields within each type: source, alpha, or required-first
+    #[arg(long, value_name = "ORDER", default_value = "source")]
+    pub field_ordering: FieldOrdering,
+
+    /// JSON file mapping dotted field paths (e.g. "owner.id") to descriptions added as docs
+    #[arg(long, value_name = "FILE")]
+    pub descriptions: Option<PathBuf>,
+
+    /// JSON file pinning field types by dotted path (e.g. {"price": "decimal", "tags": {"type": "string[]", "optional": true}}), applied after inference
+    #[arg(long, value_name = "FILE")]
+    pub schema_overrides: Option<PathBuf>,
+
+    /// Generate only this type and the types it uses, e.g. one struct of a large response
+    #[arg(long, value_name = "TYPE")]
+    pub only_type: Option<String>,
+
+    /// Declare the root type first, then every other type after the types it references
+    #[arg(long)]
+    pub topological_order: bool,
+
+    /// Browse the inferred types at a line-based prompt before generating, toggling field optionality and renaming types; `quit` exits with code 0
+    #[arg(long, requires = "input", conflicts_with = "batch")]
+    pub interactive: bool,
+
+    /// Print how the schema inferred from NEW differs from OLD's, instead of generating code
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["OLD", "NEW"],
+        conflicts_with_all = ["input", "batch"]
+    )]
+    pub diff_schema: Vec<PathBuf>,
+
+    /// Comma-separated numeric fields to type as decimals, by name or dotted path with * wildcards (e.g. "amount,*.price")
+    #[arg(long, value_name = "PATTERNS", value_delimiter = ',')]
+    pub money_fields: Vec<String>,
+
+    /// Exit with an error if any field falls back to Any, listing each field path
+    #[arg(long, visible_alias = "strict")]
+    pub fail_on_any: bool,
+
+    /// Type-check generated Rust with the local rustc, failing on compile errors (for Rust)
+    #[arg(long)]
+    pub verify_compile: bool,
+
+    /// Directory caching inferred schemas, so unchanged input skips parsing and inference
+    #[arg(long, value_name = "DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Log inference decisions to stderr for debugging: -v for debug, -vv for trace
+    #[arg(short = 'v', long = "verbose", action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Write conversion statistics and per-type field counts as JSON to this path, or '-' for stdout (needs -o)
+    #[arg(long, value_name = "PATH", conflicts_with = "batch")]
+    pub report_json: Option<PathBuf>,
+
     /// Quiet mode - suppress visual report, only output generated code
     #[arg(short = 'q', long)]
     pub quiet: bool,
 
+    /// Width of the report boxes in columns (defaults to fit the terminal)
+    #[arg(long, value_name = "COLUMNS")]
+    pub report_width: Option<usize>,
+
+    /// Report style: fancy, ascii (no Unicode box drawing or emoji), or minimal (key: value lines)
+    #[arg(long, value_name = "THEME", default_value = "fancy")]
+    pub report_theme: ReportTheme,
+
     /// Disable colored output (useful for CI/CD pipelines)
     #[arg(long)]
     pub no_color: bool,
 
+    /// List the supported input and output formats with their file extensions, then exit
+    #[arg(long)]
+    pub list_formats: bool,
+
     /// Generate shell completions for the specified shell
     #[arg(long, value_name = "SHELL")]
     pub completions: Option<Shell>,
 }
 
+/// Encoding of the output file; stdout is always plain UTF-8
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputEncoding {
+    /// UTF-8 without a byte order mark
+    #[default]
+    Utf8,
+    /// UTF-8 starting with the `EF BB BF` byte order mark
+    Utf8Bom,
+}
+
+impl OutputEncoding {
+    /// Encode generated code for writing to a file
+    pub fn encode(self, output: &str) -> Vec<u8> {
+        match self {
+            OutputEncoding::Utf8 => output.as_bytes().to_vec(),
+            OutputEncoding::Utf8Bom => ["\u{feff}", output].concat().into_bytes(),
+        }
+    }
+}
+
 impl Cli {
     /// Convert CLI arguments to GeneratorOptions
     pub fn generator_options(&self) -> GeneratorOptions {
         GeneratorOptions {
-            root_name: self.root_name.clone(),
-            optional_fields: self.optional_fields,
-            readonly: self.readonly,
-            derive_macros: self
-                .derive
-                .split(',')
-                .map(|s| s.trim().to_string())
-                .collect(),
-            public_fields: self.public_fields,
+            root_name: self.root_name(),
+            wrapper_name: self.wrapper_name.clone(),
+            item_as_root: self.item_as_root,
+            minify_identifiers: self.minify_identifiers,
+            emit_partial: self.emit_partial,
+            optional_inference: !self.no_optional_inference,
+            shared: SharedOptions {
+                flatten: self.flatten,
+                optional_fields: self.optional_fields,
+                readonly: self.readonly,
+                public_fields: self.public_fields,
+                enum_catchall: self.enum_catchall,
+                indent: self.indent,
+                rename_all: self.rename_all,
+            },
+            rust: RustOptions {
+                derive_macros: self
+                    .derive
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .collect(),
+                with_tryfrom: self.with_tryfrom,
+                deny_unknown: self.deny_unknown,
+                newtype_wrappers: self.newtype_wrappers,
+                union_repr: self.union_repr,
+                array_type: self.rust_array_type,
+                string_type: self.rust_string_type,
+            },
+            typescript: TsOptions {
+                style: self.ts_style,
+                namespace: self.namespace.clone(),
+                decimal: self.ts_decimal,
+                collapse_optional_any: self.collapse_optional_any,
+            },
+            python: PythonOptions {
+                target: self.python_target,
+                pydantic: self.pydantic_version,
+                style: self.python_style,
+            },
+            php: PhpOptions {
+                promoted: self.php_promoted,
+            },
+            ruby: RubyOptions {
+                style: self.ruby_style,
+            },
+            csharp: CSharpOptions {
+                style: self.csharp_style,
+            },
+            fsharp: FSharpOptions {
+                cli_mutable: self.cli_mutable,
+            },
+            go: GoOptions {
+                optional: self.go_optional,
+                package: self.go_package.clone(),
+            },
+            clojure: ClojureOptions {
+                style: self.clojure_style,
+            },
+            max_fields: self.max_fields,
+            epoch_dates: self.epoch_dates,
+            iso_dates: !self.no_date_inference,
+            uuids: !self.no_uuid_inference,
+            enum_threshold: self.enum_threshold,
+            typed_map_keys: self.typed_map_keys,
+            detect_sets: self.detect_sets,
+            singular_names: self.singular_names,
+            infer_constraints: self.infer_constraints,
+            max_depth: self.max_depth,
+            recursion_limit: self.recursion_limit,
+            max_union_members: self.max_union_members,
+            tagged_union: self.tagged_union.clone(),
+            share_types: self.share_types,
+            null_only_type: self.null_only_type,
+            null_policy: self.null_policy,
+            mixed_array_repr: self.mixed_array_repr,
+            field_ordering: self.field_ordering,
+            descriptions: HashMap::new(),
+            money_fields: self.money_fields.clone(),
+        }
+    }
+
+    /// Read the field descriptions file, if one was given
+    pub fn read_descriptions(&self) -> Result<HashMap<String, String>> {
+        match &self.descriptions {
+            Some(path) => Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?),
+            None => Ok(HashMap::new()),
+        }
+    }
+
+    /// Read the schema overrides file, if one was given
+    pub fn read_schema_overrides(&self) -> Result<HashMap<String, FieldOverride>> {
+        match &self.schema_overrides {
+            Some(path) => Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?),
+            None => Ok(HashMap::new()),
         }
     }
 
-    /// Read input content from file or stdin
-    pub fn read_input(&self) -> io::Result<String> {
-        match &self.input {
-            Some(path) if path.to_string_lossy() != "-" => std::fs::read_to_string(path),
-            _ => {
-                // Read from stdin
-                let mut buffer = String::new();
-                io::stdin().read_to_string(&mut buffer)?;
-                Ok(buffer)
+    /// Root type name: `-n`, then `--stdin-name` for piped input, then `Root`
+    fn root_name(&self) -> String {
+        let stdin_name = self.stdin_name.as_ref().filter(|_| self.reads_stdin());
+        self.root_name
+            .as_ref()
+            .or(stdin_name)
+            .cloned()
+            .unwrap_or_else(|| "Root".to_string())
+    }
+
+    /// Reject argument combinations clap cannot express on its own
+    ///
+    /// `--report-json -` needs `-o`, since without it the generated code is
+    /// written to stdout too and the two would be interleaved.
+    pub fn check(&self) -> std::result::Result<(), clap::Error> {
+        let report_to_stdout = self
+            .report_json
+            .as_deref()
+            .is_some_and(|path| path.as_os_str() == "-");
+        if report_to_stdout && self.output.is_none() {
+            return Err(Self::command().error(
+                ErrorKind::ArgumentConflict,
+                "--report-json - writes to stdout, so the code needs an output file (-o)",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether input is read from stdin, i.e. no input file or `-`
+    pub fn reads_stdin(&self) -> bool {
+        self.batch.is_empty()
+            && match self.input.as_slice() {
+                [] => true,
+                [path] => path.to_string_lossy() == "-",
+                _ => false,
             }
+    }
+
+    /// Read the content of each input file, or of stdin, with its format
+    pub fn read_inputs(&self) -> io::Result<Vec<(String, InputFormat)>> {
+        if self.reads_stdin() {
+            let mut buffer = String::new();
+            io::stdin().read_to_string(&mut buffer)?;
+            return Ok(vec![(buffer, self.input_format.unwrap_or_default())]);
         }
+        self.input
+            .iter()
+            .map(|path| Ok((std::fs::read_to_string(path)?, self.detect_format_of(path))))
+            .collect()
     }
 
     /// Generate shell completions and print to stdout
@@ -104,17 +544,187 @@ impl Cli {
         generate(shell, &mut cmd, "alchemist", &mut io::stdout());
     }
 
-    /// Auto-detect input format from file extension
-    pub fn detect_input_format(&self) -> InputFormat {
-        if let Some(path) = &self.input {
-            if let Some(ext) = path.extension() {
-                return match ext.to_string_lossy().to_lowercase().as_str() {
-                    "yaml" | "yml" => InputFormat::Yaml,
-                    "toml" => InputFormat::Toml,
-                    _ => self.input_format,
-                };
-            }
-        }
+    /// Table of the supported input and output formats for `--list-formats`
+    pub fn format_list() -> String {
+        let inputs = InputFormat::value_variants()
+            .iter()
+            .map(|format| (*format, format.extensions().join(", ")));
+        let outputs = OutputFormat::value_variants().iter().map(|format| {
+            let options = GeneratorOptions::default();
+            let extension = generators::for_format(*format, options).file_extension();
+            (*format, extension.to_string())
+        });
+
+        let mut output = String::from("Input formats:\n");
+        output.extend(inputs.map(|(format, extensions)| format_row(format, &extensions)));
+        output.push_str("\nOutput formats:\n");
+        output.extend(outputs.map(|(format, extension)| format_row(format, &extension)));
+        output
+    }
+
+    /// Input format of a file: the specified format, otherwise the one of
+    /// its extension, falling back to JSON
+    pub fn detect_format_of(&self, path: &Path) -> InputFormat {
         self.input_format
+            .or_else(|| {
+                path.extension()
+                    .and_then(|ext| InputFormat::from_extension(&ext.to_string_lossy()))
+            })
+            .unwrap_or_default()
+    }
+
+    /// Output format: the specified format, otherwise the one of the `-o`
+    /// file's extension, falling back to TypeScript
+    ///
+    /// A `--batch` `-o` is a directory, so its name says nothing.
+    pub fn output_format(&self) -> OutputFormat {
+        self.output_format
+            .or_else(|| {
+                self.output
+                    .as_deref()
+                    .filter(|_| self.batch.is_empty())
+                    .and_then(Path::extension)
+                    .and_then(|ext| OutputFormat::from_extension(&ext.to_string_lossy()))
+            })
+            .unwrap_or(OutputFormat::Typescript)
+    }
+
+    /// Where a `--batch` input's code is written: `<stem>.<extension>` in the
+    /// `-o` directory, or next to the input without one
+    pub fn batch_output_path(&self, input: &Path, extension: &str) -> PathBuf {
+        let stem = input.file_stem().unwrap_or_default().to_string_lossy();
+        let file_name = format!("{}.{}", stem, extension);
+        match &self.output {
+            Some(dir) => dir.join(file_name),
+            None => input.with_file_name(file_name),
+        }
+    }
+}
+
+/// One `--list-formats` line: value name, extensions and description
+fn format_row(format: impl ValueEnum, extensions: &str) -> String {
+    let value = format
+        .to_possible_value()
+        .expect("formats have no skipped variants");
+    format!(
+        "  {:<12}{:<14}{}\n",
+        value.get_name(),
+        extensions,
+        value
+            .get_help()
+            .map(|help| help.to_string())
+            .unwrap_or_default()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root_name(args: &[&str]) -> String {
+        let args = std::iter::once("alchemist").chain(args.iter().copied());
+        Cli::parse_from(args).generator_options().root_name
+    }
+
+    #[test]
+    fn test_stdin_name_applies_only_to_stdin() {
+        assert_eq!(root_name(&[]), "Root");
+        assert_eq!(root_name(&["--stdin-name", "Event"]), "Event");
+        assert_eq!(root_name(&["-i", "-", "--stdin-name", "Event"]), "Event");
+        assert_eq!(
+            root_name(&["-i", "data.json", "--stdin-name", "Event"]),
+            "Root"
+        );
+        assert_eq!(root_name(&["-n", "User", "--stdin-name", "Event"]), "User");
+    }
+
+    #[test]
+    fn test_input_format_flag_beats_extension() {
+        let format_of = |args: &[&str], path: &str| {
+            let args = std::iter::once("alchemist").chain(args.iter().copied());
+            Cli::parse_from(args).detect_format_of(Path::new(path))
+        };
+
+        assert_eq!(format_of(&[], "data.yml"), InputFormat::Yaml);
+        assert_eq!(format_of(&[], "data.txt"), InputFormat::Json);
+        assert_eq!(format_of(&["-f", "json"], "data.yml"), InputFormat::Json);
+        assert_eq!(format_of(&["-f", "yaml"], "data.json"), InputFormat::Yaml);
+    }
+
+    #[test]
+    fn test_output_format_flag_beats_extension() {
+        let format_of = |args: &[&str]| {
+            let args = std::iter::once("alchemist").chain(args.iter().copied());
+            Cli::parse_from(args).output_format()
+        };
+
+        assert_eq!(format_of(&[]), OutputFormat::Typescript);
+        assert_eq!(format_of(&["-o", "types.rs"]), OutputFormat::Rust);
+        assert_eq!(format_of(&["-o", "models.PY"]), OutputFormat::Python);
+        assert_eq!(format_of(&["-o", "types.txt"]), OutputFormat::Typescript);
+        assert_eq!(
+            format_of(&["-t", "zod", "-o", "types.rs"]),
+            OutputFormat::Zod
+        );
+    }
+
+    #[test]
+    fn test_report_json_to_stdout_requires_output_file() {
+        let check = |args: &[&str]| {
+            let args = std::iter::once("alchemist").chain(args.iter().copied());
+            Cli::parse_from(args).check()
+        };
+
+        assert!(check(&["--report-json", "-"]).is_err());
+        assert!(check(&["--report-json", "-", "-o", "types.ts"]).is_ok());
+        assert!(check(&["--report-json", "report.json"]).is_ok());
+    }
+
+    #[test]
+    fn test_output_encoding_bom() {
+        assert_eq!(OutputEncoding::Utf8.encode("é"), "é".as_bytes());
+        assert_eq!(
+            OutputEncoding::Utf8Bom.encode("é"),
+            [0xEF, 0xBB, 0xBF, 0xC3, 0xA9]
+        );
+    }
+
+    #[test]
+    fn test_batch_output_path() {
+        let cli = Cli::parse_from(["alchemist", "--batch", "data/a.json", "-t", "rust"]);
+        assert_eq!(
+            cli.batch_output_path(Path::new("data/user.v1.json"), "rs"),
+            PathBuf::from("data/user.v1.rs")
+        );
+
+        let cli = Cli::parse_from([
+            "alchemist",
+            "--batch",
+            "a.json",
+            "-o",
+            "out",
+            "--stdin-name",
+            "Event",
+        ]);
+        assert_eq!(
+            cli.batch_output_path(Path::new("data/a.json"), "ts"),
+            PathBuf::from("out/a.ts")
+        );
+        assert_eq!(cli.generator_options().root_name, "Root");
+    }
+
+    #[test]
+    fn test_format_list_covers_every_format() {
+        let list = Cli::format_list();
+
+        assert!(list.contains("  yaml        yaml, yml     YAML format\n"));
+        assert!(
+            list.contains("  rust        rs            Rust structs with serde derive macros\n")
+        );
+        let rows = list.lines().filter(|line| line.starts_with("  ")).count();
+        assert_eq!(
+            rows,
+            InputFormat::value_variants().len() + OutputFormat::value_variants().len()
+        );
     }
 }
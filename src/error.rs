@@ -21,10 +21,70 @@ pub enum AlchemistError {
     #[error("Invalid input structure: {0}")]
     InvalidStructure(String),
 
+    /// The schema is internally inconsistent, see `Schema::validate`
+    #[error("Invalid schema: {}", .0.join("; "))]
+    InvalidSchema(Vec<String>),
+
+    /// A single type has more fields than the configured limit allows
+    #[error("Type '{type_name}' has {field_count} fields, exceeding the limit of {limit} (--max-fields)")]
+    TooManyFields {
+        type_name: String,
+        field_count: usize,
+        limit: usize,
+    },
+
+    /// Fields typed as `Any` remain and `--fail-on-any` is set
+    #[error("{} field(s) fell back to Any (--fail-on-any): {}", .paths.len(), .paths.join(", "))]
+    AnyFields { paths: Vec<String> },
+
+    /// Generated Rust failed to compile under `--verify-compile`
+    #[error("Generated code does not compile (--verify-compile):\n{0}")]
+    CompileError(String),
+
     /// IO error
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
+
+    /// The `--interactive` session was quit before generating
+    #[error("Interactive session quit before generating")]
+    Cancelled,
+}
+
+impl AlchemistError {
+    /// Process exit code for this error, stable for use in scripts
+    ///
+    /// `1` for input that could not be parsed, `2` for generation failures,
+    /// `3` for strict or validation checks and `4` for I/O errors. Quitting
+    /// an `--interactive` session is not a failure, so it exits with `0`.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Self::JsonParseError(_) | Self::YamlParseError(_) | Self::InvalidStructure(_) => 1,
+            Self::GenerationError(_) | Self::InvalidSchema(_) => 2,
+            Self::TooManyFields { .. } | Self::AnyFields { .. } | Self::CompileError(_) => 3,
+            Self::IoError(_) => 4,
+            Self::Cancelled => 0,
+        }
+    }
 }
 
 /// Result type alias for Alchemist operations
 pub type Result<T> = std::result::Result<T, AlchemistError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes() {
+        let parse_error = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+        assert_eq!(AlchemistError::from(parse_error).exit_code(), 1);
+        assert_eq!(
+            AlchemistError::GenerationError("empty".to_string()).exit_code(),
+            2
+        );
+        assert_eq!(AlchemistError::AnyFields { paths: vec![] }.exit_code(), 3);
+        let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+        assert_eq!(AlchemistError::from(io_error).exit_code(), 4);
+        assert_eq!(AlchemistError::Cancelled.exit_code(), 0);
+    }
+}
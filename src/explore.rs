@@ -0,0 +1,204 @@
+//! Interactive session for refining an inferred schema (`--interactive`)
+
+use alchemist::ast::Schema;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, Write};
+
+const HELP: &str = "\
+Commands:
+  tree                   Show the types nested under the root type
+  types                  List every type with its field count
+  show <Type>            List the fields of a type
+  optional <Type.field>  Toggle whether a field is optional
+  rename <Type> <Name>   Rename a type and every reference to it
+  done                   Generate code from the edited schema
+  quit                   Exit without generating
+";
+
+/// Browse and edit a schema, one command per line, until `done` or the end
+/// of input
+///
+/// Returns whether to go on and generate code; `quit` returns `false`.
+/// Invalid commands are reported and the session continues.
+pub fn explore(
+    schema: &mut Schema,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<bool> {
+    writeln!(
+        output,
+        "Exploring {} types, type 'help' for commands",
+        schema.types.len()
+    )?;
+    write!(output, "> ")?;
+    output.flush()?;
+
+    for line in input.lines() {
+        let line = line?;
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            [] => {}
+            ["done"] => return Ok(true),
+            ["quit"] => return Ok(false),
+            ["help"] => write!(output, "{}", HELP)?,
+            ["tree"] => write!(output, "{}", tree(schema))?,
+            ["types"] => {
+                for type_def in &schema.types {
+                    writeln!(
+                        output,
+                        "{} ({} fields)",
+                        type_def.name,
+                        type_def.fields.len()
+                    )?;
+                }
+            }
+            ["show", name] => match schema.types.iter().find(|t| t.name == *name) {
+                Some(type_def) => {
+                    for field in &type_def.fields {
+                        let marker = if field.optional { "?" } else { "" };
+                        writeln!(output, "  {}{}: {}", field.name, marker, field.field_type)?;
+                    }
+                }
+                None => writeln!(output, "No type named '{}'", name)?,
+            },
+            ["optional", path] => match toggle_optional(schema, path) {
+                Ok(true) => writeln!(output, "{} is now optional", path)?,
+                Ok(false) => writeln!(output, "{} is now required", path)?,
+                Err(message) => writeln!(output, "{}", message)?,
+            },
+            ["rename", from, to] => match rename_type(schema, from, to) {
+                Ok(()) => writeln!(output, "Renamed {} to {}", from, to)?,
+                Err(message) => writeln!(output, "{}", message)?,
+            },
+            _ => writeln!(output, "Unknown command '{}', type 'help'", line.trim())?,
+        }
+        write!(output, "> ")?;
+        output.flush()?;
+    }
+
+    // End of input, e.g. Ctrl-D, accepts the edits made so far
+    writeln!(output)?;
+    Ok(true)
+}
+
+/// Render the root type and the types its fields reference, indented by
+/// nesting; a type already shown is not expanded again
+fn tree(schema: &Schema) -> String {
+    fn visit(
+        schema: &Schema,
+        name: &str,
+        depth: usize,
+        seen: &mut HashSet<String>,
+        out: &mut String,
+    ) {
+        if !seen.insert(name.to_string()) {
+            return;
+        }
+        let Some(type_def) = schema.types.iter().find(|t| t.name == name) else {
+            return;
+        };
+        for field in &type_def.fields {
+            for referenced in field.field_type.referenced_types() {
+                let indent = "  ".repeat(depth + 1);
+                out.push_str(&format!("{}.{}: {}\n", indent, field.name, referenced));
+                visit(schema, referenced, depth + 1, seen, out);
+            }
+        }
+    }
+
+    let mut out = format!("{}\n", schema.root_name);
+    visit(schema, &schema.root_name, 0, &mut HashSet::new(), &mut out);
+    out
+}
+
+/// Flip the optionality of the field at `Type.field`, returning whether it
+/// is now optional
+fn toggle_optional(schema: &mut Schema, path: &str) -> Result<bool, String> {
+    let Some((type_name, field_name)) = path.split_once('.') else {
+        return Err(format!("Expected Type.field, got '{}'", path));
+    };
+    let field = schema
+        .types
+        .iter_mut()
+        .find(|t| t.name == type_name)
+        .ok_or_else(|| format!("No type named '{}'", type_name))?
+        .fields
+        .iter_mut()
+        .find(|f| f.name == field_name)
+        .ok_or_else(|| format!("No field '{}' in {}", field_name, type_name))?;
+
+    field.optional = !field.optional;
+    Ok(field.optional)
+}
+
+/// Rename a type, refusing names that are taken or not identifiers
+fn rename_type(schema: &mut Schema, from: &str, to: &str) -> Result<(), String> {
+    if !schema.types.iter().any(|t| t.name == from) {
+        return Err(format!("No type named '{}'", from));
+    }
+    if schema.types.iter().any(|t| t.name == to) {
+        return Err(format!("A type named '{}' already exists", to));
+    }
+    let is_identifier = to.starts_with(|c: char| c.is_ascii_alphabetic())
+        && to.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_identifier {
+        return Err(format!("'{}' is not a valid type name", to));
+    }
+
+    schema.rename_types(&HashMap::from([(from.to_string(), to.to_string())]));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alchemist::ast::FieldType;
+    use alchemist::generators::GeneratorOptions;
+    use alchemist::parser::parse_json;
+
+    fn session(schema: &mut Schema, commands: &str) -> (bool, String) {
+        let mut output = Vec::new();
+        let generate = explore(schema, commands.as_bytes(), &mut output).unwrap();
+        (generate, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn test_edits_apply_to_the_schema() {
+        let json = r#"{"id": 1, "owner": {"name": "x", "nick": null}}"#;
+        let mut schema = parse_json(json, &GeneratorOptions::default()).unwrap();
+
+        let (generate, output) = session(
+            &mut schema,
+            "tree\nshow Owner\noptional Owner.name\nrename Owner User\nrename Root User\nrename Nope X\nfly\ndone\nshow User\n",
+        );
+
+        assert!(generate);
+        assert!(output.contains("> Root\n  .owner: Owner\n"));
+        assert!(output.contains("  name: string\n"));
+        assert!(output.contains("Owner.name is now optional\n"));
+        assert!(output.contains("Renamed Owner to User\n"));
+        assert!(output.contains("A type named 'User' already exists\n"));
+        assert!(output.contains("No type named 'Nope'\n"));
+        assert!(output.contains("Unknown command 'fly', type 'help'\n"));
+        // Commands after `done` are not read
+        assert_eq!(output.matches("  name").count(), 1);
+
+        let user = schema.types.iter().find(|t| t.name == "User").unwrap();
+        assert!(user.fields[0].optional);
+        let root = schema.root_type().unwrap();
+        assert_eq!(
+            root.fields[1].field_type,
+            FieldType::Reference("User".to_string())
+        );
+    }
+
+    #[test]
+    fn test_quit_skips_generation() {
+        let mut schema = parse_json(r#"{"a": 1}"#, &GeneratorOptions::default()).unwrap();
+
+        assert!(!session(&mut schema, "quit\n").0);
+        // End of input keeps the edits
+        assert!(session(&mut schema, "optional Root.a\n").0);
+        assert!(schema.root_type().unwrap().fields[0].optional);
+    }
+}
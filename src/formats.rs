@@ -4,14 +4,44 @@ use clap::ValueEnum;
 use std::fmt;
 
 /// Supported input formats for parsing
-#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
 pub enum InputFormat {
     /// JSON format
+    #[default]
     Json,
     /// YAML format
     Yaml,
     /// TOML format
     Toml,
+    /// GraphQL SDL type definitions
+    Graphql,
+    /// CSV with a header row
+    Csv,
+    /// Newline-delimited JSON (JSON Lines), one object per line
+    Ndjson,
+}
+
+impl InputFormat {
+    /// Input format for a file extension, e.g. `yml`
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        let extension = extension.to_ascii_lowercase();
+        Self::value_variants()
+            .iter()
+            .copied()
+            .find(|format| format.extensions().contains(&extension.as_str()))
+    }
+
+    /// File extensions of the format, the usual one first
+    pub fn extensions(self) -> &'static [&'static str] {
+        match self {
+            InputFormat::Json => &["json"],
+            InputFormat::Yaml => &["yaml", "yml"],
+            InputFormat::Toml => &["toml"],
+            InputFormat::Graphql => &["graphql", "gql"],
+            InputFormat::Csv => &["csv"],
+            InputFormat::Ndjson => &["ndjson", "jsonl"],
+        }
+    }
 }
 
 impl fmt::Display for InputFormat {
@@ -20,6 +50,9 @@ impl fmt::Display for InputFormat {
             InputFormat::Json => write!(f, "json"),
             InputFormat::Yaml => write!(f, "yaml"),
             InputFormat::Toml => write!(f, "toml"),
+            InputFormat::Graphql => write!(f, "graphql"),
+            InputFormat::Csv => write!(f, "csv"),
+            InputFormat::Ndjson => write!(f, "ndjson"),
         }
     }
 }
@@ -33,8 +66,123 @@ pub enum OutputFormat {
     Typescript,
     /// Zod schema validation
     Zod,
-    /// Python Pydantic models
+    /// Python Pydantic models, dataclasses or TypedDicts
     Python,
+    /// Nim object types
+    Nim,
+    /// PHP 8 classes with typed properties
+    Php,
+    /// Ruby Struct or dry-struct classes
+    Ruby,
+    /// F# record types
+    #[value(name = "fsharp")]
+    FSharp,
+    /// Go structs with encoding/json tags
+    Go,
+    /// Kotlin data classes for kotlinx.serialization
+    Kotlin,
+    /// Swift structs conforming to Codable
+    Swift,
+    /// C# records or classes for System.Text.Json
+    #[value(name = "csharp")]
+    CSharp,
+    /// C structs for embedded and FFI use
+    C,
+    /// Clojure Malli schemas or clojure.spec specs
+    Clojure,
+    /// JSON Type Definition (RFC 8927) schemas
+    Jtd,
+    /// JSON Schema (draft-07) documents
+    JsonSchema,
+    /// GraphQL SDL object types
+    Graphql,
+    /// Apache Thrift IDL structs
+    Thrift,
+    /// Language-neutral pseudocode for documentation and review
+    Pseudo,
+}
+
+impl OutputFormat {
+    /// Output format for a file extension, e.g. `rs` or `py`
+    ///
+    /// `ts` maps to TypeScript, never Zod, whose schemas are also `.ts` files.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "rs" => Some(OutputFormat::Rust),
+            "ts" | "mts" | "cts" => Some(OutputFormat::Typescript),
+            "py" | "pyi" => Some(OutputFormat::Python),
+            "nim" => Some(OutputFormat::Nim),
+            "php" => Some(OutputFormat::Php),
+            "rb" => Some(OutputFormat::Ruby),
+            "fs" | "fsx" => Some(OutputFormat::FSharp),
+            "go" => Some(OutputFormat::Go),
+            "kt" | "kts" => Some(OutputFormat::Kotlin),
+            "swift" => Some(OutputFormat::Swift),
+            "cs" => Some(OutputFormat::CSharp),
+            "h" => Some(OutputFormat::C),
+            "clj" | "cljc" => Some(OutputFormat::Clojure),
+            "graphql" | "graphqls" | "gql" => Some(OutputFormat::Graphql),
+            "thrift" => Some(OutputFormat::Thrift),
+            _ => None,
+        }
+    }
+
+    /// Output format for a MIME type such as `text/x-rust`
+    ///
+    /// Parameters like `; charset=utf-8` are ignored. TypeScript MIME types
+    /// map to TypeScript rather than Zod, and `application/json` and
+    /// `text/plain` to nothing since they do not say what kind of schema is
+    /// wanted.
+    pub fn from_mime(mime: &str) -> Option<Self> {
+        let essence = mime.split(';').next().unwrap_or_default().trim();
+        match essence.to_ascii_lowercase().as_str() {
+            "text/x-rust" | "text/rust" => Some(OutputFormat::Rust),
+            "application/typescript" | "text/typescript" | "application/x-typescript" => {
+                Some(OutputFormat::Typescript)
+            }
+            "text/x-python" | "text/python" | "application/x-python" => Some(OutputFormat::Python),
+            "text/x-nim" => Some(OutputFormat::Nim),
+            "application/x-httpd-php" | "text/x-php" | "application/x-php" => {
+                Some(OutputFormat::Php)
+            }
+            "text/x-ruby" | "application/x-ruby" => Some(OutputFormat::Ruby),
+            "text/x-fsharp" => Some(OutputFormat::FSharp),
+            "text/x-go" => Some(OutputFormat::Go),
+            "text/x-kotlin" => Some(OutputFormat::Kotlin),
+            "text/x-swift" => Some(OutputFormat::Swift),
+            "text/x-csharp" => Some(OutputFormat::CSharp),
+            "text/x-c" | "text/x-chdr" => Some(OutputFormat::C),
+            "text/x-clojure" | "application/x-clojure" => Some(OutputFormat::Clojure),
+            "application/x-thrift" => Some(OutputFormat::Thrift),
+            "application/schema+json" => Some(OutputFormat::JsonSchema),
+            "application/graphql" => Some(OutputFormat::Graphql),
+            _ => None,
+        }
+    }
+
+    /// MIME type of the generated code
+    pub fn to_mime(self) -> &'static str {
+        match self {
+            OutputFormat::Rust => "text/x-rust",
+            OutputFormat::Typescript | OutputFormat::Zod => "application/typescript",
+            OutputFormat::Python => "text/x-python",
+            OutputFormat::Nim => "text/x-nim",
+            OutputFormat::Php => "application/x-httpd-php",
+            OutputFormat::Ruby => "text/x-ruby",
+            OutputFormat::FSharp => "text/x-fsharp",
+            OutputFormat::Go => "text/x-go",
+            OutputFormat::Kotlin => "text/x-kotlin",
+            OutputFormat::Swift => "text/x-swift",
+            OutputFormat::CSharp => "text/x-csharp",
+            OutputFormat::C => "text/x-chdr",
+            OutputFormat::Clojure => "text/x-clojure",
+            OutputFormat::Jtd => "application/json",
+            OutputFormat::JsonSchema => "application/schema+json",
+            OutputFormat::Graphql => "application/graphql",
+            OutputFormat::Thrift => "application/x-thrift",
+            OutputFormat::Pseudo => "text/plain",
+        }
+    }
 }
 
 impl fmt::Display for OutputFormat {
@@ -44,6 +192,21 @@ impl fmt::Display for OutputFormat {
             OutputFormat::Typescript => write!(f, "typescript"),
             OutputFormat::Zod => write!(f, "zod"),
             OutputFormat::Python => write!(f, "python"),
+            OutputFormat::Nim => write!(f, "nim"),
+            OutputFormat::Php => write!(f, "php"),
+            OutputFormat::Ruby => write!(f, "ruby"),
+            OutputFormat::FSharp => write!(f, "fsharp"),
+            OutputFormat::Go => write!(f, "go"),
+            OutputFormat::Kotlin => write!(f, "kotlin"),
+            OutputFormat::Swift => write!(f, "swift"),
+            OutputFormat::CSharp => write!(f, "csharp"),
+            OutputFormat::C => write!(f, "c"),
+            OutputFormat::Clojure => write!(f, "clojure"),
+            OutputFormat::Jtd => write!(f, "jtd"),
+            OutputFormat::JsonSchema => write!(f, "json-schema"),
+            OutputFormat::Graphql => write!(f, "graphql"),
+            OutputFormat::Thrift => write!(f, "thrift"),
+            OutputFormat::Pseudo => write!(f, "pseudo"),
         }
     }
 }
@@ -57,6 +220,9 @@ mod tests {
         assert_eq!(InputFormat::Json.to_string(), "json");
         assert_eq!(InputFormat::Yaml.to_string(), "yaml");
         assert_eq!(InputFormat::Toml.to_string(), "toml");
+        assert_eq!(InputFormat::Graphql.to_string(), "graphql");
+        assert_eq!(InputFormat::Csv.to_string(), "csv");
+        assert_eq!(InputFormat::Ndjson.to_string(), "ndjson");
     }
 
     #[test]
@@ -65,5 +231,74 @@ mod tests {
         assert_eq!(OutputFormat::Typescript.to_string(), "typescript");
         assert_eq!(OutputFormat::Zod.to_string(), "zod");
         assert_eq!(OutputFormat::Python.to_string(), "python");
+        assert_eq!(OutputFormat::Nim.to_string(), "nim");
+        assert_eq!(OutputFormat::Php.to_string(), "php");
+        assert_eq!(OutputFormat::Ruby.to_string(), "ruby");
+        assert_eq!(OutputFormat::FSharp.to_string(), "fsharp");
+        assert_eq!(OutputFormat::Go.to_string(), "go");
+        assert_eq!(OutputFormat::Kotlin.to_string(), "kotlin");
+        assert_eq!(OutputFormat::Swift.to_string(), "swift");
+        assert_eq!(OutputFormat::CSharp.to_string(), "csharp");
+        assert_eq!(OutputFormat::C.to_string(), "c");
+        assert_eq!(OutputFormat::Clojure.to_string(), "clojure");
+        assert_eq!(OutputFormat::Jtd.to_string(), "jtd");
+        assert_eq!(OutputFormat::JsonSchema.to_string(), "json-schema");
+        assert_eq!(OutputFormat::Graphql.to_string(), "graphql");
+        assert_eq!(OutputFormat::Thrift.to_string(), "thrift");
+        assert_eq!(OutputFormat::Pseudo.to_string(), "pseudo");
+    }
+
+    #[test]
+    fn test_input_format_from_extension() {
+        assert_eq!(InputFormat::from_extension("json"), Some(InputFormat::Json));
+        assert_eq!(InputFormat::from_extension("yaml"), Some(InputFormat::Yaml));
+        assert_eq!(InputFormat::from_extension("yml"), Some(InputFormat::Yaml));
+        assert_eq!(InputFormat::from_extension("YML"), Some(InputFormat::Yaml));
+        assert_eq!(InputFormat::from_extension("toml"), Some(InputFormat::Toml));
+        assert_eq!(
+            InputFormat::from_extension("gql"),
+            Some(InputFormat::Graphql)
+        );
+        assert_eq!(InputFormat::from_extension("csv"), Some(InputFormat::Csv));
+        assert_eq!(
+            InputFormat::from_extension("jsonl"),
+            Some(InputFormat::Ndjson)
+        );
+        assert_eq!(InputFormat::from_extension("txt"), None);
+    }
+
+    #[test]
+    fn test_output_format_from_extension() {
+        assert_eq!(OutputFormat::from_extension("rs"), Some(OutputFormat::Rust));
+        assert_eq!(
+            OutputFormat::from_extension("TS"),
+            Some(OutputFormat::Typescript)
+        );
+        assert_eq!(
+            OutputFormat::from_extension("py"),
+            Some(OutputFormat::Python)
+        );
+        assert_eq!(
+            OutputFormat::from_extension("fsx"),
+            Some(OutputFormat::FSharp)
+        );
+        assert_eq!(OutputFormat::from_extension("json"), None);
+    }
+
+    #[test]
+    fn test_output_format_mime_round_trip() {
+        assert_eq!(
+            OutputFormat::from_mime("text/x-python; charset=utf-8"),
+            Some(OutputFormat::Python)
+        );
+        assert_eq!(OutputFormat::from_mime("application/json"), None);
+        for format in OutputFormat::value_variants() {
+            let expected = match format {
+                OutputFormat::Zod => Some(OutputFormat::Typescript),
+                OutputFormat::Jtd | OutputFormat::Pseudo => None,
+                other => Some(*other),
+            };
+            assert_eq!(OutputFormat::from_mime(format.to_mime()), expected);
+        }
     }
 }
@@ -0,0 +1,293 @@
+//! C struct generator
+
+use crate::ast::{FieldType, Schema, TypeDef};
+use crate::error::{AlchemistError, Result};
+use crate::generators::{split_nullable_union, CodeGenerator, Indent, SharedOptions};
+use crate::utils::to_snake_case;
+use std::collections::HashSet;
+
+/// C keywords, which get a trailing underscore as member names
+const C_KEYWORDS: &[&str] = &[
+    "auto", "bool", "break", "case", "char", "const", "continue", "default", "do", "double",
+    "else", "enum", "extern", "float", "for", "goto", "if", "inline", "int", "long", "register",
+    "restrict", "return", "short", "signed", "sizeof", "static", "struct", "switch", "typedef",
+    "union", "unsigned", "void", "volatile", "while",
+];
+
+/// C struct generator
+///
+/// Structs are declared in dependency order and embed the structs they
+/// reference. Arrays become a pointer and a `_len` member, optional fields
+/// pointers that are `NULL` when absent. Types C cannot express directly,
+/// such as maps and unions, are `void*` with the original type in a comment.
+pub struct CGenerator {
+    shared: SharedOptions,
+    /// Whitespace of one indentation level
+    indent: String,
+}
+
+impl CGenerator {
+    /// Create a new C generator with the shared options
+    pub fn new(shared: SharedOptions) -> Self {
+        let indent = shared.indent_unit(Indent::Spaces(4));
+        Self { shared, indent }
+    }
+
+    /// Generate a C struct from a type definition, given the structs
+    /// declared before it
+    fn generate_struct(&self, type_def: &TypeDef, declared: &HashSet<&str>) -> String {
+        let indent = &self.indent;
+        let mut output = String::new();
+
+        if let Some(doc) = &type_def.doc {
+            output.push_str(&format!("/* {} */\n", doc));
+        }
+        output.push_str(&format!("typedef struct {} {{\n", type_def.name));
+
+        // Standard C does not allow empty structs
+        if type_def.fields.is_empty() {
+            output.push_str(&format!(
+                "{}char unused; /* no fields were observed */\n",
+                indent
+            ));
+        }
+
+        let mut used = HashSet::new();
+        for field in &type_def.fields {
+            if let Some(doc) = &field.doc {
+                output.push_str(&format!("{}/* {} */\n", indent, doc));
+            }
+
+            let name = unique_name(member_name(&field.name), &mut used);
+            let optional = field.optional || self.shared.optional_fields;
+            let field_type = match &field.field_type {
+                FieldType::Optional(inner) => inner,
+                field_type => field_type,
+            };
+
+            match field_type {
+                FieldType::Array(element) | FieldType::Set(element) => {
+                    // A NULL pointer already stands for a missing array
+                    let (element_type, note) = self.c_type(element, declared);
+                    output.push_str(&format!(
+                        "{}{}* {};{}\n",
+                        indent,
+                        element_type,
+                        name,
+                        comment(note)
+                    ));
+                    output.push_str(&format!("{}size_t {}_len;\n", indent, name));
+                }
+                _ => {
+                    let (mut c_type, note) = self.c_type(&field.field_type, declared);
+                    if optional {
+                        c_type = pointer_to(c_type);
+                    }
+                    output.push_str(&format!(
+                        "{}{} {};{}\n",
+                        indent,
+                        c_type,
+                        name,
+                        comment(note)
+                    ));
+                }
+            }
+        }
+
+        output.push_str(&format!("}} {};\n", type_def.name));
+        output
+    }
+
+    /// Convert AST FieldType to a C type, with a note on what a `void*`
+    /// fallback or text representation holds
+    fn c_type(&self, field_type: &FieldType, declared: &HashSet<&str>) -> (String, Option<String>) {
+        match field_type {
+            FieldType::String | FieldType::Enum(_) | FieldType::Uuid => ("char*".to_string(), None),
+            FieldType::DateTime(format) if !format.is_epoch() => {
+                ("char*".to_string(), Some(format.description().to_string()))
+            }
+            FieldType::Integer | FieldType::DateTime(_) => ("int64_t".to_string(), None),
+            FieldType::Float => ("double".to_string(), None),
+            FieldType::Decimal => ("char*".to_string(), Some("decimal digits".to_string())),
+            FieldType::Boolean => ("bool".to_string(), None),
+            // A struct not yet complete, in a reference cycle, can only be pointed to
+            FieldType::Reference(name) if declared.contains(name.as_str()) => (name.clone(), None),
+            FieldType::Reference(name) => (format!("struct {}*", name), None),
+            FieldType::Optional(inner) => {
+                let (c_type, note) = self.c_type(inner, declared);
+                (pointer_to(c_type), note)
+            }
+            FieldType::Union(types) => match split_nullable_union(types) {
+                (members, nullable) if members.len() == 1 => {
+                    let (c_type, note) = self.c_type(members[0], declared);
+                    if nullable {
+                        (pointer_to(c_type), note)
+                    } else {
+                        (c_type, note)
+                    }
+                }
+                _ => ("void*".to_string(), Some(field_type.to_string())),
+            },
+            FieldType::Array(_)
+            | FieldType::Set(_)
+            | FieldType::Map(_, _)
+            | FieldType::Null
+            | FieldType::Any => ("void*".to_string(), Some(field_type.to_string())),
+        }
+    }
+}
+
+/// Make a C type a pointer, unless it already is one
+fn pointer_to(c_type: String) -> String {
+    if c_type.ends_with('*') {
+        c_type
+    } else {
+        c_type + "*"
+    }
+}
+
+/// Render a note as a trailing comment
+fn comment(note: Option<String>) -> String {
+    note.map(|note| format!(" /* {} */", note))
+        .unwrap_or_default()
+}
+
+/// Get the snake_case member name of a key, with a trailing underscore when
+/// it is a keyword
+fn member_name(key: &str) -> String {
+    let mut name: String = to_snake_case(key)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    if C_KEYWORDS.contains(&name.as_str()) {
+        name.push('_');
+    }
+    name
+}
+
+/// Suffix a name with a number until no earlier member uses it
+fn unique_name(name: String, used: &mut HashSet<String>) -> String {
+    let mut candidate = name.clone();
+    let mut counter = 1;
+    while !used.insert(candidate.clone()) {
+        counter += 1;
+        candidate = format!("{}{}", name, counter);
+    }
+    candidate
+}
+
+impl CodeGenerator for CGenerator {
+    fn generate(&self, schema: &Schema) -> Result<String> {
+        if schema.types.is_empty() {
+            return Err(AlchemistError::GenerationError(
+                "Schema has no types to generate".to_string(),
+            ));
+        }
+
+        // Structs must be declared before the structs that embed them
+        let mut declared = HashSet::new();
+        let mut structs = Vec::new();
+        for type_def in schema.iter_topological(false) {
+            structs.push(self.generate_struct(type_def, &declared));
+            declared.insert(type_def.name.as_str());
+        }
+
+        let guard = format!("{}_TYPES_H", member_name(&schema.root_name).to_uppercase());
+        let mut output = String::new();
+        output.push_str("/* Generated by Alchemist */\n");
+        output.push_str("/* Do not edit manually */\n\n");
+        output.push_str(&format!("#ifndef {}\n#define {}\n\n", guard, guard));
+
+        // Include only the headers the structs use
+        let uses = |text: &str| structs.iter().any(|s| s.contains(text));
+        let headers = [
+            ("stdbool.h", uses(&format!("{}bool", self.indent))),
+            ("stddef.h", uses(&format!("{}size_t ", self.indent))),
+            ("stdint.h", uses("int64_t")),
+        ];
+        let mut any_header = false;
+        for (header, _) in headers.iter().filter(|(_, used)| *used) {
+            output.push_str(&format!("#include <{}>\n", header));
+            any_header = true;
+        }
+        if any_header {
+            output.push('\n');
+        }
+
+        output.push_str(&structs.join("\n"));
+        output.push_str(&format!("\n#endif /* {} */\n", guard));
+
+        Ok(output)
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "h"
+    }
+
+    fn name(&self) -> &'static str {
+        "C"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Field;
+    use crate::generators::GeneratorOptions;
+    use crate::parser::parse_json;
+
+    fn generate_from_json(json: &str) -> String {
+        let options = GeneratorOptions::default();
+        let schema = parse_json(json, &options).unwrap();
+        CGenerator::new(options.shared).generate(&schema).unwrap()
+    }
+
+    #[test]
+    fn test_generate_structs() {
+        let output = generate_from_json(
+            r#"{"id": 1, "ratio": 0.5, "active": true, "tags": ["a"], "owner": {"name": "x", "type": 1, "fn": 2}, "items": [{"a": 1}, {"b": "x"}], "mixed": [1, "a"]}"#,
+        );
+
+        assert!(output.contains(
+            "#ifndef ROOT_TYPES_H\n#define ROOT_TYPES_H\n\n#include <stdbool.h>\n#include <stddef.h>\n#include <stdint.h>\n"
+        ));
+        assert!(output.contains(
+            "typedef struct Root {\n    int64_t id;\n    double ratio;\n    bool active;\n    char** tags;\n    size_t tags_len;\n    Owner owner;\n    Items* items;\n    size_t items_len;\n"
+        ));
+        assert!(output.contains("    void** mixed; /* integer | string */\n"));
+        assert!(output.contains(
+            "typedef struct Owner {\n    char* name;\n    int64_t type;\n    int64_t fn;\n} Owner;\n"
+        ));
+        assert!(
+            output.contains("typedef struct Items {\n    int64_t* a;\n    char* b;\n} Items;\n")
+        );
+        assert!(output.ends_with("} Root;\n\n#endif /* ROOT_TYPES_H */\n"));
+        // Embedded structs are declared first
+        assert!(output.find("} Owner;").unwrap() < output.find("typedef struct Root").unwrap());
+    }
+
+    #[test]
+    fn test_cycles_maps_and_member_names() {
+        let mut schema = Schema::new("Node");
+        let mut node = TypeDef::new("Node");
+        node.add_field(Field::new("next", FieldType::Reference("Node".to_string())).optional());
+        node.add_field(Field::new(
+            "attrs",
+            FieldType::Map(Box::new(FieldType::String), Box::new(FieldType::Integer)),
+        ));
+        node.add_field(Field::new("default", FieldType::Boolean));
+        node.add_field(Field::new("2fa", FieldType::Boolean));
+        schema.add_type(node);
+        let output = CGenerator::new(SharedOptions::default())
+            .generate(&schema)
+            .unwrap();
+
+        assert!(output.contains(
+            "typedef struct Node {\n    struct Node* next;\n    void* attrs; /* map<string, integer> */\n    bool default_;\n    bool _2fa;\n} Node;\n"
+        ));
+        assert!(!output.contains("stdint.h"));
+    }
+}
@@ -0,0 +1,372 @@
+//! Clojure Malli and clojure.spec generator
+
+use crate::ast::{Field, FieldType, Schema, TypeDef};
+use crate::error::{AlchemistError, Result};
+use crate::generators::{split_nullable_union, CodeGenerator, Indent, SharedOptions};
+use crate::utils::to_snake_case;
+use clap::ValueEnum;
+use serde::Deserialize;
+
+/// Schema library targeted by the Clojure generator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ClojureStyle {
+    /// Malli vector schemas, one `def` per type
+    #[default]
+    Malli,
+    /// `clojure.spec.alpha` specs with `s/keys` maps
+    Spec,
+}
+
+/// Options of the Clojure generator
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ClojureOptions {
+    /// Schema library to target
+    #[serde(rename = "clojure_style")]
+    pub style: ClojureStyle,
+}
+
+/// Clojure generator
+///
+/// Malli schemas reference nested types through their vars, so types are
+/// defined before use.
+pub struct ClojureGenerator {
+    options: ClojureOptions,
+    shared: SharedOptions,
+    /// Whitespace of one indentation level
+    indent: String,
+}
+
+impl ClojureGenerator {
+    /// Create a new Clojure generator with its own and the shared options
+    pub fn new(options: ClojureOptions, shared: SharedOptions) -> Self {
+        let indent = shared.indent_unit(Indent::Spaces(2));
+        Self {
+            options,
+            shared,
+            indent,
+        }
+    }
+
+    /// Generate a Malli `[:map ...]` schema from a type definition
+    fn generate_malli(&self, type_def: &TypeDef) -> String {
+        let mut output = String::new();
+
+        if let Some(doc) = &type_def.doc {
+            output.push_str(&format!(";; {}\n", doc));
+        }
+        output.push_str(&format!("(def {}\n{}[:map", type_def.name, self.indent));
+
+        // Entries line up one column inside the `[:map`
+        let entry_indent = format!("\n{} ", self.indent);
+        for field in &type_def.fields {
+            output.push_str(&entry_indent);
+            if let Some(doc) = &field.doc {
+                output.push_str(&format!(";; {}{}", doc, entry_indent));
+            }
+            let key = keyword(&field.name).unwrap_or_else(|| format!("{:?}", field.name));
+            let properties = if self.is_optional(field) {
+                " {:optional true}"
+            } else {
+                ""
+            };
+            output.push_str(&format!(
+                "[{}{} {}]",
+                key,
+                properties,
+                self.field_type_to_malli(&field.field_type)
+            ));
+        }
+
+        output.push_str("])\n");
+        output
+    }
+
+    /// Generate `s/def` specs for the fields of a type and the type itself
+    fn generate_spec(&self, type_def: &TypeDef, ns: &str) -> String {
+        let mut output = String::new();
+        let field_ns = format!("{}.{}", ns, kebab_case(&type_def.name));
+        let mut required = Vec::new();
+        let mut optional = Vec::new();
+
+        for field in &type_def.fields {
+            // `s/keys` matches unqualified keys by the name of their spec
+            let Some(key) = keyword(&field.name) else {
+                output.push_str(&format!(
+                    ";; {:?} is not a valid keyword and is not checked\n",
+                    field.name
+                ));
+                continue;
+            };
+            if let Some(doc) = &field.doc {
+                output.push_str(&format!(";; {}\n", doc));
+            }
+            let spec = format!(":{}/{}", field_ns, &key[1..]);
+            output.push_str(&format!(
+                "(s/def {} {})\n",
+                spec,
+                self.field_type_to_spec(&field.field_type)
+            ));
+            if self.is_optional(field) {
+                optional.push(spec);
+            } else {
+                required.push(spec);
+            }
+        }
+
+        if let Some(doc) = &type_def.doc {
+            output.push_str(&format!(";; {}\n", doc));
+        }
+        let mut keys = String::new();
+        if !required.is_empty() {
+            keys.push_str(&format!(" :req-un [{}]", required.join(" ")));
+        }
+        if !optional.is_empty() {
+            keys.push_str(&format!(" :opt-un [{}]", optional.join(" ")));
+        }
+        output.push_str(&format!("(s/def ::{} (s/keys{}))\n", type_def.name, keys));
+        output
+    }
+
+    /// Whether a field may be missing from the data
+    fn is_optional(&self, field: &Field) -> bool {
+        field.optional || self.shared.optional_fields
+    }
+
+    /// Convert AST FieldType to a Malli schema
+    fn field_type_to_malli(&self, field_type: &FieldType) -> String {
+        match field_type {
+            FieldType::String | FieldType::Uuid => ":string".to_string(),
+            FieldType::Enum(values) => format!("[:enum {}]", quoted(values)),
+            FieldType::DateTime(format) if !format.is_epoch() => ":string".to_string(),
+            FieldType::Integer | FieldType::DateTime(_) => ":int".to_string(),
+            FieldType::Float | FieldType::Decimal => ":double".to_string(),
+            FieldType::Boolean => ":boolean".to_string(),
+            FieldType::Null => ":nil".to_string(),
+            FieldType::Any => ":any".to_string(),
+            FieldType::Array(inner) | FieldType::Set(inner) => {
+                format!("[:vector {}]", self.field_type_to_malli(inner))
+            }
+            FieldType::Optional(inner) => format!("[:maybe {}]", self.field_type_to_malli(inner)),
+            FieldType::Reference(name) => name.clone(),
+            FieldType::Union(types) => {
+                let (members, nullable) = split_nullable_union(types);
+                let members: Vec<String> = members
+                    .into_iter()
+                    .map(|t| self.field_type_to_malli(t))
+                    .collect();
+                let union = match members.as_slice() {
+                    [] => return ":nil".to_string(),
+                    [single] => single.clone(),
+                    _ => format!("[:or {}]", members.join(" ")),
+                };
+                if nullable {
+                    format!("[:maybe {}]", union)
+                } else {
+                    union
+                }
+            }
+            // JSON object keys are strings
+            FieldType::Map(_, value) => {
+                format!("[:map-of :string {}]", self.field_type_to_malli(value))
+            }
+        }
+    }
+
+    /// Convert AST FieldType to a spec form
+    fn field_type_to_spec(&self, field_type: &FieldType) -> String {
+        match field_type {
+            FieldType::String | FieldType::Uuid => "string?".to_string(),
+            // Sets are predicates testing membership
+            FieldType::Enum(values) => format!("#{{{}}}", quoted(values)),
+            FieldType::DateTime(format) if !format.is_epoch() => "string?".to_string(),
+            FieldType::Integer | FieldType::DateTime(_) => "int?".to_string(),
+            FieldType::Float => "double?".to_string(),
+            FieldType::Decimal => "number?".to_string(),
+            FieldType::Boolean => "boolean?".to_string(),
+            FieldType::Null => "nil?".to_string(),
+            FieldType::Any => "any?".to_string(),
+            FieldType::Array(inner) | FieldType::Set(inner) => format!(
+                "(s/coll-of {} :kind vector?)",
+                self.field_type_to_spec(inner)
+            ),
+            FieldType::Optional(inner) => format!("(s/nilable {})", self.field_type_to_spec(inner)),
+            FieldType::Reference(name) => format!("::{}", name),
+            FieldType::Union(types) => {
+                let (members, nullable) = split_nullable_union(types);
+                let union = match members.as_slice() {
+                    [] => return "nil?".to_string(),
+                    [single] => self.field_type_to_spec(single),
+                    _ => {
+                        // Each branch of `s/or` needs a distinct tag
+                        let branches: Vec<String> = members
+                            .iter()
+                            .enumerate()
+                            .map(|(i, t)| {
+                                let mut tag = spec_tag(t);
+                                if members.iter().filter(|m| spec_tag(m) == tag).count() > 1 {
+                                    tag = format!("{}-{}", tag, i);
+                                }
+                                format!(":{} {}", tag, self.field_type_to_spec(t))
+                            })
+                            .collect();
+                        format!("(s/or {})", branches.join(" "))
+                    }
+                };
+                if nullable {
+                    format!("(s/nilable {})", union)
+                } else {
+                    union
+                }
+            }
+            FieldType::Map(_, value) => {
+                format!("(s/map-of string? {})", self.field_type_to_spec(value))
+            }
+        }
+    }
+}
+
+/// Enum values as space-separated string literals
+fn quoted(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|value| format!("\"{}\"", value))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Tag prefix naming the kind of an `s/or` branch
+fn spec_tag(field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::String | FieldType::Enum(_) => "string".to_string(),
+        FieldType::DateTime(format) if !format.is_epoch() => "string".to_string(),
+        FieldType::Integer | FieldType::DateTime(_) => "int".to_string(),
+        FieldType::Float | FieldType::Decimal => "number".to_string(),
+        FieldType::Boolean => "boolean".to_string(),
+        FieldType::Array(_) | FieldType::Set(_) => "vector".to_string(),
+        FieldType::Map(_, _) => "map".to_string(),
+        FieldType::Reference(name) => kebab_case(name),
+        _ => "value".to_string(),
+    }
+}
+
+/// Convert a type name to kebab-case for namespaces and tags
+fn kebab_case(name: &str) -> String {
+    to_snake_case(name).replace('_', "-")
+}
+
+/// Render a field name as a keyword, or `None` if it cannot be one
+fn keyword(name: &str) -> Option<String> {
+    let symbol_char = |c: char| c.is_ascii_alphanumeric() || "*+!-_?<>=.".contains(c);
+    let valid = name
+        .chars()
+        .next()
+        .is_some_and(|c| !c.is_ascii_digit() && symbol_char(c))
+        && name.chars().all(symbol_char);
+    valid.then(|| format!(":{}", name))
+}
+
+impl CodeGenerator for ClojureGenerator {
+    fn generate(&self, schema: &Schema) -> Result<String> {
+        if schema.types.is_empty() {
+            return Err(AlchemistError::GenerationError(
+                "Schema has no types to generate".to_string(),
+            ));
+        }
+
+        let mut output = String::new();
+
+        output.push_str(";; Generated by Alchemist\n");
+        output.push_str(";; Do not edit manually\n\n");
+
+        let root_ns = kebab_case(&schema.root_name);
+        let definitions: Vec<String> = match self.options.style {
+            ClojureStyle::Malli => {
+                output.push_str(&format!("(ns {}.schema)\n\n", root_ns));
+                // Vars must be defined before the schemas that use them
+                schema
+                    .iter_topological(false)
+                    .map(|type_def| self.generate_malli(type_def))
+                    .collect()
+            }
+            ClojureStyle::Spec => {
+                let ns = format!("{}.spec", root_ns);
+                output.push_str(&format!(
+                    "(ns {}\n{}(:require [clojure.spec.alpha :as s]))\n\n",
+                    ns, self.indent
+                ));
+                schema
+                    .iter_topological(false)
+                    .map(|type_def| self.generate_spec(type_def, &ns))
+                    .collect()
+            }
+        };
+        output.push_str(&definitions.join("\n"));
+
+        Ok(output)
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "clj"
+    }
+
+    fn name(&self) -> &'static str {
+        match self.options.style {
+            ClojureStyle::Malli => "Clojure (Malli)",
+            ClojureStyle::Spec => "Clojure (spec)",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::GeneratorOptions;
+    use crate::parser::parse_json;
+
+    fn generate(json: &str, clojure_style: ClojureStyle) -> String {
+        let options = GeneratorOptions {
+            clojure: ClojureOptions {
+                style: clojure_style,
+            },
+            ..Default::default()
+        };
+        let schema = parse_json(json, &options).unwrap();
+        ClojureGenerator::new(options.clojure, options.shared)
+            .generate(&schema)
+            .unwrap()
+    }
+
+    const USERS: &str = r#"{"users": [{"id": 1, "tags": ["a"]}, {"id": 2, "nick-name": null}], "counts": {"a": 1}}"#;
+
+    #[test]
+    fn test_generate_malli() {
+        let output = generate(USERS, ClojureStyle::Malli);
+
+        assert!(output.contains("(ns root.schema)\n"));
+        assert!(output.contains(
+            "(def Users\n  [:map\n   [:id :int]\n   [:tags {:optional true} [:vector :string]]\n   [:nick-name {:optional true} :any]])\n"
+        ));
+        assert!(output.contains("   [:users [:vector Users]]\n"));
+        // Referenced vars are defined first
+        assert!(output.find("(def Users").unwrap() < output.find("(def Root").unwrap());
+    }
+
+    #[test]
+    fn test_generate_spec() {
+        let output = generate(USERS, ClojureStyle::Spec);
+
+        assert!(output.contains("(ns root.spec\n  (:require [clojure.spec.alpha :as s]))\n"));
+        assert!(output.contains("(s/def :root.spec.users/id int?)\n"));
+        assert!(
+            output.contains("(s/def :root.spec.users/tags (s/coll-of string? :kind vector?))\n")
+        );
+        assert!(output.contains(
+            "(s/def ::Users (s/keys :req-un [:root.spec.users/id] :opt-un [:root.spec.users/tags :root.spec.users/nick-name]))\n"
+        ));
+        assert!(
+            output.contains("(s/def :root.spec.root/users (s/coll-of ::Users :kind vector?))\n")
+        );
+    }
+}
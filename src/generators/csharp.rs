@@ -0,0 +1,368 @@
+//! C# record and class generator
+
+use crate::ast::{DateTimeFormat, Field, FieldType, Schema, TypeDef};
+use crate::error::{AlchemistError, Result};
+use crate::generators::{split_nullable_union, CodeGenerator, Indent, SharedOptions};
+use crate::utils::{to_pascal_case, to_snake_case};
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::collections::HashSet;
+
+/// Kind of type emitted by the C# generator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CSharpStyle {
+    /// Positional `record` types, immutable and compared by value
+    #[default]
+    Record,
+    /// Classes with `{ get; set; }` properties, `required` unless optional
+    Class,
+}
+
+/// Options of the C# generator
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct CSharpOptions {
+    /// Kind of type to emit
+    #[serde(rename = "csharp_style")]
+    pub style: CSharpStyle,
+}
+
+/// C# generator for System.Text.Json
+///
+/// Properties are PascalCase and every one carries `[JsonPropertyName]`
+/// with the original key, since System.Text.Json matches names exactly by
+/// default. Optional properties are nullable under `#nullable enable`.
+pub struct CSharpGenerator {
+    options: CSharpOptions,
+    shared: SharedOptions,
+    /// Whitespace of one indentation level
+    indent: String,
+}
+
+impl CSharpGenerator {
+    /// Create a new C# generator with its own and the shared options
+    pub fn new(options: CSharpOptions, shared: SharedOptions) -> Self {
+        let indent = shared.indent_unit(Indent::Spaces(4));
+        Self {
+            options,
+            shared,
+            indent,
+        }
+    }
+
+    /// Generate a record or class from a type definition
+    fn generate_type(&self, type_def: &TypeDef) -> String {
+        let indent = &self.indent;
+        let mut output = String::new();
+
+        if let Some(doc) = &type_def.doc {
+            output.push_str(&format!("/// <summary>{}</summary>\n", xml_escape(doc)));
+        }
+
+        // Members cannot share the name of their enclosing type
+        let mut used = HashSet::from([type_def.name.clone()]);
+        let properties: Vec<(&Field, String, String)> = type_def
+            .fields
+            .iter()
+            .map(|field| {
+                let name = unique_name(property_name(&field.name), &mut used);
+                (field, name, self.property_type(field))
+            })
+            .collect();
+
+        match self.options.style {
+            CSharpStyle::Record => {
+                for (field, name, _) in &properties {
+                    if let Some(doc) = &field.doc {
+                        output.push_str(&format!(
+                            "/// <param name=\"{}\">{}</param>\n",
+                            name,
+                            xml_escape(doc)
+                        ));
+                    }
+                }
+                if properties.is_empty() {
+                    output.push_str(&format!("public record {}();\n", type_def.name));
+                    return output;
+                }
+                output.push_str(&format!("public record {}(\n", type_def.name));
+                let parameters: Vec<String> = properties
+                    .iter()
+                    .map(|(field, name, csharp_type)| {
+                        format!(
+                            "{}[property: JsonPropertyName({})] {} {}",
+                            indent,
+                            csharp_string(&field.name),
+                            csharp_type,
+                            name
+                        )
+                    })
+                    .collect();
+                output.push_str(&parameters.join(",\n"));
+                output.push_str("\n);\n");
+            }
+            CSharpStyle::Class => {
+                output.push_str(&format!("public class {}\n{{\n", type_def.name));
+                let members: Vec<String> = properties
+                    .iter()
+                    .map(|(field, name, csharp_type)| {
+                        let mut member = String::new();
+                        if let Some(doc) = &field.doc {
+                            member.push_str(&format!(
+                                "{}/// <summary>{}</summary>\n",
+                                indent,
+                                xml_escape(doc)
+                            ));
+                        }
+                        member.push_str(&format!(
+                            "{}[JsonPropertyName({})]\n",
+                            indent,
+                            csharp_string(&field.name)
+                        ));
+                        let required = if csharp_type.ends_with('?') {
+                            ""
+                        } else {
+                            "required "
+                        };
+                        member.push_str(&format!(
+                            "{}public {}{} {} {{ get; set; }}\n",
+                            indent, required, csharp_type, name
+                        ));
+                        member
+                    })
+                    .collect();
+                output.push_str(&members.join("\n"));
+                output.push_str("}\n");
+            }
+        }
+
+        output
+    }
+
+    /// C# type of a property, nullable when the field may be missing
+    fn property_type(&self, field: &Field) -> String {
+        let csharp_type = self.field_type_to_csharp(&field.field_type);
+        if field.optional || self.shared.optional_fields {
+            nullable(csharp_type)
+        } else {
+            csharp_type
+        }
+    }
+
+    /// Convert AST FieldType to C# type string
+    fn field_type_to_csharp(&self, field_type: &FieldType) -> String {
+        match field_type {
+            FieldType::String | FieldType::Enum(_) => "string".to_string(),
+            FieldType::Uuid => "Guid".to_string(),
+            FieldType::DateTime(DateTimeFormat::Rfc3339) => "DateTimeOffset".to_string(),
+            FieldType::DateTime(DateTimeFormat::LocalDateTime) => "DateTime".to_string(),
+            FieldType::DateTime(DateTimeFormat::Date) => "DateOnly".to_string(),
+            FieldType::Integer | FieldType::DateTime(_) => "long".to_string(),
+            FieldType::Float => "double".to_string(),
+            FieldType::Decimal => "decimal".to_string(),
+            FieldType::Boolean => "bool".to_string(),
+            FieldType::Array(inner) => format!("List<{}>", self.field_type_to_csharp(inner)),
+            FieldType::Set(inner) => format!("HashSet<{}>", self.field_type_to_csharp(inner)),
+            FieldType::Optional(inner) => nullable(self.field_type_to_csharp(inner)),
+            FieldType::Reference(name) => name.clone(),
+            FieldType::Union(types) => match split_nullable_union(types) {
+                (members, nullable_union) if members.len() == 1 => {
+                    let member = self.field_type_to_csharp(members[0]);
+                    if nullable_union {
+                        nullable(member)
+                    } else {
+                        member
+                    }
+                }
+                (_, true) => "object?".to_string(),
+                _ => "object".to_string(),
+            },
+            FieldType::Map(key, value) => format!(
+                "Dictionary<{}, {}>",
+                self.field_type_to_csharp(key),
+                self.field_type_to_csharp(value)
+            ),
+            FieldType::Null => "object?".to_string(),
+            FieldType::Any => "object".to_string(),
+        }
+    }
+}
+
+/// Make a C# type nullable, once
+fn nullable(csharp_type: String) -> String {
+    if csharp_type.ends_with('?') {
+        csharp_type
+    } else {
+        csharp_type + "?"
+    }
+}
+
+/// Get the PascalCase property name of a key
+fn property_name(key: &str) -> String {
+    let words: String = key
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let mut name = to_pascal_case(&to_snake_case(&words));
+    if name.is_empty() {
+        name.push_str("Field");
+    } else if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    name
+}
+
+/// Suffix a name with a number until no earlier member uses it
+fn unique_name(name: String, used: &mut HashSet<String>) -> String {
+    let mut candidate = name.clone();
+    let mut counter = 1;
+    while !used.insert(candidate.clone()) {
+        counter += 1;
+        candidate = format!("{}{}", name, counter);
+    }
+    candidate
+}
+
+/// Quote text as a C# string, escaping quotes, backslashes and control
+/// characters
+fn csharp_string(text: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Escape text for an XML doc comment
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+impl CodeGenerator for CSharpGenerator {
+    fn generate(&self, schema: &Schema) -> Result<String> {
+        if schema.types.is_empty() {
+            return Err(AlchemistError::GenerationError(
+                "Schema has no types to generate".to_string(),
+            ));
+        }
+
+        let types: Vec<String> = schema
+            .types
+            .iter()
+            .map(|type_def| self.generate_type(type_def))
+            .collect();
+
+        // Import only what the types use
+        let uses = |names: &[&str]| {
+            types
+                .iter()
+                .any(|text| names.iter().any(|name| text.contains(name)))
+        };
+        let mut usings = Vec::new();
+        if uses(&[" Guid", "<Guid", "DateTime", "DateOnly"]) {
+            usings.push("System");
+        }
+        if uses(&["List<", "HashSet<", "Dictionary<"]) {
+            usings.push("System.Collections.Generic");
+        }
+        if uses(&["JsonPropertyName"]) {
+            usings.push("System.Text.Json.Serialization");
+        }
+
+        let mut output = String::new();
+        output.push_str("// Generated by Alchemist\n");
+        output.push_str("// Do not edit manually\n\n");
+        for using in &usings {
+            output.push_str(&format!("using {};\n", using));
+        }
+        if !usings.is_empty() {
+            output.push('\n');
+        }
+        output.push_str("#nullable enable\n\n");
+        output.push_str(&types.join("\n"));
+
+        Ok(output)
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "cs"
+    }
+
+    fn name(&self) -> &'static str {
+        match self.options.style {
+            CSharpStyle::Record => "C# (records)",
+            CSharpStyle::Class => "C# (classes)",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::GeneratorOptions;
+    use crate::parser::parse_json;
+
+    fn generate(json: &str, style: CSharpStyle) -> String {
+        let options = GeneratorOptions {
+            csharp: CSharpOptions { style },
+            ..Default::default()
+        };
+        let schema = parse_json(json, &options).unwrap();
+        CSharpGenerator::new(options.csharp, options.shared)
+            .generate(&schema)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_generate_records() {
+        let output = generate(
+            r#"{"id": 1, "ratio": 0.5, "tags": ["a"], "scores": [1, null], "owner": {"first-name": "x"}, "items": [{"a": 1}, {"b": "x"}], "extra": null}"#,
+            CSharpStyle::Record,
+        );
+
+        assert!(output.starts_with(
+            "// Generated by Alchemist\n// Do not edit manually\n\nusing System.Collections.Generic;\nusing System.Text.Json.Serialization;\n\n#nullable enable\n\n"
+        ));
+        assert!(output.contains(
+            "public record Root(\n    [property: JsonPropertyName(\"id\")] long Id,\n    [property: JsonPropertyName(\"ratio\")] double Ratio,\n    [property: JsonPropertyName(\"tags\")] List<string> Tags,\n    [property: JsonPropertyName(\"scores\")] List<long?> Scores,\n    [property: JsonPropertyName(\"owner\")] Owner Owner,\n"
+        ));
+        assert!(output.contains("    [property: JsonPropertyName(\"extra\")] object? Extra\n);\n"));
+        assert!(output.contains(
+            "public record Owner(\n    [property: JsonPropertyName(\"first-name\")] string FirstName\n);\n"
+        ));
+        assert!(output.contains(
+            "public record Items(\n    [property: JsonPropertyName(\"a\")] long? A,\n    [property: JsonPropertyName(\"b\")] string? B\n);\n"
+        ));
+    }
+
+    #[test]
+    fn test_generate_classes() {
+        let output = generate(
+            r#"[{"id": "550e8400-e29b-41d4-a716-446655440000", "seen": "2024-01-02T15:04:05Z", "k\u0001\"": 1}, {"id": "6fa459ea-ee8a-3ca4-894e-db77e160355e", "k\u0001\"": 2}]"#,
+            CSharpStyle::Class,
+        );
+
+        assert!(output.contains("using System;\n"));
+        assert!(output.contains(
+            "public class RootItem\n{\n    [JsonPropertyName(\"id\")]\n    public required Guid Id { get; set; }\n\n"
+        ));
+        assert!(output.contains(
+            "    /// <summary>ISO 8601 date and time with offset</summary>\n    [JsonPropertyName(\"seen\")]\n    public DateTimeOffset? Seen { get; set; }\n\n"
+        ));
+        assert!(output.contains(
+            "    [JsonPropertyName(\"k\\u0001\\\"\")]\n    public required long K { get; set; }\n}\n"
+        ));
+    }
+}
@@ -0,0 +1,329 @@
+//! F# record type generator
+
+use crate::ast::{DateTimeFormat, Field, FieldType, Schema, TypeDef};
+use crate::error::{AlchemistError, Result};
+use crate::generators::{
+    recursive_types, split_nullable_union, CodeGenerator, Indent, SharedOptions,
+};
+use serde::Deserialize;
+
+/// F# keywords that must be quoted with double backticks as field names
+const FSHARP_KEYWORDS: &[&str] = &[
+    "abstract",
+    "and",
+    "as",
+    "assert",
+    "base",
+    "begin",
+    "class",
+    "default",
+    "delegate",
+    "do",
+    "done",
+    "downcast",
+    "downto",
+    "elif",
+    "else",
+    "end",
+    "exception",
+    "extern",
+    "false",
+    "finally",
+    "fixed",
+    "for",
+    "fun",
+    "function",
+    "global",
+    "if",
+    "in",
+    "inherit",
+    "inline",
+    "interface",
+    "internal",
+    "lazy",
+    "let",
+    "match",
+    "member",
+    "module",
+    "mutable",
+    "namespace",
+    "new",
+    "not",
+    "null",
+    "of",
+    "open",
+    "or",
+    "override",
+    "private",
+    "public",
+    "rec",
+    "return",
+    "select",
+    "sig",
+    "static",
+    "struct",
+    "then",
+    "to",
+    "true",
+    "try",
+    "type",
+    "upcast",
+    "use",
+    "val",
+    "void",
+    "when",
+    "while",
+    "with",
+    "yield",
+];
+
+/// Options of the F# generator
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct FSharpOptions {
+    /// Whether to mark records `[<CLIMutable>]`
+    pub cli_mutable: bool,
+}
+
+/// F# record type generator
+///
+/// Field names are kept as they appear in the data, so the records work with
+/// `System.Text.Json` and `Thoth.Json` without renaming attributes.
+pub struct FSharpGenerator {
+    options: FSharpOptions,
+    shared: SharedOptions,
+    /// Whitespace of one indentation level
+    indent: String,
+}
+
+impl FSharpGenerator {
+    /// Create a new F# generator with its own and the shared options
+    pub fn new(options: FSharpOptions, shared: SharedOptions) -> Self {
+        let indent = shared.indent_unit(Indent::Spaces(4));
+        Self {
+            options,
+            shared,
+            indent,
+        }
+    }
+
+    /// Generate an F# record from a type definition, declared with
+    /// `keyword`: `type`, or `and` to continue a recursive group
+    fn generate_record(&self, type_def: &TypeDef, keyword: &str) -> String {
+        let indent = &self.indent;
+        let mut output = String::new();
+
+        if let Some(doc) = &type_def.doc {
+            output.push_str(&format!("/// {}\n", doc));
+        }
+
+        // Records need at least one field; `[<CLIMutable>]` only applies to records
+        if type_def.fields.is_empty() {
+            output.push_str(&format!(
+                "{} {}() =\n{}class end\n",
+                keyword, type_def.name, indent
+            ));
+            return output;
+        }
+
+        // An attribute goes after `and`, but may stand on its own line before `type`
+        match (self.options.cli_mutable, keyword) {
+            (true, "and") => output.push_str("and [<CLIMutable>] "),
+            (true, _) => output.push_str(&format!("[<CLIMutable>]\n{} ", keyword)),
+            (false, _) => output.push_str(&format!("{} ", keyword)),
+        }
+        output.push_str(&format!("{} =\n{}{{\n", type_def.name, indent));
+        for field in &type_def.fields {
+            if let Some(doc) = &field.doc {
+                output.push_str(&format!("{}{}/// {}\n", indent, indent, doc));
+            }
+
+            let mut fsharp_type = self.field_type_to_fsharp(&field.field_type);
+            if (field.optional || self.shared.optional_fields) && !fsharp_type.ends_with(" option")
+            {
+                fsharp_type = format!("{} option", fsharp_type);
+            }
+            output.push_str(&format!(
+                "{}{}{}: {}\n",
+                indent,
+                indent,
+                field_name(field),
+                fsharp_type
+            ));
+        }
+        output.push_str(&format!("{}}}\n", indent));
+
+        output
+    }
+
+    /// Convert AST FieldType to F# type string
+    fn field_type_to_fsharp(&self, field_type: &FieldType) -> String {
+        match field_type {
+            FieldType::String | FieldType::Enum(_) => "string".to_string(),
+            FieldType::DateTime(DateTimeFormat::Rfc3339) => "System.DateTimeOffset".to_string(),
+            FieldType::DateTime(DateTimeFormat::LocalDateTime) => "System.DateTime".to_string(),
+            FieldType::DateTime(DateTimeFormat::Date) => "System.DateOnly".to_string(),
+            FieldType::Integer | FieldType::DateTime(_) => "int64".to_string(),
+            FieldType::Float => "float".to_string(),
+            FieldType::Decimal => "decimal".to_string(),
+            FieldType::Uuid => "System.Guid".to_string(),
+            FieldType::Boolean => "bool".to_string(),
+            FieldType::Array(inner) | FieldType::Set(inner) => {
+                format!("{} list", self.field_type_to_fsharp(inner))
+            }
+            FieldType::Optional(inner) => format!("{} option", self.field_type_to_fsharp(inner)),
+            FieldType::Reference(name) => name.clone(),
+            FieldType::Union(types) => match split_nullable_union(types) {
+                (members, nullable) if members.len() == 1 => {
+                    let member = self.field_type_to_fsharp(members[0]);
+                    if nullable {
+                        format!("{} option", member)
+                    } else {
+                        member
+                    }
+                }
+                _ => "obj".to_string(),
+            },
+            FieldType::Map(_, value) => {
+                // JSON object keys are strings
+                format!("Map<string, {}>", self.field_type_to_fsharp(value))
+            }
+            FieldType::Null | FieldType::Any => "obj".to_string(),
+        }
+    }
+}
+
+/// Get the record field name, quoting it in double backticks when it is not
+/// a plain identifier
+fn field_name(field: &Field) -> String {
+    let name = &field.name;
+    let is_identifier = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    if is_identifier && !FSHARP_KEYWORDS.contains(&name.as_str()) {
+        name.clone()
+    } else {
+        format!("``{}``", name)
+    }
+}
+
+impl CodeGenerator for FSharpGenerator {
+    fn generate(&self, schema: &Schema) -> Result<String> {
+        if schema.types.is_empty() {
+            return Err(AlchemistError::GenerationError(
+                "Schema has no types to generate".to_string(),
+            ));
+        }
+
+        let mut output = String::new();
+
+        output.push_str("// Generated by Alchemist\n");
+        output.push_str("// Do not edit manually\n\n");
+        output.push_str(&format!("module {}Types\n\n", schema.root_name));
+
+        // Types must be declared before the records that use them, unless
+        // they are one `type ... and ...` group, which recursive types need
+        let recursive = !recursive_types(schema).is_empty();
+        let records: Vec<String> = schema
+            .iter_topological(false)
+            .enumerate()
+            .map(|(i, type_def)| {
+                let keyword = if recursive && i > 0 { "and" } else { "type" };
+                self.generate_record(type_def, keyword)
+            })
+            .collect();
+        output.push_str(&records.join("\n"));
+
+        Ok(output)
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "fs"
+    }
+
+    fn name(&self) -> &'static str {
+        "F#"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::GeneratorOptions;
+    use crate::parser::parse_json;
+
+    fn generate(json: &str, cli_mutable: bool) -> String {
+        let options = GeneratorOptions {
+            fsharp: FSharpOptions { cli_mutable },
+            ..Default::default()
+        };
+        let schema = parse_json(json, &options).unwrap();
+        FSharpGenerator::new(options.fsharp, options.shared)
+            .generate(&schema)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_generate_records() {
+        let output = generate(
+            r#"{"id": 1, "ratio": 0.5, "tags": ["a"], "scores": [1, null], "owner": {"type": "x"}, "items": [{"a": 1}, {"b": "x"}]}"#,
+            false,
+        );
+
+        assert!(output.contains("module RootTypes\n"));
+        assert!(output.contains("type Root =\n    {\n        id: int64\n        ratio: float\n"));
+        assert!(output.contains("        tags: string list\n"));
+        assert!(output.contains("        scores: int64 option list\n"));
+        assert!(output.contains("        owner: Owner\n"));
+        assert!(output.contains("        ``type``: string\n"));
+        assert!(output.contains("        a: int64 option\n"));
+        assert!(!output.contains("[<CLIMutable>]"));
+        // Referenced records are declared first
+        assert!(output.find("type Owner").unwrap() < output.find("type Root").unwrap());
+    }
+
+    #[test]
+    fn test_cli_mutable_attribute() {
+        let output = generate(r#"{"id": 1}"#, true);
+
+        assert!(output.contains("[<CLIMutable>]\ntype Root =\n"));
+
+        let output = generate(r#"{"empty": {}}"#, true);
+        assert!(output.contains("type Empty() =\n    class end\n"));
+        assert!(!output.contains("[<CLIMutable>]\ntype Empty"));
+    }
+
+    #[test]
+    fn test_recursive_types_form_one_group() {
+        let mut schema = Schema::new("Root");
+        let mut root = TypeDef::new("Root");
+        root.add_field(Field::new(
+            "author",
+            FieldType::Reference("User".to_string()),
+        ));
+        schema.add_type(root);
+        let mut user = TypeDef::new("User");
+        user.add_field(Field::new(
+            "posts",
+            FieldType::Array(Box::new(FieldType::Reference("Post".to_string()))),
+        ));
+        schema.add_type(user);
+        let mut post = TypeDef::new("Post");
+        post.add_field(Field::new("author", FieldType::Reference("User".to_string())).optional());
+        schema.add_type(post);
+
+        let options = FSharpOptions { cli_mutable: true };
+        let output = FSharpGenerator::new(options, SharedOptions::default())
+            .generate(&schema)
+            .unwrap();
+
+        assert_eq!(output.matches("\ntype ").count(), 1);
+        assert!(output.contains("[<CLIMutable>]\ntype Post =\n"));
+        assert!(output.contains("and [<CLIMutable>] User =\n"));
+        assert!(output.contains("and [<CLIMutable>] Root =\n"));
+        assert!(output.contains("        author: User option\n"));
+    }
+}
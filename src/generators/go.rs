@@ -0,0 +1,421 @@
+//! Go struct generator
+
+use crate::ast::{DateTimeFormat, Field, FieldType, Schema, TypeDef};
+use crate::error::{AlchemistError, Result};
+use crate::generators::{
+    recursive_types, root_alias, split_nullable_union, CodeGenerator, Indent, SharedOptions,
+};
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::collections::HashSet;
+
+/// Words written in all caps in Go identifiers, e.g. `UserID` for `user_id`
+const GO_INITIALISMS: &[&str] = &[
+    "ACL", "API", "ASCII", "CPU", "CSS", "DNS", "EOF", "GUID", "HTML", "HTTP", "HTTPS", "ID", "IP",
+    "JSON", "SQL", "SSH", "TCP", "TLS", "TTL", "UDP", "UI", "UID", "URI", "URL", "UTF8", "UUID",
+    "VM", "XML",
+];
+
+/// How optional fields are represented in Go
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GoOptional {
+    /// Pointer types, e.g. `*string`, so a missing value is `nil`
+    #[default]
+    Pointer,
+    /// Value types with `omitempty` in the tag, so a missing value is the zero value
+    Omitempty,
+}
+
+/// Options of the Go generator
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct GoOptions {
+    /// Representation of optional and nullable fields
+    #[serde(rename = "go_optional")]
+    pub optional: GoOptional,
+    /// Package clause of the generated file
+    #[serde(rename = "go_package")]
+    pub package: String,
+}
+
+impl Default for GoOptions {
+    fn default() -> Self {
+        Self {
+            optional: GoOptional::default(),
+            package: "models".to_string(),
+        }
+    }
+}
+
+/// Go struct generator
+///
+/// Fields are exported with PascalCase names and keep the original key in a
+/// `json:"..."` tag, so the structs work with `encoding/json` as is.
+pub struct GoGenerator {
+    options: GoOptions,
+    shared: SharedOptions,
+    /// Whitespace of one indentation level
+    indent: String,
+}
+
+impl GoGenerator {
+    /// Create a new Go generator with its own and the shared options
+    pub fn new(options: GoOptions, shared: SharedOptions) -> Self {
+        let indent = shared.indent_unit(Indent::Tab);
+        Self {
+            options,
+            shared,
+            indent,
+        }
+    }
+
+    /// Generate a Go struct from a type definition
+    ///
+    /// Types in `recursive` refer back to themselves and are held through a
+    /// pointer, since a Go struct cannot contain itself by value.
+    fn generate_struct(&self, type_def: &TypeDef, recursive: &HashSet<&str>) -> String {
+        let mut output = String::new();
+
+        if let Some(doc) = &type_def.doc {
+            output.push_str(&format!("// {}\n", doc));
+        }
+        if type_def.fields.is_empty() {
+            output.push_str(&format!("type {} struct{{}}\n", type_def.name));
+            return output;
+        }
+
+        // Rows of name, type and tag, with the doc comment above each
+        let mut used_names = HashSet::new();
+        let rows: Vec<(Option<&String>, [String; 3])> = type_def
+            .fields
+            .iter()
+            .map(|field| {
+                let name = unique_name(go_field_name(field.code_name()), &mut used_names);
+                (
+                    field.doc.as_ref(),
+                    [name, self.field_go_type(field, recursive), self.tag(field)],
+                )
+            })
+            .collect();
+
+        output.push_str(&format!("type {} struct {{\n", type_def.name));
+        // gofmt aligns the columns of consecutive fields; a comment line
+        // starts a new block
+        let mut start = 0;
+        while start < rows.len() {
+            let end = (start + 1..rows.len())
+                .find(|&i| rows[i].0.is_some())
+                .unwrap_or(rows.len());
+            let block = &rows[start..end];
+            let name_width = block.iter().map(|(_, row)| row[0].len()).max().unwrap_or(0);
+            let type_width = block.iter().map(|(_, row)| row[1].len()).max().unwrap_or(0);
+            for (doc, [name, go_type, tag]) in block {
+                if let Some(doc) = doc {
+                    output.push_str(&format!("{}// {}\n", self.indent, doc));
+                }
+                output.push_str(&format!(
+                    "{}{:name_width$} {:type_width$} {}\n",
+                    self.indent, name, go_type, tag
+                ));
+            }
+            start = end;
+        }
+        output.push_str("}\n");
+
+        output
+    }
+
+    /// The Go type of a field, with its optionality applied
+    fn field_go_type(&self, field: &Field, recursive: &HashSet<&str>) -> String {
+        let optional = field.optional || self.shared.optional_fields;
+        let field_type = match &field.field_type {
+            // The zero value stands in for null and missing values alike
+            FieldType::Optional(inner) if self.options.optional == GoOptional::Omitempty => inner,
+            field_type => field_type,
+        };
+
+        let go_type = self.field_type_to_go(field_type);
+        let needs_pointer = match field_type {
+            FieldType::Reference(name) => recursive.contains(name.as_str()),
+            _ => false,
+        } || (optional && self.options.optional == GoOptional::Pointer);
+
+        if needs_pointer && !is_nilable(&go_type) {
+            format!("*{}", go_type)
+        } else {
+            go_type
+        }
+    }
+
+    /// The struct tag of a field, keyed by the original field name
+    fn tag(&self, field: &Field) -> String {
+        let omitempty = if field.optional || self.shared.optional_fields {
+            ",omitempty"
+        } else {
+            ""
+        };
+        format!(
+            "`json:\"{}{}\"`",
+            field.name.replace('"', "\\\""),
+            omitempty
+        )
+    }
+
+    /// Convert AST FieldType to Go type string
+    fn field_type_to_go(&self, field_type: &FieldType) -> String {
+        match field_type {
+            FieldType::String | FieldType::Enum(_) | FieldType::Uuid => "string".to_string(),
+            // encoding/json reads time.Time as RFC 3339, which needs an offset
+            FieldType::DateTime(DateTimeFormat::Rfc3339) => "time.Time".to_string(),
+            FieldType::DateTime(format) if !format.is_epoch() => "string".to_string(),
+            FieldType::Integer | FieldType::DateTime(_) => "int64".to_string(),
+            FieldType::Float => "float64".to_string(),
+            // Keeps the number's text, so no precision is lost
+            FieldType::Decimal => "json.Number".to_string(),
+            FieldType::Boolean => "bool".to_string(),
+            FieldType::Array(inner) | FieldType::Set(inner) => {
+                format!("[]{}", self.field_type_to_go(inner))
+            }
+            FieldType::Optional(inner) => nullable(self.field_type_to_go(inner)),
+            FieldType::Reference(name) => name.clone(),
+            FieldType::Union(types) => match split_nullable_union(types) {
+                (members, nullable_union) if members.len() == 1 => {
+                    let member = self.field_type_to_go(members[0]);
+                    if nullable_union {
+                        nullable(member)
+                    } else {
+                        member
+                    }
+                }
+                _ => "interface{}".to_string(),
+            },
+            FieldType::Map(key, value) => {
+                let key = match **key {
+                    FieldType::Integer => "int64",
+                    _ => "string",
+                };
+                format!("map[{}]{}", key, self.field_type_to_go(value))
+            }
+            FieldType::Null | FieldType::Any => "interface{}".to_string(),
+        }
+    }
+}
+
+/// A Go type that can also hold `nil`
+fn nullable(go_type: String) -> String {
+    if is_nilable(&go_type) {
+        go_type
+    } else {
+        format!("*{}", go_type)
+    }
+}
+
+/// Whether a Go type already has `nil` as a value
+fn is_nilable(go_type: &str) -> bool {
+    go_type.starts_with('*')
+        || go_type.starts_with("[]")
+        || go_type.starts_with("map[")
+        || go_type == "interface{}"
+}
+
+/// Exported Go name for a field, e.g. `user_id` and `userId` to `UserID`
+///
+/// Names that would start with a digit get a `Field` prefix.
+fn go_field_name(name: &str) -> String {
+    let name = name.trim_start_matches("r#");
+    let mut words: Vec<String> = Vec::new();
+    let mut word = String::new();
+    let mut last_lower = false;
+    for c in name.chars() {
+        if !c.is_ascii_alphanumeric() {
+            words.extend((!word.is_empty()).then(|| std::mem::take(&mut word)));
+            last_lower = false;
+            continue;
+        }
+        if c.is_ascii_uppercase() && last_lower {
+            words.push(std::mem::take(&mut word));
+        }
+        last_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        word.push(c);
+    }
+    words.extend((!word.is_empty()).then_some(word));
+
+    let mut result: String = words
+        .iter()
+        .map(|word| {
+            let upper = word.to_ascii_uppercase();
+            if GO_INITIALISMS.contains(&upper.as_str()) {
+                upper
+            } else {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            }
+        })
+        .collect();
+    if !result.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        result.insert_str(0, "Field");
+    }
+    result
+}
+
+/// Number a field name taken by an earlier field of the same struct
+fn unique_name(name: String, used: &mut HashSet<String>) -> String {
+    let mut candidate = name.clone();
+    let mut counter = 1;
+    while !used.insert(candidate.clone()) {
+        counter += 1;
+        candidate = format!("{}{}", name, counter);
+    }
+    candidate
+}
+
+impl CodeGenerator for GoGenerator {
+    fn generate(&self, schema: &Schema) -> Result<String> {
+        if schema.types.is_empty() {
+            return Err(AlchemistError::GenerationError(
+                "Schema has no types to generate".to_string(),
+            ));
+        }
+
+        let recursive = recursive_types(schema);
+        let alias = root_alias(schema, &self.shared);
+        let structs: Vec<String> = schema
+            .iter_topological(false)
+            .map(
+                |type_def| match alias.as_ref().filter(|a| a.name == type_def.name) {
+                    Some(alias) => {
+                        let doc = alias.doc.map(|doc| format!("// {}\n", doc));
+                        format!(
+                            "{}type {} {}\n",
+                            doc.unwrap_or_default(),
+                            alias.name,
+                            self.field_type_to_go(alias.field_type)
+                        )
+                    }
+                    None => self.generate_struct(type_def, &recursive),
+                },
+            )
+            .collect();
+
+        let mut output = String::new();
+
+        // The form Go tools recognize as generated code
+        output.push_str("// Code generated by Alchemist. DO NOT EDIT.\n\n");
+        output.push_str(&format!("package {}\n\n", self.options.package));
+        let imports: Vec<&str> = [("encoding/json", "json.Number"), ("time", "time.Time")]
+            .into_iter()
+            .filter(|(_, used)| structs.iter().any(|s| s.contains(used)))
+            .map(|(package, _)| package)
+            .collect();
+        match imports.as_slice() {
+            [] => {}
+            [package] => output.push_str(&format!("import \"{}\"\n\n", package)),
+            packages => {
+                output.push_str("import (\n");
+                for package in packages {
+                    output.push_str(&format!("{}\"{}\"\n", self.indent, package));
+                }
+                output.push_str(")\n\n");
+            }
+        }
+        output.push_str(&structs.join("\n"));
+
+        Ok(output)
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "go"
+    }
+
+    fn name(&self) -> &'static str {
+        "Go"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::GeneratorOptions;
+    use crate::parser::parse_json;
+
+    fn generate(json: &str, optional: GoOptional) -> String {
+        let options = GeneratorOptions {
+            go: GoOptions {
+                optional,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let schema = parse_json(json, &options).unwrap();
+        GoGenerator::new(options.go, options.shared)
+            .generate(&schema)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_generate_structs() {
+        let output = generate(
+            r#"{"user_id": 1, "ratio": 0.5, "tags": ["a"], "extra": null, "owner": {"apiUrl": "x"}, "items": [{"a": 1}, {"b": "x"}]}"#,
+            GoOptional::Pointer,
+        );
+
+        assert!(
+            output.starts_with("// Code generated by Alchemist. DO NOT EDIT.\n\npackage models\n")
+        );
+        assert!(output.contains(
+            "type Root struct {\n\tUserID int64       `json:\"user_id\"`\n\tRatio  float64     `json:\"ratio\"`\n\tTags   []string    `json:\"tags\"`\n\tExtra  interface{} `json:\"extra,omitempty\"`\n\tOwner  Owner       `json:\"owner\"`\n\tItems  []Items     `json:\"items\"`\n}"
+        ));
+        assert!(output.contains("\tAPIURL string `json:\"apiUrl\"`\n"));
+        assert!(output.contains("\tA *int64  `json:\"a,omitempty\"`\n"));
+        assert!(!output.contains("encoding/json"));
+        // Referenced structs are declared first
+        assert!(output.find("type Owner").unwrap() < output.find("type Root").unwrap());
+    }
+
+    #[test]
+    fn test_maps_decimals_and_recursion() {
+        let mut schema = Schema::new("Node");
+        let mut node = TypeDef::new("Node");
+        node.add_field(Field::new(
+            "parent",
+            FieldType::Reference("Node".to_string()),
+        ));
+        node.add_field(Field::new(
+            "prices",
+            FieldType::Map(Box::new(FieldType::String), Box::new(FieldType::Decimal)),
+        ));
+        schema.add_type(node);
+        let output = GoGenerator::new(GoOptions::default(), SharedOptions::default())
+            .generate(&schema)
+            .unwrap();
+
+        assert!(output.contains("import \"encoding/json\"\n"));
+        assert!(output.contains("\tParent *Node                  `json:\"parent\"`\n"));
+        assert!(output.contains("\tPrices map[string]json.Number `json:\"prices\"`\n"));
+    }
+
+    #[test]
+    fn test_omitempty_keeps_value_types() {
+        let output = generate(
+            r#"{"items": [{"a": 1, "n": null}, {"b": "x", "n": 2}]}"#,
+            GoOptional::Omitempty,
+        );
+
+        assert!(output.contains("\tA int64  `json:\"a,omitempty\"`\n"));
+        assert!(output.contains("\tN int64  `json:\"n\"`\n"));
+        assert!(output.contains("\tB string `json:\"b,omitempty\"`\n"));
+    }
+
+    #[test]
+    fn test_field_names() {
+        assert_eq!(go_field_name("user_id"), "UserID");
+        assert_eq!(go_field_name("createdAt"), "CreatedAt");
+        assert_eq!(go_field_name("r#type"), "Type");
+        assert_eq!(go_field_name("_1st-place"), "Field1stPlace");
+        assert_eq!(go_field_name("HTTPStatus"), "HTTPStatus");
+    }
+}
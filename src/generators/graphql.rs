@@ -0,0 +1,281 @@
+//! GraphQL SDL type generator
+
+use crate::ast::{DateTimeFormat, Field, FieldType, Schema, TypeDef};
+use crate::error::{AlchemistError, Result};
+use crate::generators::{split_nullable_union, CodeGenerator, Indent, SharedOptions};
+use std::collections::HashSet;
+
+/// GraphQL SDL generator
+///
+/// Every type becomes an object `type`, with `!` on fields that are always
+/// present and never `null`. Values GraphQL cannot type, such as maps, `Any`
+/// and unions of several types, use a custom `JSON` scalar declared once at
+/// the top.
+pub struct GraphqlGenerator {
+    shared: SharedOptions,
+    /// Whitespace of one indentation level
+    indent: String,
+}
+
+impl GraphqlGenerator {
+    /// Create a new GraphQL generator with the shared options
+    pub fn new(shared: SharedOptions) -> Self {
+        let indent = shared.indent_unit(Indent::Spaces(2));
+        Self { shared, indent }
+    }
+
+    /// Generate a GraphQL object type from a type definition
+    fn generate_type(&self, type_def: &TypeDef) -> String {
+        let indent = &self.indent;
+        let mut output = String::new();
+
+        if let Some(doc) = &type_def.doc {
+            output.push_str(&format!("{}\n", graphql_string(doc)));
+        }
+        output.push_str(&format!("type {} {{\n", type_def.name));
+
+        // Object types need at least one field
+        if type_def.fields.is_empty() {
+            output.push_str(&format!(
+                "{}\"No fields were observed\"\n{}_empty: Boolean\n",
+                indent, indent
+            ));
+        }
+
+        let mut used = HashSet::new();
+        for field in &type_def.fields {
+            let name = unique_name(field_name(&field.name), &mut used);
+            let doc = match (&field.doc, name != field.name) {
+                (Some(doc), true) => Some(format!("{} (JSON key \"{}\")", doc, field.name)),
+                (Some(doc), false) => Some(doc.clone()),
+                (None, true) => Some(format!("JSON key \"{}\"", field.name)),
+                (None, false) => None,
+            };
+            if let Some(doc) = doc {
+                output.push_str(&format!("{}{}\n", indent, graphql_string(&doc)));
+            }
+
+            let mut graphql_type = self.field_type_to_graphql(&field.field_type);
+            if self.is_optional(field) {
+                graphql_type = nullable(graphql_type);
+            }
+            output.push_str(&format!("{}{}: {}\n", indent, name, graphql_type));
+        }
+
+        output.push_str("}\n");
+        output
+    }
+
+    /// Whether a field may be missing from the data
+    fn is_optional(&self, field: &Field) -> bool {
+        field.optional || self.shared.optional_fields
+    }
+
+    /// Convert AST FieldType to a GraphQL type, ending in `!` unless it
+    /// admits `null`
+    fn field_type_to_graphql(&self, field_type: &FieldType) -> String {
+        match field_type {
+            FieldType::String | FieldType::Enum(_) => "String!".to_string(),
+            FieldType::Uuid => "ID!".to_string(),
+            FieldType::DateTime(format) if !format.is_epoch() => "String!".to_string(),
+            // Int is 32-bit, too small for timestamps in milliseconds
+            FieldType::DateTime(DateTimeFormat::EpochMillis) => "Float!".to_string(),
+            FieldType::Integer | FieldType::DateTime(_) => "Int!".to_string(),
+            FieldType::Float | FieldType::Decimal => "Float!".to_string(),
+            FieldType::Boolean => "Boolean!".to_string(),
+            FieldType::Array(inner) | FieldType::Set(inner) => {
+                format!("[{}]!", self.field_type_to_graphql(inner))
+            }
+            FieldType::Optional(inner) => nullable(self.field_type_to_graphql(inner)),
+            FieldType::Reference(name) => format!("{}!", name),
+            FieldType::Union(types) => match split_nullable_union(types) {
+                (members, nullable_union) if members.len() == 1 => {
+                    let member = self.field_type_to_graphql(members[0]);
+                    if nullable_union {
+                        nullable(member)
+                    } else {
+                        member
+                    }
+                }
+                (_, true) => "JSON".to_string(),
+                _ => "JSON!".to_string(),
+            },
+            FieldType::Map(_, _) => "JSON!".to_string(),
+            FieldType::Null | FieldType::Any => "JSON".to_string(),
+        }
+    }
+}
+
+/// Make a GraphQL type nullable by dropping its `!`
+fn nullable(graphql_type: String) -> String {
+    match graphql_type.strip_suffix('!') {
+        Some(inner) => inner.to_string(),
+        None => graphql_type,
+    }
+}
+
+/// Whether values of this type need the `JSON` scalar, see
+/// `GraphqlGenerator::field_type_to_graphql`
+fn uses_json_scalar(field_type: &FieldType) -> bool {
+    match field_type {
+        FieldType::Map(_, _) | FieldType::Null | FieldType::Any => true,
+        FieldType::Array(inner) | FieldType::Set(inner) | FieldType::Optional(inner) => {
+            uses_json_scalar(inner)
+        }
+        FieldType::Union(types) => match split_nullable_union(types) {
+            (members, _) if members.len() == 1 => uses_json_scalar(members[0]),
+            _ => true,
+        },
+        _ => false,
+    }
+}
+
+/// Get a valid GraphQL field name for a key, replacing the characters names
+/// cannot contain with underscores
+///
+/// Names starting with `__` are reserved for introspection, so a leading run
+/// of underscores is cut down to one.
+fn field_name(key: &str) -> String {
+    let mut name: String = key
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    while name.starts_with("__") {
+        name.remove(0);
+    }
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    name
+}
+
+/// Suffix a name with a number until no earlier field uses it
+fn unique_name(name: String, used: &mut HashSet<String>) -> String {
+    let mut candidate = name.clone();
+    let mut counter = 1;
+    while !used.insert(candidate.clone()) {
+        counter += 1;
+        candidate = format!("{}{}", name, counter);
+    }
+    candidate
+}
+
+/// Quote text as a GraphQL string, escaping quotes, backslashes and control
+/// characters
+fn graphql_string(text: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+impl CodeGenerator for GraphqlGenerator {
+    fn generate(&self, schema: &Schema) -> Result<String> {
+        if schema.types.is_empty() {
+            return Err(AlchemistError::GenerationError(
+                "Schema has no types to generate".to_string(),
+            ));
+        }
+
+        let types: Vec<String> = schema
+            .types
+            .iter()
+            .map(|type_def| self.generate_type(type_def))
+            .collect();
+
+        let mut output = String::new();
+        output.push_str("# Generated by Alchemist\n");
+        output.push_str("# Do not edit manually\n\n");
+        if schema
+            .types
+            .iter()
+            .flat_map(|t| &t.fields)
+            .any(|field| uses_json_scalar(&field.field_type))
+        {
+            output.push_str("\"Any JSON value\"\nscalar JSON\n\n");
+        }
+        output.push_str(&types.join("\n"));
+
+        Ok(output)
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "graphql"
+    }
+
+    fn name(&self) -> &'static str {
+        "GraphQL"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::GeneratorOptions;
+    use crate::parser::parse_json;
+
+    fn generate(json: &str) -> String {
+        let options = GeneratorOptions::default();
+        let schema = parse_json(json, &options).unwrap();
+        GraphqlGenerator::new(options.shared)
+            .generate(&schema)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_generate_object_types() {
+        let output = generate(
+            r#"{"id": 1, "ratio": 0.5, "tags": ["a"], "scores": [1, null], "owner": {"first-name": "x"}, "note": null, "users": [{"a": 1}, {"b": "x"}]}"#,
+        );
+
+        assert!(output.starts_with(
+            "# Generated by Alchemist\n# Do not edit manually\n\n\"Any JSON value\"\nscalar JSON\n\n"
+        ));
+        assert!(output.contains(
+            "type Root {\n  id: Int!\n  ratio: Float!\n  tags: [String!]!\n  scores: [Int]!\n  owner: Owner!\n  note: JSON\n  users: [Users!]!\n}\n"
+        ));
+        assert!(output.contains(
+            "type Owner {\n  \"JSON key \\\"first-name\\\"\"\n  first_name: String!\n}\n"
+        ));
+        assert!(output.contains("type Users {\n  a: Int\n  b: String\n}\n"));
+    }
+
+    #[test]
+    fn test_json_scalar_only_when_used() {
+        let output = generate(r#"{"active": true}"#);
+
+        assert!(!output.contains("scalar JSON"));
+        assert!(output.contains("type Root {\n  active: Boolean!\n}\n"));
+
+        // Only field types count, not text that merely looks like one
+        let output = generate(r#"{"note: JSON": true}"#);
+        assert!(!output.contains("scalar JSON"));
+    }
+
+    #[test]
+    fn test_field_names_are_unique_and_unreserved() {
+        let output = generate(r#"{"a-b": 1, "a_b": "x", "__t": true}"#);
+
+        assert!(output.contains(
+            "type Root {\n  \"JSON key \\\"a-b\\\"\"\n  a_b: Int!\n  \"JSON key \\\"a_b\\\"\"\n  a_b2: String!\n  \"JSON key \\\"__t\\\"\"\n  _t: Boolean!\n}\n"
+        ));
+    }
+
+    #[test]
+    fn test_descriptions_are_graphql_strings() {
+        assert_eq!(
+            graphql_string("say \"hi\"\\\n\u{1}é"),
+            "\"say \\\"hi\\\"\\\\\\n\\u0001é\""
+        );
+    }
+}
@@ -0,0 +1,245 @@
+//! JSON Schema (draft-07) generator
+
+use crate::ast::{DateTimeFormat, Field, FieldType, Schema, TypeDef};
+use crate::error::{AlchemistError, Result};
+use crate::generators::{json_document, CodeGenerator, Indent, SharedOptions};
+use serde_json::{json, Map, Value};
+
+/// JSON Schema generator
+///
+/// Every type goes into `$defs`, and the document itself is a `$ref` to the
+/// root type. Fields missing from some samples are left out of `required`,
+/// while nullable ones also accept `null`.
+pub struct JsonSchemaGenerator {
+    shared: SharedOptions,
+    /// Whitespace of one indentation level
+    indent: String,
+}
+
+impl JsonSchemaGenerator {
+    /// Create a new JSON Schema generator with the shared options
+    pub fn new(shared: SharedOptions) -> Self {
+        let indent = shared.indent_unit(Indent::Spaces(2));
+        Self { shared, indent }
+    }
+
+    /// Generate an object schema from a type definition
+    fn type_def_to_schema(&self, type_def: &TypeDef) -> Value {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+
+        for field in &type_def.fields {
+            let mut schema = self.field_type_to_schema(&field.field_type);
+            if let (Some(doc), Value::Object(map)) = (&field.doc, &mut schema) {
+                map.insert("description".to_string(), json!(doc));
+            }
+            properties.insert(field.name.clone(), schema);
+            if !self.is_optional(field) {
+                required.push(field.name.clone());
+            }
+        }
+
+        let mut schema = Map::new();
+        if let Some(doc) = &type_def.doc {
+            schema.insert("description".to_string(), json!(doc));
+        }
+        schema.insert("type".to_string(), json!("object"));
+        schema.insert("properties".to_string(), Value::Object(properties));
+        if !required.is_empty() {
+            schema.insert("required".to_string(), json!(required));
+        }
+        Value::Object(schema)
+    }
+
+    /// Whether a field may be missing from the data
+    fn is_optional(&self, field: &Field) -> bool {
+        field.optional || self.shared.optional_fields
+    }
+
+    /// Convert AST FieldType to a JSON Schema
+    fn field_type_to_schema(&self, field_type: &FieldType) -> Value {
+        match field_type {
+            FieldType::String => json!({ "type": "string" }),
+            FieldType::Uuid => json!({ "type": "string", "format": "uuid" }),
+            FieldType::Enum(values) => json!({ "type": "string", "enum": values }),
+            FieldType::Integer => json!({ "type": "integer" }),
+            FieldType::Float | FieldType::Decimal => json!({ "type": "number" }),
+            FieldType::Boolean => json!({ "type": "boolean" }),
+            FieldType::Null => json!({ "type": "null" }),
+            FieldType::Any => json!({}),
+            FieldType::DateTime(DateTimeFormat::Rfc3339) => {
+                json!({ "type": "string", "format": "date-time" })
+            }
+            FieldType::DateTime(DateTimeFormat::Date) => {
+                json!({ "type": "string", "format": "date" })
+            }
+            // `date-time` requires an offset, so local date-times are plain strings
+            FieldType::DateTime(DateTimeFormat::LocalDateTime) => json!({ "type": "string" }),
+            FieldType::DateTime(_) => json!({ "type": "integer" }),
+            FieldType::Array(inner) => {
+                json!({ "type": "array", "items": self.field_type_to_schema(inner) })
+            }
+            FieldType::Set(inner) => json!({
+                "type": "array",
+                "items": self.field_type_to_schema(inner),
+                "uniqueItems": true
+            }),
+            FieldType::Optional(inner) => nullable(self.field_type_to_schema(inner)),
+            FieldType::Reference(name) => json!({ "$ref": format!("#/$defs/{}", name) }),
+            FieldType::Union(types) => {
+                let members: Vec<Value> =
+                    types.iter().map(|t| self.field_type_to_schema(t)).collect();
+                json!({ "anyOf": members })
+            }
+            FieldType::Map(key, value) => {
+                let mut schema = json!({
+                    "type": "object",
+                    "additionalProperties": self.field_type_to_schema(value)
+                });
+                // Object keys are always strings, so integer keys are a pattern
+                if matches!(key.as_ref(), FieldType::Integer) {
+                    schema["propertyNames"] = json!({ "pattern": "^-?[0-9]+$" });
+                }
+                schema
+            }
+        }
+    }
+}
+
+/// Make a schema also accept `null`
+///
+/// A single `type` gains `"null"`; anything else, like a `$ref`, is wrapped
+/// in an `anyOf`. The empty schema already accepts `null`.
+fn nullable(schema: Value) -> Value {
+    match schema.as_object() {
+        Some(map) if map.is_empty() => schema,
+        Some(map) if !map.contains_key("enum") => match map.get("type") {
+            Some(Value::String(name)) if name == "null" => schema,
+            Some(Value::String(name)) => {
+                let mut map = map.clone();
+                map.insert("type".to_string(), json!([name, "null"]));
+                Value::Object(map)
+            }
+            _ => json!({ "anyOf": [schema, { "type": "null" }] }),
+        },
+        _ => json!({ "anyOf": [schema, { "type": "null" }] }),
+    }
+}
+
+impl CodeGenerator for JsonSchemaGenerator {
+    fn generate(&self, schema: &Schema) -> Result<String> {
+        let root = schema.root_type().ok_or_else(|| {
+            AlchemistError::GenerationError("Schema has no types to generate".to_string())
+        })?;
+
+        let mut definitions = Map::new();
+        for type_def in &schema.types {
+            definitions.insert(type_def.name.clone(), self.type_def_to_schema(type_def));
+        }
+
+        let document = json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "$ref": format!("#/$defs/{}", root.name),
+            "$defs": definitions
+        });
+
+        json_document(&document, &self.indent)
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn name(&self) -> &'static str {
+        "JSON Schema"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::GeneratorOptions;
+    use crate::parser::parse_json;
+
+    fn generate(json: &str) -> Value {
+        let options = GeneratorOptions::default();
+        let schema = parse_json(json, &options).unwrap();
+        let output = JsonSchemaGenerator::new(options.shared)
+            .generate(&schema)
+            .unwrap();
+        serde_json::from_str(&output).unwrap()
+    }
+
+    #[test]
+    fn test_generate_defs_and_root_ref() {
+        let document = generate(
+            r#"{"id": 1, "ratio": 0.5, "tags": ["a"], "users": [{"name": "a"}, {"name": "b", "age": 3}], "scores": [1, null], "mixed": [1, "a"]}"#,
+        );
+
+        assert_eq!(
+            document["$schema"],
+            "http://json-schema.org/draft-07/schema#"
+        );
+        assert_eq!(document["$ref"], "#/$defs/Root");
+        let root = &document["$defs"]["Root"];
+        assert_eq!(root["type"], "object");
+        assert_eq!(root["properties"]["id"], json!({ "type": "integer" }));
+        assert_eq!(root["properties"]["ratio"], json!({ "type": "number" }));
+        assert_eq!(
+            root["properties"]["users"],
+            json!({ "type": "array", "items": { "$ref": "#/$defs/Users" } })
+        );
+        assert_eq!(
+            root["properties"]["scores"],
+            json!({ "type": "array", "items": { "type": ["integer", "null"] } })
+        );
+        assert_eq!(
+            root["properties"]["mixed"]["items"],
+            json!({ "anyOf": [{ "type": "integer" }, { "type": "string" }] })
+        );
+        assert_eq!(
+            document["$defs"]["Users"],
+            json!({
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" },
+                    "age": { "type": "integer" }
+                },
+                "required": ["name"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_maps_and_nullable_references() {
+        let mut schema = Schema::new("Root");
+        let mut root = TypeDef::new("Root");
+        root.add_field(Field::new(
+            "scores",
+            FieldType::Map(Box::new(FieldType::Integer), Box::new(FieldType::Float)),
+        ));
+        root.add_field(Field::new(
+            "parent",
+            FieldType::Optional(Box::new(FieldType::Reference("Root".to_string()))),
+        ));
+        schema.add_type(root);
+        let output = JsonSchemaGenerator::new(SharedOptions::default())
+            .generate(&schema)
+            .unwrap();
+        let document: Value = serde_json::from_str(&output).unwrap();
+        let properties = &document["$defs"]["Root"]["properties"];
+
+        assert_eq!(
+            properties["scores"],
+            json!({
+                "type": "object",
+                "additionalProperties": { "type": "number" },
+                "propertyNames": { "pattern": "^-?[0-9]+$" }
+            })
+        );
+        assert_eq!(
+            properties["parent"],
+            json!({ "anyOf": [{ "$ref": "#/$defs/Root" }, { "type": "null" }] })
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! JSON Type Definition (RFC 8927) generator
+
+use crate::ast::{
+    walk_schema, Constraints, DateTimeFormat, Field, FieldType, ReferenceCounter, Schema, TypeDef,
+};
+use crate::error::{AlchemistError, Result};
+use crate::generators::{
+    json_document, split_nullable_union, CodeGenerator, Indent, SharedOptions,
+};
+use serde_json::{json, Map, Value};
+
+/// JSON Type Definition generator
+///
+/// The root type is the top-level schema and every other type goes into
+/// `definitions`, referenced with `{"ref": "Name"}`. Integers are `float64`,
+/// since JTD has no 64-bit integer type, unless constraint inference saw
+/// only values that fit `int32`.
+pub struct JtdGenerator {
+    shared: SharedOptions,
+    /// Whitespace of one indentation level
+    indent: String,
+}
+
+impl JtdGenerator {
+    /// Create a new JTD generator with the shared options
+    pub fn new(shared: SharedOptions) -> Self {
+        let indent = shared.indent_unit(Indent::Spaces(2));
+        Self { shared, indent }
+    }
+
+    /// Generate a properties form from a type definition
+    fn type_def_to_jtd(&self, type_def: &TypeDef) -> Map<String, Value> {
+        let mut properties = Map::new();
+        let mut optional_properties = Map::new();
+
+        for field in &type_def.fields {
+            let mut schema =
+                if field.field_type == FieldType::Integer && fits_int32(&field.constraints) {
+                    json_map(json!({ "type": "int32" }))
+                } else {
+                    self.field_type_to_jtd(&field.field_type)
+                };
+            if let Some(doc) = &field.doc {
+                schema.insert("metadata".to_string(), json!({ "description": doc }));
+            }
+            if self.is_optional(field) {
+                optional_properties.insert(field.name.clone(), Value::Object(schema));
+            } else {
+                properties.insert(field.name.clone(), Value::Object(schema));
+            }
+        }
+
+        let mut form = Map::new();
+        if let Some(doc) = &type_def.doc {
+            form.insert("metadata".to_string(), json!({ "description": doc }));
+        }
+        // A properties form needs at least one of the two keywords
+        if !properties.is_empty() || optional_properties.is_empty() {
+            form.insert("properties".to_string(), Value::Object(properties));
+        }
+        if !optional_properties.is_empty() {
+            form.insert(
+                "optionalProperties".to_string(),
+                Value::Object(optional_properties),
+            );
+        }
+        form
+    }
+
+    /// Whether a field may be missing from the data
+    fn is_optional(&self, field: &Field) -> bool {
+        field.optional || self.shared.optional_fields
+    }
+
+    /// Convert AST FieldType to a JTD schema
+    fn field_type_to_jtd(&self, field_type: &FieldType) -> Map<String, Value> {
+        let schema = match field_type {
+            FieldType::String | FieldType::Uuid => json!({ "type": "string" }),
+            FieldType::Enum(values) => json!({ "enum": values }),
+            FieldType::DateTime(DateTimeFormat::Rfc3339) => json!({ "type": "timestamp" }),
+            FieldType::DateTime(format) if !format.is_epoch() => json!({ "type": "string" }),
+            // Integers of unknown range and epoch milliseconds may overflow
+            // int32, and JTD has no int64
+            FieldType::Integer | FieldType::Float | FieldType::Decimal | FieldType::DateTime(_) => {
+                json!({ "type": "float64" })
+            }
+            FieldType::Boolean => json!({ "type": "boolean" }),
+            // The empty form accepts any value, null included
+            FieldType::Null | FieldType::Any => json!({}),
+            FieldType::Array(inner) | FieldType::Set(inner) => {
+                json!({ "elements": self.field_type_to_jtd(inner) })
+            }
+            FieldType::Optional(inner) => return nullable(self.field_type_to_jtd(inner)),
+            FieldType::Reference(name) => json!({ "ref": name }),
+            // JTD has no unions, only nullability
+            FieldType::Union(types) => match split_nullable_union(types) {
+                (members, true) if members.len() == 1 => {
+                    return nullable(self.field_type_to_jtd(members[0]))
+                }
+                (members, false) if members.len() == 1 => {
+                    return self.field_type_to_jtd(members[0])
+                }
+                _ => json!({}),
+            },
+            // Map keys are always strings in JTD
+            FieldType::Map(_, value) => json!({ "values": self.field_type_to_jtd(value) }),
+        };
+        json_map(schema)
+    }
+}
+
+/// Whether the observed range of an integer field fits `int32`
+fn fits_int32(constraints: &Constraints) -> bool {
+    let fits = |bound: Option<f64>| {
+        bound.is_some_and(|n| (i32::MIN as f64..=i32::MAX as f64).contains(&n))
+    };
+    fits(constraints.min) && fits(constraints.max)
+}
+
+/// Unwrap the object of a `json!` schema
+fn json_map(schema: Value) -> Map<String, Value> {
+    match schema {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    }
+}
+
+/// Mark a schema as accepting `null`, unless it already accepts anything
+fn nullable(mut schema: Map<String, Value>) -> Map<String, Value> {
+    if !schema.is_empty() {
+        schema.insert("nullable".to_string(), Value::Bool(true));
+    }
+    schema
+}
+
+impl CodeGenerator for JtdGenerator {
+    fn generate(&self, schema: &Schema) -> Result<String> {
+        let root = schema.root_type().ok_or_else(|| {
+            AlchemistError::GenerationError("Schema has no types to generate".to_string())
+        })?;
+
+        // The root only needs a definition if something refers back to it
+        let mut references = ReferenceCounter::default();
+        walk_schema(&mut references, schema);
+
+        let mut definitions = Map::new();
+        for type_def in &schema.types {
+            if type_def.name != root.name || references.counts.contains_key(&root.name) {
+                definitions.insert(
+                    type_def.name.clone(),
+                    Value::Object(self.type_def_to_jtd(type_def)),
+                );
+            }
+        }
+
+        let mut document = Map::new();
+        if !definitions.is_empty() {
+            document.insert("definitions".to_string(), Value::Object(definitions));
+        }
+        document.extend(self.type_def_to_jtd(root));
+
+        json_document(&Value::Object(document), &self.indent)
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "jtd.json"
+    }
+
+    fn name(&self) -> &'static str {
+        "JSON Type Definition"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::GeneratorOptions;
+    use crate::parser::parse_json;
+
+    fn generate(json: &str) -> Value {
+        generate_with(json, GeneratorOptions::default())
+    }
+
+    fn generate_with(json: &str, options: GeneratorOptions) -> Value {
+        let schema = parse_json(json, &options).unwrap();
+        let output = JtdGenerator::new(options.shared).generate(&schema).unwrap();
+        serde_json::from_str(&output).unwrap()
+    }
+
+    #[test]
+    fn test_generate_properties_and_definitions() {
+        let document = generate(
+            r#"{"id": 1, "ratio": 0.5, "tags": ["a"], "users": [{"name": "a"}, {"name": "b", "age": 3}], "scores": [1, null]}"#,
+        );
+
+        assert_eq!(document["properties"]["id"], json!({ "type": "float64" }));
+        assert_eq!(
+            document["properties"]["ratio"],
+            json!({ "type": "float64" })
+        );
+        assert_eq!(
+            document["properties"]["tags"],
+            json!({ "elements": { "type": "string" } })
+        );
+        assert_eq!(
+            document["properties"]["users"],
+            json!({ "elements": { "ref": "Users" } })
+        );
+        assert_eq!(
+            document["properties"]["scores"],
+            json!({ "elements": { "type": "float64", "nullable": true } })
+        );
+        assert_eq!(
+            document["definitions"]["Users"],
+            json!({
+                "properties": { "name": { "type": "string" } },
+                "optionalProperties": { "age": { "type": "float64" } }
+            })
+        );
+        assert!(document["definitions"].get("Root").is_none());
+    }
+    #[test]
+    fn test_int32_only_for_observed_ranges_that_fit() {
+        let options = GeneratorOptions {
+            infer_constraints: true,
+            ..Default::default()
+        };
+        let document = generate_with(
+            r#"[{"small": -5, "large": 1, "ratio": 0.5}, {"small": 7, "large": 4102444800000, "ratio": 1.5}]"#,
+            options,
+        );
+
+        let item = &document["definitions"]["RootItem"]["properties"];
+        assert_eq!(item["small"], json!({ "type": "int32" }));
+        assert_eq!(item["large"], json!({ "type": "float64" }));
+        assert_eq!(item["ratio"], json!({ "type": "float64" }));
+    }
+}
@@ -0,0 +1,318 @@
+//! Kotlin data class generator
+
+use crate::ast::{FieldType, Schema, TypeDef};
+use crate::error::{AlchemistError, Result};
+use crate::generators::{split_nullable_union, CodeGenerator, Indent, SharedOptions};
+use crate::utils::{to_pascal_case, to_snake_case};
+use std::collections::HashSet;
+
+/// Kotlin hard keywords, which need backticks as property names
+const KOTLIN_KEYWORDS: &[&str] = &[
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+/// Kotlin data class generator for kotlinx.serialization
+///
+/// Properties are camelCase, with `@SerialName` keeping the original key
+/// wherever the two differ. Optional properties are nullable and default to
+/// `null`, so a missing key still decodes.
+pub struct KotlinGenerator {
+    shared: SharedOptions,
+    /// Whitespace of one indentation level
+    indent: String,
+}
+
+impl KotlinGenerator {
+    /// Create a new Kotlin generator with the shared options
+    pub fn new(shared: SharedOptions) -> Self {
+        let indent = shared.indent_unit(Indent::Spaces(4));
+        Self { shared, indent }
+    }
+
+    /// Generate a Kotlin data class from a type definition
+    fn generate_class(&self, type_def: &TypeDef) -> String {
+        let indent = &self.indent;
+        let mut output = String::new();
+
+        if let Some(doc) = &type_def.doc {
+            output.push_str(&format!("/** {} */\n", doc));
+        }
+        output.push_str("@Serializable\n");
+
+        // Data classes need at least one property
+        if type_def.fields.is_empty() {
+            output.push_str(&format!("class {}\n", type_def.name));
+            return output;
+        }
+
+        output.push_str(&format!("data class {}(\n", type_def.name));
+        let mut used = HashSet::new();
+        for field in &type_def.fields {
+            if let Some(doc) = &field.doc {
+                output.push_str(&format!("{}/** {} */\n", indent, doc));
+            }
+
+            let name = unique_name(property_name(&field.name), &mut used);
+            if name.trim_matches('`') != field.name {
+                output.push_str(&format!(
+                    "{}@SerialName({})\n",
+                    indent,
+                    kotlin_string(&field.name)
+                ));
+            }
+
+            let mut kotlin_type = self.field_type_to_kotlin(&field.field_type);
+            let optional = field.optional || self.shared.optional_fields;
+            if optional && !kotlin_type.ends_with('?') {
+                kotlin_type.push('?');
+            }
+            let default = if optional { " = null" } else { "" };
+            output.push_str(&format!(
+                "{}val {}: {}{},\n",
+                indent, name, kotlin_type, default
+            ));
+        }
+        output.push_str(")\n");
+
+        output
+    }
+
+    /// Convert AST FieldType to Kotlin type string
+    fn field_type_to_kotlin(&self, field_type: &FieldType) -> String {
+        match field_type {
+            FieldType::String | FieldType::Enum(_) | FieldType::Uuid => "String".to_string(),
+            // kotlinx-datetime is a separate library, so ISO dates stay strings
+            FieldType::DateTime(format) if !format.is_epoch() => "String".to_string(),
+            FieldType::Integer | FieldType::DateTime(_) => "Long".to_string(),
+            FieldType::Float => "Double".to_string(),
+            // Keeps the exact digits, whether the value is a number or a string
+            FieldType::Decimal => "JsonPrimitive".to_string(),
+            FieldType::Boolean => "Boolean".to_string(),
+            FieldType::Array(inner) => format!("List<{}>", self.field_type_to_kotlin(inner)),
+            FieldType::Set(inner) => format!("Set<{}>", self.field_type_to_kotlin(inner)),
+            FieldType::Optional(inner) => nullable(self.field_type_to_kotlin(inner)),
+            FieldType::Reference(name) => name.clone(),
+            FieldType::Union(types) => match split_nullable_union(types) {
+                (members, nullable_union) if members.len() == 1 => {
+                    let member = self.field_type_to_kotlin(members[0]);
+                    if nullable_union {
+                        nullable(member)
+                    } else {
+                        member
+                    }
+                }
+                (_, true) => "JsonElement?".to_string(),
+                _ => "JsonElement".to_string(),
+            },
+            FieldType::Map(key, value) => format!(
+                "Map<{}, {}>",
+                self.field_type_to_kotlin(key),
+                self.field_type_to_kotlin(value)
+            ),
+            FieldType::Null | FieldType::Any => "JsonElement".to_string(),
+        }
+    }
+}
+
+/// Make a Kotlin type nullable, once
+fn nullable(kotlin_type: String) -> String {
+    if kotlin_type.ends_with('?') {
+        kotlin_type
+    } else {
+        kotlin_type + "?"
+    }
+}
+
+/// Get the camelCase property name of a key, in backticks when it is a
+/// keyword
+fn property_name(key: &str) -> String {
+    let words: String = key
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let pascal = to_pascal_case(&to_snake_case(&words));
+    let mut chars = pascal.chars();
+    let mut name: String = match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => "field".to_string(),
+    };
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+
+    if KOTLIN_KEYWORDS.contains(&name.as_str()) {
+        format!("`{}`", name)
+    } else {
+        name
+    }
+}
+
+/// Suffix a name with a number until no earlier property uses it
+fn unique_name(name: String, used: &mut HashSet<String>) -> String {
+    let mut candidate = name.clone();
+    let mut counter = 1;
+    while !used.insert(candidate.clone()) {
+        counter += 1;
+        candidate = format!("{}{}", name, counter);
+    }
+    candidate
+}
+
+/// Quote text as a Kotlin string, escaping `$` so it is not a template
+fn kotlin_string(text: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '$' => quoted.push_str("\\$"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+impl CodeGenerator for KotlinGenerator {
+    fn generate(&self, schema: &Schema) -> Result<String> {
+        if schema.types.is_empty() {
+            return Err(AlchemistError::GenerationError(
+                "Schema has no types to generate".to_string(),
+            ));
+        }
+
+        let classes: Vec<String> = schema
+            .types
+            .iter()
+            .map(|type_def| self.generate_class(type_def))
+            .collect();
+
+        // Import only what the classes use
+        let uses = |text: &str| classes.iter().any(|class| class.contains(text));
+        let mut imports = Vec::new();
+        if uses("@SerialName(") {
+            imports.push("kotlinx.serialization.SerialName");
+        }
+        imports.push("kotlinx.serialization.Serializable");
+        if uses("JsonElement") {
+            imports.push("kotlinx.serialization.json.JsonElement");
+        }
+        if uses("JsonPrimitive") {
+            imports.push("kotlinx.serialization.json.JsonPrimitive");
+        }
+
+        let mut output = String::new();
+        output.push_str("// Generated by Alchemist\n");
+        output.push_str("// Do not edit manually\n\n");
+        for import in imports {
+            output.push_str(&format!("import {}\n", import));
+        }
+        output.push('\n');
+        output.push_str(&classes.join("\n"));
+
+        Ok(output)
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "kt"
+    }
+
+    fn name(&self) -> &'static str {
+        "Kotlin"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Field;
+    use crate::generators::GeneratorOptions;
+    use crate::parser::parse_json;
+
+    fn generate_from_json(json: &str) -> String {
+        let options = GeneratorOptions::default();
+        let schema = parse_json(json, &options).unwrap();
+        KotlinGenerator::new(options.shared)
+            .generate(&schema)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_generate_data_classes() {
+        let output = generate_from_json(
+            r#"{"id": 1, "ratio": 0.5, "tags": ["a"], "scores": [1, null], "owner": {"first-name": "x", "class": 1, "type": 2, "fn": 3, "match": 4}, "items": [{"a": 1}, {"b": "x"}], "extra": null}"#,
+        );
+
+        assert!(output.starts_with(
+            "// Generated by Alchemist\n// Do not edit manually\n\nimport kotlinx.serialization.SerialName\nimport kotlinx.serialization.Serializable\nimport kotlinx.serialization.json.JsonElement\n\n"
+        ));
+        assert!(output.contains(
+            "@Serializable\ndata class Root(\n    val id: Long,\n    val ratio: Double,\n    val tags: List<String>,\n    val scores: List<Long?>,\n    val owner: Owner,\n"
+        ));
+        assert!(output.contains("    val extra: JsonElement? = null,\n)\n"));
+        assert!(output.contains("    @SerialName(\"first-name\")\n    val firstName: String,\n"));
+        assert!(output.contains(
+            "    val `class`: Long,\n    val type: Long,\n    val fn: Long,\n    val match: Long,\n"
+        ));
+        assert!(output.contains("    val a: Long? = null,\n    val b: String? = null,\n"));
+        // Classes follow the schema's type order
+        assert!(output.find("class Root").unwrap() < output.find("class Owner").unwrap());
+    }
+
+    #[test]
+    fn test_maps_decimals_and_property_names() {
+        let mut schema = Schema::new("Root");
+        let mut root = TypeDef::new("Root");
+        root.add_field(Field::new(
+            "prices",
+            FieldType::Map(Box::new(FieldType::String), Box::new(FieldType::Decimal)),
+        ));
+        for name in ["user_name", "userName", "2fa", "$ref", "a\"b\\"] {
+            root.add_field(Field::new(name, FieldType::Boolean));
+        }
+        schema.add_type(root);
+        let output = KotlinGenerator::new(SharedOptions::default())
+            .generate(&schema)
+            .unwrap();
+
+        assert!(output.contains("import kotlinx.serialization.json.JsonPrimitive\n"));
+        assert!(!output.contains("JsonElement"));
+        assert!(output.contains("    val prices: Map<String, JsonPrimitive>,\n"));
+        assert!(output.contains("    @SerialName(\"user_name\")\n    val userName: Boolean,\n"));
+        assert!(output.contains("    @SerialName(\"userName\")\n    val userName2: Boolean,\n"));
+        assert!(output.contains("    @SerialName(\"2fa\")\n    val _2fa: Boolean,\n"));
+        assert!(output.contains("    @SerialName(\"\\$ref\")\n    val ref: Boolean,\n"));
+        assert!(output.contains("    @SerialName(\"a\\\"b\\\\\")\n    val aB: Boolean,\n"));
+    }
+}
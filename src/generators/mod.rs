@@ -1,60 +1,504 @@
 //! Code generators module
 
+pub mod c;
+pub mod clojure;
+pub mod csharp;
+pub mod fsharp;
+pub mod go;
+pub mod graphql;
+pub mod json_schema;
+pub mod jtd;
+pub mod kotlin;
+pub mod nim;
+pub mod php;
+pub mod pseudo;
 pub mod python;
+pub mod ruby;
 pub mod rust;
+pub mod swift;
+pub mod thrift;
 pub mod typescript;
 pub mod zod;
 
-use crate::ast::Schema;
-use crate::error::Result;
+use crate::ast::{walk_type_def, FieldType, ReferenceCounter, Schema, TypeOrigin};
+use crate::error::{AlchemistError, Result};
+use crate::formats::OutputFormat;
+use crate::parser::{FieldOrdering, MixedArrayRepr, NullOnlyType, NullPolicy};
+use crate::utils::{to_pascal_case, to_snake_case};
+use clap::ValueEnum;
+use clojure::ClojureOptions;
+use csharp::CSharpOptions;
+use fsharp::FSharpOptions;
+use go::GoOptions;
+use php::PhpOptions;
+use python::PythonOptions;
+use ruby::RubyOptions;
+use rust::RustOptions;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use typescript::TsOptions;
 
 /// Options for code generation
-#[derive(Debug, Clone)]
+///
+/// Options read by the parser sit at the top level, those read by several
+/// generators in [`SharedOptions`] and language-specific ones in a section
+/// per language. Each generator takes its own section and the shared one.
+/// The sections are flattened when deserializing, so option JSON is a single
+/// flat object.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
 pub struct GeneratorOptions {
     /// Root type name
     pub root_name: String,
+    /// Name of the wrapper type generated around a root array (parser)
+    pub wrapper_name: Option<String>,
+    /// Whether the element type of a root array of objects is the root, with no wrapper (parser)
+    pub item_as_root: bool,
+    /// Whether types below the root are named `T1`, `T2`, ... (parser)
+    pub minify_identifiers: bool,
+    /// Whether every type gets a `NamePartial` copy with all fields optional (parser)
+    pub emit_partial: bool,
+    /// Whether fields missing from some samples, or null in them, are marked optional (parser)
+    pub optional_inference: bool,
+    /// Options read by several generators, `flatten` and `optional_fields`
+    /// also by the parser
+    #[serde(flatten)]
+    pub shared: SharedOptions,
+    /// Rust options
+    #[serde(flatten)]
+    pub rust: RustOptions,
+    /// TypeScript options, the decimal representation also applies to Zod
+    #[serde(flatten)]
+    pub typescript: TsOptions,
+    /// Python options
+    #[serde(flatten)]
+    pub python: PythonOptions,
+    /// PHP options
+    #[serde(flatten)]
+    pub php: PhpOptions,
+    /// Ruby options
+    #[serde(flatten)]
+    pub ruby: RubyOptions,
+    /// F# options
+    #[serde(flatten)]
+    pub fsharp: FSharpOptions,
+    /// Go options
+    #[serde(flatten)]
+    pub go: GoOptions,
+    /// Clojure options
+    #[serde(flatten)]
+    pub clojure: ClojureOptions,
+    /// C# options
+    #[serde(flatten)]
+    pub csharp: CSharpOptions,
+    /// Maximum number of fields allowed on a single type (parser)
+    pub max_fields: Option<usize>,
+    /// Whether to detect epoch timestamps in time-named integer fields (parser)
+    pub epoch_dates: bool,
+    /// Whether ISO 8601 date and date-time strings become date-times (parser)
+    pub iso_dates: bool,
+    /// Whether canonical UUID strings become UUIDs (parser)
+    pub uuids: bool,
+    /// Most distinct values a string field of merged objects can have to become an enum, 0 for none (parser)
+    pub enum_threshold: usize,
+    /// Whether maps with all-integer keys get integer key types (parser)
+    pub typed_map_keys: bool,
+    /// Whether plural fields of distinct strings or integers become sets (parser)
+    pub detect_sets: bool,
+    /// Whether array element and map value types are named in the singular, e.g. `servers: Vec<Server>` (parser)
+    pub singular_names: bool,
+    /// Whether number ranges and string lengths seen in the samples become constraints (parser)
+    pub infer_constraints: bool,
+    /// Maximum nesting depth of generated types below the root (parser)
+    pub max_depth: Option<usize>,
+    /// Deepest nesting of arrays and objects, or GraphQL list types, accepted in the input, which fails beyond it (parser)
+    pub recursion_limit: usize,
+    /// Largest union kept before it collapses to `Any` (parser)
+    pub max_union_members: usize,
+    /// Field splitting arrays of objects into one type per value, as a tagged union (parser)
+    pub tagged_union: Option<String>,
+    /// Whether nested types with identical fields are generated once and shared (parser)
+    pub share_types: bool,
+    /// Treatment of fields that are only ever null (parser)
+    pub null_only_type: NullOnlyType,
+    /// Treatment of fields that are null in some samples (parser)
+    pub null_policy: NullPolicy,
+    /// Type of arrays mixing primitives with objects or arrays (parser)
+    pub mixed_array_repr: MixedArrayRepr,
+    /// Order of the fields within each type (parser)
+    pub field_ordering: FieldOrdering,
+    /// Field descriptions keyed by dotted field path, e.g. `owner.id` (parser)
+    pub descriptions: HashMap<String, String>,
+    /// Patterns of numeric fields typed as decimals, e.g. `*.price` (parser)
+    pub money_fields: Vec<String>,
+}
+
+impl Default for GeneratorOptions {
+    fn default() -> Self {
+        Self {
+            root_name: "Root".to_string(),
+            wrapper_name: None,
+            item_as_root: false,
+            minify_identifiers: false,
+            emit_partial: false,
+            optional_inference: true,
+            shared: SharedOptions::default(),
+            rust: RustOptions::default(),
+            typescript: TsOptions::default(),
+            python: PythonOptions::default(),
+            php: PhpOptions::default(),
+            ruby: RubyOptions::default(),
+            fsharp: FSharpOptions::default(),
+            go: GoOptions::default(),
+            clojure: ClojureOptions::default(),
+            csharp: CSharpOptions::default(),
+            max_fields: None,
+            epoch_dates: false,
+            iso_dates: true,
+            uuids: true,
+            enum_threshold: 10,
+            typed_map_keys: false,
+            detect_sets: false,
+            singular_names: false,
+            infer_constraints: false,
+            max_depth: None,
+            recursion_limit: 128,
+            max_union_members: 8,
+            tagged_union: None,
+            share_types: false,
+            null_only_type: NullOnlyType::default(),
+            null_policy: NullPolicy::default(),
+            mixed_array_repr: MixedArrayRepr::default(),
+            field_ordering: FieldOrdering::default(),
+            descriptions: HashMap::new(),
+            money_fields: Vec::new(),
+        }
+    }
+}
+
+/// Options read by several generators
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SharedOptions {
+    /// Whether the wrapper around a root array becomes a type alias of the array, with `item_as_root` a `{Root}List` one (parser, Rust, TypeScript, Zod, Python, Go)
+    pub flatten: bool,
     /// Whether to generate optional fields
     pub optional_fields: bool,
-    /// Whether to use readonly modifier (TypeScript)
+    /// Whether to use readonly modifier (TypeScript, PHP)
     pub readonly: bool,
-    /// Derive macros to add (Rust)
-    pub derive_macros: Vec<String>,
-    /// Whether to use pub modifier for fields (Rust)
+    /// Whether to use pub modifier for fields (Rust, Nim)
     pub public_fields: bool,
+    /// Whether unions and string enums get a catch-all member so unknown values still deserialize (Rust, TypeScript, Zod, Python)
+    pub enum_catchall: bool,
+    /// Indentation of the generated code, instead of each language's conventional one
+    pub indent: Option<Indent>,
+    /// Case convention for field names, with the original keys kept by rename attributes (Rust, TypeScript)
+    pub rename_all: Option<RenameAll>,
 }
 
-impl Default for GeneratorOptions {
+impl Default for SharedOptions {
     fn default() -> Self {
         Self {
-            root_name: "Root".to_string(),
+            flatten: false,
             optional_fields: false,
             readonly: false,
-            derive_macros: vec![
-                "Debug".to_string(),
-                "Clone".to_string(),
-                "Serialize".to_string(),
-                "Deserialize".to_string(),
-            ],
             public_fields: true,
+            enum_catchall: false,
+            indent: None,
+            rename_all: None,
+        }
+    }
+}
+
+impl SharedOptions {
+    /// Whitespace of one indentation level: the `indent` option, or the
+    /// generator's `conventional` one without it
+    pub(crate) fn indent_unit(&self, conventional: Indent) -> String {
+        self.indent.unwrap_or(conventional).unit()
+    }
+
+    /// Name a field with this key gets under the `rename_all` convention,
+    /// or the key itself without one
+    pub fn renamed_key(&self, key: &str) -> String {
+        match self.rename_all {
+            Some(rename_all) => rename_all.apply(key),
+            None => key.to_string(),
         }
     }
 }
 
+/// Case convention for field names, `#[serde(rename_all)]` in Rust
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+pub enum RenameAll {
+    #[value(name = "lowercase")]
+    #[serde(rename = "lowercase")]
+    Lowercase,
+    #[value(name = "UPPERCASE")]
+    #[serde(rename = "UPPERCASE")]
+    Uppercase,
+    #[value(name = "PascalCase")]
+    #[serde(rename = "PascalCase")]
+    PascalCase,
+    #[value(name = "camelCase")]
+    #[serde(rename = "camelCase")]
+    CamelCase,
+    #[value(name = "snake_case")]
+    #[serde(rename = "snake_case")]
+    SnakeCase,
+    #[value(name = "SCREAMING_SNAKE_CASE")]
+    #[serde(rename = "SCREAMING_SNAKE_CASE")]
+    ScreamingSnakeCase,
+    #[value(name = "kebab-case")]
+    #[serde(rename = "kebab-case")]
+    KebabCase,
+    #[value(name = "SCREAMING-KEBAB-CASE")]
+    #[serde(rename = "SCREAMING-KEBAB-CASE")]
+    ScreamingKebabCase,
+}
+
+impl RenameAll {
+    /// The convention's name as written in the serde attribute
+    pub fn serde_name(self) -> &'static str {
+        match self {
+            RenameAll::Lowercase => "lowercase",
+            RenameAll::Uppercase => "UPPERCASE",
+            RenameAll::PascalCase => "PascalCase",
+            RenameAll::CamelCase => "camelCase",
+            RenameAll::SnakeCase => "snake_case",
+            RenameAll::ScreamingSnakeCase => "SCREAMING_SNAKE_CASE",
+            RenameAll::KebabCase => "kebab-case",
+            RenameAll::ScreamingKebabCase => "SCREAMING-KEBAB-CASE",
+        }
+    }
+
+    /// The key serde expects for a field under this convention, given its
+    /// key or identifier in any case
+    pub fn apply(self, field: &str) -> String {
+        let field = to_snake_case(field);
+        match self {
+            RenameAll::Lowercase | RenameAll::SnakeCase => field,
+            RenameAll::Uppercase | RenameAll::ScreamingSnakeCase => field.to_ascii_uppercase(),
+            RenameAll::PascalCase => to_pascal_case(&field),
+            RenameAll::CamelCase => {
+                let pascal = to_pascal_case(&field);
+                let mut chars = pascal.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => pascal,
+                }
+            }
+            RenameAll::KebabCase => field.replace('_', "-"),
+            RenameAll::ScreamingKebabCase => field.replace('_', "-").to_ascii_uppercase(),
+        }
+    }
+}
+
+/// One level of indentation in generated code
+///
+/// Parsed from a width such as `4`, or `tab`. In option JSON it is a number
+/// or one of those strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "IndentSetting")]
+pub enum Indent {
+    /// This many spaces, from 1 to 8
+    Spaces(u8),
+    /// A single tab
+    Tab,
+}
+
+impl Indent {
+    /// The whitespace of one level
+    pub fn unit(self) -> String {
+        match self {
+            Indent::Spaces(width) => " ".repeat(width as usize),
+            Indent::Tab => "\t".to_string(),
+        }
+    }
+}
+
+impl FromStr for Indent {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "tab" | "tabs" => Ok(Indent::Tab),
+            _ => match s.parse::<u8>() {
+                Ok(width @ 1..=8) => Ok(Indent::Spaces(width)),
+                _ => Err("expected a width from 1 to 8, or 'tab'".to_string()),
+            },
+        }
+    }
+}
+
+/// How an `Indent` is written in option JSON
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum IndentSetting {
+    Width(u64),
+    Name(String),
+}
+
+impl TryFrom<IndentSetting> for Indent {
+    type Error = String;
+
+    fn try_from(setting: IndentSetting) -> std::result::Result<Self, Self::Error> {
+        match setting {
+            IndentSetting::Width(width) => width.to_string().parse(),
+            IndentSetting::Name(name) => name.parse(),
+        }
+    }
+}
+
+/// Create the generator for an output format
+pub fn for_format(format: OutputFormat, options: GeneratorOptions) -> Box<dyn CodeGenerator> {
+    log::debug!("generator selected format={}", format);
+    let shared = options.shared;
+    match format {
+        OutputFormat::Rust => Box::new(rust::RustGenerator::new(options.rust, shared)),
+        OutputFormat::Typescript => Box::new(typescript::TypeScriptGenerator::new(
+            options.typescript,
+            shared,
+        )),
+        OutputFormat::Zod => Box::new(zod::ZodGenerator::new(options.typescript, shared)),
+        OutputFormat::Python => Box::new(python::PythonGenerator::new(options.python, shared)),
+        OutputFormat::Nim => Box::new(nim::NimGenerator::new(shared)),
+        OutputFormat::Php => Box::new(php::PhpGenerator::new(options.php, shared)),
+        OutputFormat::Ruby => Box::new(ruby::RubyGenerator::new(options.ruby, shared)),
+        OutputFormat::FSharp => Box::new(fsharp::FSharpGenerator::new(options.fsharp, shared)),
+        OutputFormat::Go => Box::new(go::GoGenerator::new(options.go, shared)),
+        OutputFormat::C => Box::new(c::CGenerator::new(shared)),
+        OutputFormat::Kotlin => Box::new(kotlin::KotlinGenerator::new(shared)),
+        OutputFormat::Swift => Box::new(swift::SwiftGenerator::new(shared)),
+        OutputFormat::CSharp => Box::new(csharp::CSharpGenerator::new(options.csharp, shared)),
+        OutputFormat::Clojure => Box::new(clojure::ClojureGenerator::new(options.clojure, shared)),
+        OutputFormat::Jtd => Box::new(jtd::JtdGenerator::new(shared)),
+        OutputFormat::JsonSchema => Box::new(json_schema::JsonSchemaGenerator::new(shared)),
+        OutputFormat::Graphql => Box::new(graphql::GraphqlGenerator::new(shared)),
+        OutputFormat::Thrift => Box::new(thrift::ThriftGenerator::new(shared)),
+        OutputFormat::Pseudo => Box::new(pseudo::PseudoGenerator::new(shared)),
+    }
+}
+
+/// Pretty-print a JSON document with `indent` as one level, ending in a
+/// newline
+pub(crate) fn json_document(document: &serde_json::Value, indent: &str) -> Result<String> {
+    let mut output = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut output, formatter);
+    document
+        .serialize(&mut serializer)
+        .map_err(|e| AlchemistError::GenerationError(e.to_string()))?;
+    output.push(b'\n');
+    String::from_utf8(output).map_err(|e| AlchemistError::GenerationError(e.to_string()))
+}
+
+/// Split a union into its members other than `null`, without duplicates, and
+/// whether `null` was one of them
+///
+/// Shared by the generators for dynamically typed languages, which spell
+/// nullability separately from the union itself.
+pub(crate) fn split_nullable_union(types: &[FieldType]) -> (Vec<&FieldType>, bool) {
+    let mut members: Vec<&FieldType> = Vec::new();
+    for member in types {
+        if !matches!(member, FieldType::Null) && !members.contains(&member) {
+            members.push(member);
+        }
+    }
+    let nullable = types.iter().any(|t| matches!(t, FieldType::Null));
+    (members, nullable)
+}
+
+/// Names of the types that can reach themselves through their fields
+pub(crate) fn recursive_types(schema: &Schema) -> HashSet<&str> {
+    let edges: HashMap<&str, Vec<String>> = schema
+        .types
+        .iter()
+        .map(|t| {
+            let mut references = ReferenceCounter::default();
+            walk_type_def(&mut references, t);
+            (t.name.as_str(), references.counts.into_keys().collect())
+        })
+        .collect();
+
+    schema
+        .types
+        .iter()
+        .map(|t| t.name.as_str())
+        .filter(|&start| {
+            let mut pending: Vec<&str> = edges
+                .get(start)
+                .into_iter()
+                .flatten()
+                .map(String::as_str)
+                .collect();
+            let mut seen = HashSet::new();
+            while let Some(name) = pending.pop() {
+                if name == start {
+                    return true;
+                }
+                if seen.insert(name) {
+                    pending.extend(edges.get(name).into_iter().flatten().map(String::as_str));
+                }
+            }
+            false
+        })
+        .collect()
+}
+
+/// A root array as a type alias, when `flatten` replaces the wrapper
+/// around it
+pub(crate) struct RootAlias<'a> {
+    pub name: &'a str,
+    /// The wrapped array
+    pub field_type: &'a FieldType,
+    /// The wrapper's doc, or else that of its `items` field
+    pub doc: Option<&'a str>,
+}
+
+/// The alias replacing the wrapper the parser generates around a root
+/// array, if `flatten` is set
+///
+/// The wrapper is the root, or with `item_as_root` a `{Root}List` type next
+/// to it. A root object with a single field is never flattened, since that
+/// field is part of the data.
+pub(crate) fn root_alias<'a>(schema: &'a Schema, options: &SharedOptions) -> Option<RootAlias<'a>> {
+    if !options.flatten {
+        return None;
+    }
+    let wrapper = schema
+        .types
+        .iter()
+        .find(|t| t.origin == TypeOrigin::ArrayWrapper)?;
+    match wrapper.fields.as_slice() {
+        [items] => Some(RootAlias {
+            name: &wrapper.name,
+            field_type: &items.field_type,
+            doc: wrapper.doc.as_deref().or(items.doc.as_deref()),
+        }),
+        _ => None,
+    }
+}
+
 /// Trait for code generators
 ///
 /// This trait defines the interface for generating code from an intermediate AST.
 /// Each target language (Rust, TypeScript, Zod) implements this trait to produce
 /// language-specific output.
 ///
+/// Output must be reproducible: the same schema and options always produce
+/// byte-identical code, with no timestamps, paths or other details of the
+/// run, so generated files can be vendored and diffed.
+///
 /// # Example
 ///
 /// ```ignore
-/// use alchemist::generators::{CodeGenerator, GeneratorOptions};
-/// use alchemist::generators::typescript::TypeScriptGenerator;
+/// use alchemist::generators::{CodeGenerator, SharedOptions};
+/// use alchemist::generators::typescript::{TsOptions, TypeScriptGenerator};
 /// use alchemist::ast::Schema;
 ///
 /// let schema = Schema::new("User");
-/// let generator = TypeScriptGenerator::new(GeneratorOptions::default());
+/// let generator = TypeScriptGenerator::new(TsOptions::default(), SharedOptions::default());
 /// let code = generator.generate(&schema)?;
 /// ```
 pub trait CodeGenerator {
@@ -84,3 +528,85 @@ pub trait CodeGenerator {
     /// Returns a human-readable name for the generator
     fn name(&self) -> &'static str;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::OutputFormat;
+    use crate::parser::parse_json;
+    use clap::ValueEnum;
+
+    #[test]
+    fn test_indent_option_sets_the_unit() {
+        assert_eq!("tab".parse(), Ok(Indent::Tab));
+        assert_eq!("2".parse(), Ok(Indent::Spaces(2)));
+        assert!("0".parse::<Indent>().is_err());
+        assert!("9".parse::<Indent>().is_err());
+
+        let json = r#"{"owner": {"id": 1}}"#;
+        let options = GeneratorOptions {
+            shared: SharedOptions {
+                indent: Some(Indent::Spaces(4)),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let schema = parse_json(json, &options).unwrap();
+        let output = for_format(OutputFormat::Typescript, options)
+            .generate(&schema)
+            .unwrap();
+        assert!(output.contains("\n    owner: Owner;\n"));
+
+        let options: GeneratorOptions = serde_json::from_str(r#"{"indent": "tab"}"#).unwrap();
+        assert_eq!(options.shared.indent, Some(Indent::Tab));
+        let options: GeneratorOptions = serde_json::from_str(r#"{"indent": 3}"#).unwrap();
+        assert_eq!(options.shared.indent, Some(Indent::Spaces(3)));
+    }
+
+    #[test]
+    fn test_every_generator_indents_with_the_option() {
+        let json = r#"{"id": 1, "price": "12.50", "tags": ["a"], "mixed": [1, "a", null], "owner": {"name": "x", "seen": "2024-01-02T15:04:05Z"}, "users": [{"a": 1}, {"b": true}], "counts": {"x": 1}}"#;
+        for format in OutputFormat::value_variants() {
+            let options = GeneratorOptions {
+                shared: SharedOptions {
+                    indent: Some(Indent::Tab),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let schema = parse_json(json, &options).unwrap();
+            let output = for_format(*format, options).generate(&schema).unwrap();
+            for line in output.lines() {
+                assert!(
+                    !line.trim_start_matches('\t').starts_with("  "),
+                    "{} indents {:?} with spaces",
+                    format,
+                    line
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_output_is_byte_identical_across_runs() {
+        let json = r#"{"id": 1, "tags": ["a", "b"], "mixed": [1, "a", null], "owner": {"name": "x"}, "users": [{"a": 1}, {"b": true}], "counts": {"x": 1}}"#;
+        let generate = |format| {
+            let options = GeneratorOptions {
+                detect_sets: true,
+                emit_partial: true,
+                ..Default::default()
+            };
+            let schema = parse_json(json, &options).unwrap();
+            for_format(format, options).generate(&schema).unwrap()
+        };
+
+        for &format in OutputFormat::value_variants() {
+            assert_eq!(
+                generate(format),
+                generate(format),
+                "{} output differs",
+                format
+            );
+        }
+    }
+}
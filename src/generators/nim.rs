@@ -0,0 +1,319 @@
+//! Nim object type generator
+
+use crate::ast::{Field, FieldType, Schema, TypeDef};
+use crate::error::{AlchemistError, Result};
+use crate::generators::{CodeGenerator, Indent, SharedOptions};
+
+/// Nim keywords that must be quoted with backticks to be used as field names
+const NIM_KEYWORDS: &[&str] = &[
+    "addr",
+    "and",
+    "as",
+    "asm",
+    "bind",
+    "block",
+    "break",
+    "case",
+    "cast",
+    "concept",
+    "const",
+    "continue",
+    "converter",
+    "defer",
+    "discard",
+    "distinct",
+    "div",
+    "do",
+    "elif",
+    "else",
+    "end",
+    "enum",
+    "except",
+    "export",
+    "finally",
+    "for",
+    "from",
+    "func",
+    "if",
+    "import",
+    "in",
+    "include",
+    "interface",
+    "is",
+    "isnot",
+    "iterator",
+    "let",
+    "macro",
+    "method",
+    "mixin",
+    "mod",
+    "nil",
+    "not",
+    "notin",
+    "object",
+    "of",
+    "or",
+    "out",
+    "proc",
+    "ptr",
+    "raise",
+    "ref",
+    "return",
+    "shl",
+    "shr",
+    "static",
+    "template",
+    "try",
+    "tuple",
+    "type",
+    "using",
+    "var",
+    "when",
+    "while",
+    "xor",
+    "yield",
+];
+
+/// Nim object type generator
+///
+/// The generated objects can be filled from `std/json` with `to(node, T)`,
+/// so every JSON key must be usable as a Nim field name.
+pub struct NimGenerator {
+    shared: SharedOptions,
+    /// Whitespace of one indentation level
+    indent: String,
+}
+
+impl NimGenerator {
+    /// Create a new Nim generator with the shared options
+    pub fn new(shared: SharedOptions) -> Self {
+        let indent = shared.indent_unit(Indent::Spaces(2));
+        Self { shared, indent }
+    }
+
+    /// Generate a Nim object type from a type definition
+    fn generate_object(&self, type_def: &TypeDef) -> Result<String> {
+        let mut output = String::new();
+        let export = if self.shared.public_fields { "*" } else { "" };
+        let indent = &self.indent;
+
+        output.push_str(&format!("{}{}* = object\n", indent, type_def.name));
+
+        if let Some(doc) = &type_def.doc {
+            output.push_str(&format!("{}{}## {}\n", indent, indent, doc));
+        }
+
+        for field in &type_def.fields {
+            let mut nim_type = self.field_type_to_nim(&field.field_type);
+            if (field.optional || self.shared.optional_fields)
+                && !matches!(field.field_type, FieldType::Optional(_))
+            {
+                nim_type = format!("Option[{}]", nim_type);
+            }
+
+            let doc = match &field.doc {
+                Some(doc) => format!(" ## {}", doc),
+                None => String::new(),
+            };
+
+            output.push_str(&format!(
+                "{}{}{}: {}{}\n",
+                indent.repeat(2),
+                field_name(type_def, field)?,
+                export,
+                nim_type,
+                doc
+            ));
+        }
+
+        Ok(output)
+    }
+
+    /// Convert AST FieldType to Nim type string
+    fn field_type_to_nim(&self, field_type: &FieldType) -> String {
+        match field_type {
+            FieldType::String | FieldType::Enum(_) | FieldType::Uuid => "string".to_string(),
+            FieldType::DateTime(format) if !format.is_epoch() => "string".to_string(),
+            FieldType::Integer | FieldType::DateTime(_) => "int64".to_string(),
+            // Nim has no decimal type in the standard library
+            FieldType::Float | FieldType::Decimal => "float64".to_string(),
+            FieldType::Boolean => "bool".to_string(),
+            FieldType::Array(inner) | FieldType::Set(inner) => {
+                format!("seq[{}]", self.field_type_to_nim(inner))
+            }
+            FieldType::Optional(inner) => format!("Option[{}]", self.field_type_to_nim(inner)),
+            FieldType::Reference(name) => name.clone(),
+            // std/json only unmarshals tables with string keys
+            FieldType::Map(_, value) => {
+                format!("Table[string, {}]", self.field_type_to_nim(value))
+            }
+            FieldType::Null | FieldType::Union(_) | FieldType::Any => "JsonNode".to_string(),
+        }
+    }
+}
+
+/// Get the field name, quoting keywords in backticks
+///
+/// `std/json` matches JSON keys to field names and has no way to map a field
+/// to another key, so a key that isn't a Nim identifier is an error.
+fn field_name(type_def: &TypeDef, field: &Field) -> Result<String> {
+    let name = &field.name;
+    let is_identifier = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !name.ends_with('_')
+        && !name.contains("__");
+
+    if !is_identifier {
+        return Err(AlchemistError::GenerationError(format!(
+            "JSON key {:?} of type '{}' is not a Nim identifier, which std/json cannot map to a field",
+            name, type_def.name
+        )));
+    }
+    if NIM_KEYWORDS.contains(&name.as_str()) {
+        Ok(format!("`{}`", name))
+    } else {
+        Ok(name.clone())
+    }
+}
+
+/// Whether any field of the schema uses a type matching `predicate`
+fn schema_uses(schema: &Schema, predicate: fn(&FieldType) -> bool) -> bool {
+    fn visit(field_type: &FieldType, predicate: fn(&FieldType) -> bool) -> bool {
+        predicate(field_type)
+            || match field_type {
+                FieldType::Array(inner) | FieldType::Set(inner) | FieldType::Optional(inner) => {
+                    visit(inner, predicate)
+                }
+                FieldType::Map(key, value) => visit(key, predicate) || visit(value, predicate),
+                FieldType::Union(types) => types.iter().any(|t| visit(t, predicate)),
+                _ => false,
+            }
+    }
+
+    schema
+        .types
+        .iter()
+        .flat_map(|t| &t.fields)
+        .any(|f| visit(&f.field_type, predicate))
+}
+
+impl CodeGenerator for NimGenerator {
+    fn generate(&self, schema: &Schema) -> Result<String> {
+        if schema.types.is_empty() {
+            return Err(AlchemistError::GenerationError(
+                "Schema has no types to generate".to_string(),
+            ));
+        }
+
+        let mut output = String::new();
+
+        // Add header comment
+        output.push_str("# Generated by Alchemist\n");
+        output.push_str("# Do not edit manually\n\n");
+
+        // Import only the modules the types need
+        let optional_fields = self.shared.optional_fields
+            || schema
+                .types
+                .iter()
+                .flat_map(|t| &t.fields)
+                .any(|f| f.optional);
+        let mut imports = Vec::new();
+        if schema_uses(schema, |t| {
+            matches!(t, FieldType::Null | FieldType::Union(_) | FieldType::Any)
+        }) {
+            imports.push("json");
+        }
+        if optional_fields || schema_uses(schema, |t| matches!(t, FieldType::Optional(_))) {
+            imports.push("options");
+        }
+        if schema_uses(schema, |t| matches!(t, FieldType::Map(_, _))) {
+            imports.push("tables");
+        }
+        if !imports.is_empty() {
+            output.push_str(&format!("import std/[{}]\n\n", imports.join(", ")));
+        }
+
+        // A single type section lets objects reference each other in any order
+        output.push_str("type\n");
+        let objects = schema
+            .iter_topological(false)
+            .map(|type_def| self.generate_object(type_def))
+            .collect::<Result<Vec<_>>>()?;
+        output.push_str(&objects.join("\n"));
+
+        Ok(output)
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "nim"
+    }
+
+    fn name(&self) -> &'static str {
+        "Nim"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user_schema() -> Schema {
+        let mut schema = Schema::new("User");
+        let mut user = TypeDef::new("User");
+        user.add_field(Field::new("name", FieldType::String));
+        user.add_field(Field::new("age", FieldType::Integer).optional());
+        user.add_field(Field::new(
+            "scores",
+            FieldType::Array(Box::new(FieldType::Float)),
+        ));
+        user.add_field(Field::new(
+            "labels",
+            FieldType::Map(Box::new(FieldType::String), Box::new(FieldType::String)),
+        ));
+        user.add_field(Field::new("type", FieldType::String));
+        schema.add_type(user);
+        schema
+    }
+
+    #[test]
+    fn test_generate_object_type() {
+        let generator = NimGenerator::new(SharedOptions::default());
+        let output = generator.generate(&user_schema()).unwrap();
+
+        assert!(output.contains("import std/[options, tables]\n"));
+        assert!(output.contains("type\n  User* = object\n"));
+        assert!(output.contains("    name*: string\n"));
+        assert!(output.contains("    age*: Option[int64]\n"));
+        assert!(output.contains("    scores*: seq[float64]\n"));
+        assert!(output.contains("    labels*: Table[string, string]\n"));
+        assert!(output.contains("    `type`*: string\n"));
+    }
+
+    #[test]
+    fn test_export_marker_follows_public_fields() {
+        let generator = NimGenerator::new(SharedOptions {
+            public_fields: false,
+            ..Default::default()
+        });
+        let output = generator.generate(&user_schema()).unwrap();
+
+        assert!(output.contains("  User* = object\n"));
+        assert!(output.contains("    name: string\n"));
+    }
+
+    #[test]
+    fn test_keys_that_are_not_identifiers_are_rejected() {
+        for key in ["q\"k", "2x", "first-name", "trailing_"] {
+            let mut schema = Schema::new("Root");
+            let mut root = TypeDef::new("Root");
+            root.add_field(Field::new(key, FieldType::Integer));
+            schema.add_type(root);
+
+            let error = NimGenerator::new(SharedOptions::default())
+                .generate(&schema)
+                .unwrap_err();
+            assert!(error.to_string().contains(&format!("{:?}", key)));
+        }
+    }
+}
@@ -0,0 +1,570 @@
+//! PHP 8 class generator
+
+use crate::ast::{Field, FieldType, Schema, TypeDef};
+use crate::error::{AlchemistError, Result};
+use crate::generators::{split_nullable_union, CodeGenerator, Indent, SharedOptions};
+use serde::Deserialize;
+use std::collections::HashSet;
+
+/// PHP keywords and reserved type names, which get a trailing underscore as
+/// class names
+const PHP_RESERVED: &[&str] = &[
+    "__halt_compiler",
+    "abstract",
+    "and",
+    "array",
+    "as",
+    "bool",
+    "break",
+    "callable",
+    "case",
+    "catch",
+    "class",
+    "clone",
+    "const",
+    "continue",
+    "declare",
+    "default",
+    "die",
+    "do",
+    "echo",
+    "else",
+    "elseif",
+    "empty",
+    "enddeclare",
+    "endfor",
+    "endforeach",
+    "endif",
+    "endswitch",
+    "endwhile",
+    "enum",
+    "eval",
+    "exit",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "float",
+    "fn",
+    "for",
+    "foreach",
+    "function",
+    "global",
+    "goto",
+    "if",
+    "implements",
+    "include",
+    "include_once",
+    "instanceof",
+    "insteadof",
+    "int",
+    "interface",
+    "isset",
+    "iterable",
+    "list",
+    "match",
+    "mixed",
+    "namespace",
+    "never",
+    "new",
+    "null",
+    "numeric",
+    "object",
+    "or",
+    "parent",
+    "print",
+    "private",
+    "protected",
+    "public",
+    "readonly",
+    "require",
+    "require_once",
+    "resource",
+    "return",
+    "self",
+    "static",
+    "string",
+    "switch",
+    "throw",
+    "trait",
+    "true",
+    "try",
+    "unset",
+    "use",
+    "var",
+    "void",
+    "while",
+    "xor",
+    "yield",
+];
+
+/// Options of the PHP generator
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PhpOptions {
+    /// Whether to declare properties as promoted constructor parameters
+    #[serde(rename = "php_promoted")]
+    pub promoted: bool,
+}
+
+/// PHP 8 class generator
+///
+/// Emits one class per type with typed public properties, or with promoted
+/// constructor parameters when `php_promoted` is set.
+pub struct PhpGenerator {
+    options: PhpOptions,
+    shared: SharedOptions,
+    /// Whitespace of one indentation level
+    indent: String,
+}
+
+impl PhpGenerator {
+    /// Create a new PHP generator with its own and the shared options
+    pub fn new(options: PhpOptions, shared: SharedOptions) -> Self {
+        let indent = shared.indent_unit(Indent::Spaces(4));
+        Self {
+            options,
+            shared,
+            indent,
+        }
+    }
+
+    /// Generate a PHP class from a type definition
+    fn generate_class(&self, type_def: &TypeDef) -> String {
+        let mut output = String::new();
+
+        if let Some(doc) = &type_def.doc {
+            output.push_str(&format!("/**\n * {}\n */\n", doc));
+        }
+        output.push_str(&format!("final class {}\n{{\n", class_name(&type_def.name)));
+
+        // Distinct keys can sanitize to the same property name
+        let mut used = HashSet::new();
+        let fields: Vec<(&Field, String)> = type_def
+            .fields
+            .iter()
+            .map(|field| (field, unique_name(property_name(field), &mut used)))
+            .collect();
+
+        if self.options.promoted {
+            output.push_str(&self.generate_constructor(fields));
+        } else {
+            output.push_str(&self.generate_properties(&fields));
+        }
+
+        output.push_str("}\n");
+        output
+    }
+
+    /// Generate typed property declarations
+    fn generate_properties(&self, fields: &[(&Field, String)]) -> String {
+        let indent = &self.indent;
+        let mut output = String::new();
+
+        for (index, (field, name)) in fields.iter().enumerate() {
+            if index > 0 && (field.doc.is_some() || self.var_doc(field).is_some()) {
+                output.push('\n');
+            }
+            match (&field.doc, self.var_doc(field)) {
+                (Some(doc), Some(var)) => output.push_str(&format!(
+                    "{indent}/**\n{indent} * {doc}\n{indent} *\n{indent} * @var {var}\n{indent} */\n"
+                )),
+                (Some(doc), None) => output.push_str(&format!("{}/** {} */\n", indent, doc)),
+                (None, Some(var)) => output.push_str(&format!("{}/** @var {} */\n", indent, var)),
+                (None, None) => {}
+            }
+
+            // Readonly properties cannot declare a default value
+            let default = if self.is_optional(field) && !self.shared.readonly {
+                " = null"
+            } else {
+                ""
+            };
+            output.push_str(&format!(
+                "{}{} ${}{};\n",
+                indent,
+                self.declaration(field),
+                name,
+                default
+            ));
+        }
+
+        output
+    }
+
+    /// Generate a constructor whose parameters are promoted to properties
+    fn generate_constructor(&self, mut fields: Vec<(&Field, String)>) -> String {
+        let indent = &self.indent;
+        let mut output = String::new();
+
+        // Optional parameters must follow required ones
+        fields.sort_by_key(|(f, _)| self.is_optional(f));
+
+        // Document parameters whose type or meaning the signature doesn't show
+        let params: Vec<String> = fields
+            .iter()
+            .filter(|(f, _)| f.doc.is_some() || self.var_doc(f).is_some())
+            .map(|(f, name)| {
+                let var = self
+                    .var_doc(f)
+                    .unwrap_or_else(|| self.field_type_to_doc(&f.field_type));
+                let doc = f
+                    .doc
+                    .as_ref()
+                    .map(|d| format!(" {}", d))
+                    .unwrap_or_default();
+                format!("{} * @param {} ${}{}\n", indent, var, name, doc)
+            })
+            .collect();
+        if !params.is_empty() {
+            output.push_str(&format!("{}/**\n", indent));
+            for param in params {
+                output.push_str(&param);
+            }
+            output.push_str(&format!("{} */\n", indent));
+        }
+
+        if fields.is_empty() {
+            output.push_str(&format!(
+                "{indent}public function __construct()\n{indent}{{\n{indent}}}\n"
+            ));
+            return output;
+        }
+
+        output.push_str(&format!("{}public function __construct(\n", indent));
+        for (field, name) in fields {
+            let default = if self.is_optional(field) {
+                " = null"
+            } else {
+                ""
+            };
+            output.push_str(&format!(
+                "{}{} ${}{},\n",
+                indent.repeat(2),
+                self.declaration(field),
+                name,
+                default
+            ));
+        }
+        output.push_str(&format!("{indent}) {{\n{indent}}}\n"));
+        output
+    }
+
+    /// Visibility, modifiers and type of a property, e.g. `public ?int`
+    fn declaration(&self, field: &Field) -> String {
+        let readonly = if self.shared.readonly {
+            "readonly "
+        } else {
+            ""
+        };
+        let mut php_type = self.field_type_to_php(&field.field_type);
+        if self.is_optional(field) && !admits_null(&php_type) {
+            php_type = nullable(&php_type);
+        }
+        format!("public {}{}", readonly, php_type)
+    }
+
+    /// Whether a field may be missing from the data
+    fn is_optional(&self, field: &Field) -> bool {
+        field.optional || self.shared.optional_fields
+    }
+
+    /// The `@var` docblock type for fields whose PHP type is `array`
+    fn var_doc(&self, field: &Field) -> Option<String> {
+        let inner = match &field.field_type {
+            FieldType::Optional(inner) => inner.as_ref(),
+            other => other,
+        };
+        if !matches!(
+            inner,
+            FieldType::Array(_) | FieldType::Set(_) | FieldType::Map(_, _)
+        ) {
+            return None;
+        }
+        let doc = self.field_type_to_doc(&field.field_type);
+        if self.is_optional(field) && !doc.ends_with("|null") {
+            Some(format!("{}|null", doc))
+        } else {
+            Some(doc)
+        }
+    }
+
+    /// Convert AST FieldType to a PHP type declaration
+    fn field_type_to_php(&self, field_type: &FieldType) -> String {
+        match field_type {
+            FieldType::String | FieldType::Enum(_) | FieldType::Uuid => "string".to_string(),
+            FieldType::DateTime(format) if !format.is_epoch() => "string".to_string(),
+            FieldType::Integer | FieldType::DateTime(_) => "int".to_string(),
+            FieldType::Float | FieldType::Decimal => "float".to_string(),
+            FieldType::Boolean => "bool".to_string(),
+            FieldType::Array(_) | FieldType::Set(_) | FieldType::Map(_, _) => "array".to_string(),
+            FieldType::Optional(inner) => {
+                let inner = self.field_type_to_php(inner);
+                if admits_null(&inner) {
+                    inner
+                } else {
+                    nullable(&inner)
+                }
+            }
+            FieldType::Reference(name) => class_name(name),
+            FieldType::Union(types) => {
+                let (types, mut has_null) = split_nullable_union(types);
+                let mut members: Vec<String> = Vec::new();
+                for member in types {
+                    // Flatten nullable members, as `?X` cannot appear in a
+                    // union, and drop PHP types two members share, e.g. two
+                    // arrays
+                    let rendered = self.field_type_to_php(member);
+                    for part in rendered.split('|') {
+                        let part = match part.strip_prefix('?') {
+                            Some(part) => {
+                                has_null = true;
+                                part
+                            }
+                            None => part,
+                        };
+                        if part == "null" {
+                            has_null = true;
+                        } else if !members.iter().any(|m| m == part) {
+                            members.push(part.to_string());
+                        }
+                    }
+                }
+                match members.as_slice() {
+                    [] => "mixed".to_string(),
+                    [single] if has_null => nullable(single),
+                    _ if members.iter().any(|m| m == "mixed") => "mixed".to_string(),
+                    _ if has_null => format!("{}|null", members.join("|")),
+                    _ => members.join("|"),
+                }
+            }
+            FieldType::Null | FieldType::Any => "mixed".to_string(),
+        }
+    }
+
+    /// Convert AST FieldType to a docblock type, which can describe elements
+    fn field_type_to_doc(&self, field_type: &FieldType) -> String {
+        match field_type {
+            FieldType::Array(inner) | FieldType::Set(inner) => {
+                let element = self.field_type_to_doc(inner);
+                if element.contains('|') {
+                    format!("({})[]", element)
+                } else {
+                    format!("{}[]", element)
+                }
+            }
+            FieldType::Map(key, value) => format!(
+                "array<{}, {}>",
+                self.field_type_to_doc(key),
+                self.field_type_to_doc(value)
+            ),
+            FieldType::Optional(inner) => format!("{}|null", self.field_type_to_doc(inner)),
+            FieldType::Union(types) => {
+                let mut members: Vec<String> = Vec::new();
+                for member in types {
+                    let rendered = self.field_type_to_doc(member);
+                    if !members.contains(&rendered) {
+                        members.push(rendered);
+                    }
+                }
+                members.join("|")
+            }
+            FieldType::Null => "null".to_string(),
+            FieldType::Reference(name) => class_name(name),
+            other => self.field_type_to_php(other),
+        }
+    }
+}
+
+/// Whether a PHP type declaration already accepts `null`
+fn admits_null(php_type: &str) -> bool {
+    php_type == "mixed" || php_type.starts_with('?') || php_type.ends_with("|null")
+}
+
+/// Make a PHP type declaration nullable
+fn nullable(php_type: &str) -> String {
+    if php_type.contains('|') {
+        format!("{}|null", php_type)
+    } else {
+        format!("?{}", php_type)
+    }
+}
+
+/// Get the property name for a field, replacing characters PHP does not
+/// allow in identifiers
+fn property_name(field: &Field) -> String {
+    let mut name: String = field
+        .name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        name.insert(0, '_');
+    }
+    name
+}
+
+/// Get the class name of a type, with a trailing underscore when PHP
+/// reserves it, e.g. `List_`
+fn class_name(name: &str) -> String {
+    if PHP_RESERVED.contains(&name.to_ascii_lowercase().as_str()) {
+        format!("{}_", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Suffix a name with a number until no earlier property uses it
+fn unique_name(name: String, used: &mut HashSet<String>) -> String {
+    let mut candidate = name.clone();
+    let mut counter = 1;
+    while !used.insert(candidate.clone()) {
+        counter += 1;
+        candidate = format!("{}{}", name, counter);
+    }
+    candidate
+}
+
+impl CodeGenerator for PhpGenerator {
+    fn generate(&self, schema: &Schema) -> Result<String> {
+        if schema.types.is_empty() {
+            return Err(AlchemistError::GenerationError(
+                "Schema has no types to generate".to_string(),
+            ));
+        }
+
+        let mut output = String::new();
+
+        output.push_str("<?php\n\n");
+        output.push_str("// Generated by Alchemist\n");
+        output.push_str("// Do not edit manually\n\n");
+        output.push_str("declare(strict_types=1);\n\n");
+
+        // Generate all type definitions (in reverse order so nested types come first)
+        let classes: Vec<String> = schema
+            .types
+            .iter()
+            .rev()
+            .map(|type_def| self.generate_class(type_def))
+            .collect();
+        output.push_str(&classes.join("\n"));
+
+        Ok(output)
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "php"
+    }
+
+    fn name(&self) -> &'static str {
+        "PHP"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user_schema() -> Schema {
+        let mut schema = Schema::new("User");
+        let mut user = TypeDef::new("User");
+        user.add_field(Field::new("id", FieldType::Integer));
+        user.add_field(Field::new("nickname", FieldType::String).optional());
+        user.add_field(Field::new(
+            "tags",
+            FieldType::Array(Box::new(FieldType::String)),
+        ));
+        user.add_field(Field::new(
+            "scores",
+            FieldType::Map(Box::new(FieldType::String), Box::new(FieldType::Float)),
+        ));
+        user.add_field(Field::new(
+            "key",
+            FieldType::Union(vec![FieldType::Integer, FieldType::String]),
+        ));
+        schema.add_type(user);
+        schema
+    }
+
+    #[test]
+    fn test_generate_typed_properties() {
+        let generator = PhpGenerator::new(PhpOptions::default(), SharedOptions::default());
+        let output = generator.generate(&user_schema()).unwrap();
+
+        assert!(output.starts_with("<?php\n"));
+        assert!(output.contains("final class User\n{\n"));
+        assert!(output.contains("    public int $id;\n"));
+        assert!(output.contains("    public ?string $nickname = null;\n"));
+        assert!(output.contains("    /** @var string[] */\n    public array $tags;\n"));
+        assert!(
+            output.contains("    /** @var array<string, float> */\n    public array $scores;\n")
+        );
+        assert!(output.contains("    public int|string $key;\n"));
+    }
+
+    #[test]
+    fn test_generate_promoted_constructor() {
+        let generator = PhpGenerator::new(PhpOptions { promoted: true }, SharedOptions::default());
+        let output = generator.generate(&user_schema()).unwrap();
+
+        assert!(output.contains("     * @param string[] $tags\n"));
+        assert!(output.contains(
+            "    public function __construct(\n        public int $id,\n        public array $tags,\n"
+        ));
+        // The optional parameter moves last
+        assert!(output.contains("        public ?string $nickname = null,\n    ) {\n    }\n"));
+    }
+
+    #[test]
+    fn test_names_are_unique_and_unreserved() {
+        let mut schema = Schema::new("List");
+        let mut list = TypeDef::new("List");
+        list.add_field(Field::new("first-name", FieldType::String));
+        list.add_field(Field::new("first_name", FieldType::String));
+        list.add_field(Field::new(
+            "parent",
+            FieldType::Optional(Box::new(FieldType::Reference("Class".to_string()))),
+        ));
+        schema.add_type(list);
+        schema.add_type(TypeDef::new("Class"));
+
+        let output = PhpGenerator::new(PhpOptions::default(), SharedOptions::default())
+            .generate(&schema)
+            .unwrap();
+
+        assert!(output.contains("final class List_\n{\n"));
+        assert!(output.contains("final class Class_\n{\n"));
+        assert!(
+            output.contains("    public string $first_name;\n    public string $first_name2;\n")
+        );
+        assert!(output.contains("    public ?Class_ $parent;\n"));
+    }
+
+    #[test]
+    fn test_nullable_union_members_flatten() {
+        let mut schema = Schema::new("Root");
+        let mut root = TypeDef::new("Root");
+        root.add_field(Field::new(
+            "value",
+            FieldType::Union(vec![
+                FieldType::Optional(Box::new(FieldType::Integer)),
+                FieldType::String,
+            ]),
+        ));
+        root.add_field(
+            Field::new(
+                "key",
+                FieldType::Union(vec![FieldType::Integer, FieldType::String]),
+            )
+            .optional(),
+        );
+        schema.add_type(root);
+
+        let output = PhpGenerator::new(PhpOptions::default(), SharedOptions::default())
+            .generate(&schema)
+            .unwrap();
+
+        assert!(output.contains("    public int|string|null $value;\n"));
+        assert!(output.contains("    public int|string|null $key = null;\n"));
+    }
+}
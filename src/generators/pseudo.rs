@@ -0,0 +1,168 @@
+//! Language-neutral pseudocode generator
+
+use crate::ast::{FieldType, Schema, TypeDef};
+use crate::error::{AlchemistError, Result};
+use crate::generators::{split_nullable_union, CodeGenerator, Indent, SharedOptions};
+
+/// Pseudocode generator
+///
+/// Renders the schema as plain `Type Name { field: Label }` blocks for docs
+/// and reviews, root type first, with no language syntax to get in the way.
+pub struct PseudoGenerator {
+    shared: SharedOptions,
+    /// Whitespace of one indentation level
+    indent: String,
+}
+
+impl PseudoGenerator {
+    /// Create a new pseudocode generator with the shared options
+    pub fn new(shared: SharedOptions) -> Self {
+        let indent = shared.indent_unit(Indent::Spaces(2));
+        Self { shared, indent }
+    }
+
+    /// Describe a type definition as a `Type Name { ... }` block
+    fn generate_type(&self, type_def: &TypeDef) -> String {
+        let mut output = String::new();
+
+        if let Some(doc) = &type_def.doc {
+            output.push_str(&format!("// {}\n", doc));
+        }
+        output.push_str(&format!("Type {} {{\n", type_def.name));
+
+        for field in &type_def.fields {
+            let mut line = format!(
+                "{}{}: {}",
+                self.indent,
+                field.name,
+                label(&field.field_type)
+            );
+            if field.optional || self.shared.optional_fields {
+                line.push_str(" (optional)");
+            }
+            if let Some(doc) = &field.doc {
+                line.push_str(&format!("  // {}", doc));
+            }
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        output.push_str("}\n");
+        output
+    }
+}
+
+/// Human-readable label of a field type, e.g. `List of Nullable Integer`
+fn label(field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::String => "String".to_string(),
+        FieldType::Enum(values) => values
+            .iter()
+            .map(|value| format!("\"{}\"", value))
+            .collect::<Vec<_>>()
+            .join(" or "),
+        FieldType::Integer => "Integer".to_string(),
+        FieldType::Float => "Float".to_string(),
+        FieldType::Decimal => "Decimal".to_string(),
+        FieldType::Uuid => "UUID".to_string(),
+        FieldType::Boolean => "Boolean".to_string(),
+        FieldType::Null => "Null".to_string(),
+        FieldType::Any => "Any".to_string(),
+        FieldType::DateTime(format) => format!("DateTime ({})", format.description()),
+        FieldType::Array(inner) => format!("List of {}", element_label(inner)),
+        FieldType::Set(inner) => format!("Set of unique {}", element_label(inner)),
+        FieldType::Optional(inner) => format!("Nullable {}", element_label(inner)),
+        FieldType::Reference(name) => name.clone(),
+        FieldType::Union(types) => {
+            let (members, nullable) = split_nullable_union(types);
+            let union = members
+                .into_iter()
+                .map(label)
+                .collect::<Vec<_>>()
+                .join(" or ");
+            if nullable {
+                format!("Nullable {}", parenthesize(union))
+            } else {
+                union
+            }
+        }
+        FieldType::Map(key, value) => {
+            format!("Map of {} to {}", element_label(key), element_label(value))
+        }
+    }
+}
+
+/// Label of a type nested in another, parenthesized if it is a union
+fn element_label(field_type: &FieldType) -> String {
+    parenthesize(label(field_type))
+}
+
+/// Wrap a label in parentheses if it lists alternatives
+fn parenthesize(label: String) -> String {
+    if label.contains(" or ") {
+        format!("({})", label)
+    } else {
+        label
+    }
+}
+
+impl CodeGenerator for PseudoGenerator {
+    fn generate(&self, schema: &Schema) -> Result<String> {
+        if schema.types.is_empty() {
+            return Err(AlchemistError::GenerationError(
+                "Schema has no types to generate".to_string(),
+            ));
+        }
+
+        let mut output = String::new();
+
+        output.push_str("// Generated by Alchemist\n");
+        output.push_str("// Do not edit manually\n\n");
+
+        let types: Vec<String> = schema
+            .types
+            .iter()
+            .map(|type_def| self.generate_type(type_def))
+            .collect();
+        output.push_str(&types.join("\n"));
+
+        Ok(output)
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "txt"
+    }
+
+    fn name(&self) -> &'static str {
+        "Pseudocode"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::GeneratorOptions;
+    use crate::parser::parse_json;
+
+    #[test]
+    fn test_generate_pseudocode() {
+        let options = GeneratorOptions::default();
+        let schema = parse_json(
+            r#"{"id": 1, "tags": ["a"], "scores": [1, null], "mixed": [1, "a"], "owner": {"name": "x"}, "users": [{"a": 1}, {"b": true}]}"#,
+            &options,
+        )
+        .unwrap();
+        let output = PseudoGenerator::new(options.shared)
+            .generate(&schema)
+            .unwrap();
+
+        assert!(output.contains(
+            "Type Root {\n  id: Integer\n  tags: List of String\n  scores: List of Nullable Integer\n  mixed: List of (Integer or String)\n  owner: Owner\n  users: List of Users\n}\n"
+        ));
+        assert!(
+            output.contains("Type Users {\n  a: Integer (optional)\n  b: Boolean (optional)\n}\n")
+        );
+        // The root type comes first
+        assert!(output.find("Type Root").unwrap() < output.find("Type Owner").unwrap());
+    }
+}
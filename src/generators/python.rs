@@ -1,33 +1,138 @@
-//! Python Pydantic model generator
+//! Python model generator: Pydantic models, dataclasses or TypedDicts
 
-use crate::ast::{FieldType, Schema, TypeDef};
+use crate::ast::{Constraints, DateTimeFormat, Field, FieldType, Schema, TypeDef};
 use crate::error::{AlchemistError, Result};
-use crate::generators::{CodeGenerator, GeneratorOptions};
+use crate::generators::{root_alias, split_nullable_union, CodeGenerator, Indent, SharedOptions};
 use crate::utils::to_snake_case;
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::collections::HashSet;
 
-/// Python Pydantic generator
+/// Python version whose typing syntax the generated models use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, ValueEnum, Deserialize)]
+pub enum PythonTarget {
+    /// `typing.List`, `Optional[X]` and quoted forward references
+    #[default]
+    #[value(name = "3.8")]
+    #[serde(rename = "3.8")]
+    Py38,
+    /// Built-in generics such as `list[int]`
+    #[value(name = "3.9")]
+    #[serde(rename = "3.9")]
+    Py39,
+    /// `X | None` unions and postponed annotations
+    #[value(name = "3.10")]
+    #[serde(rename = "3.10")]
+    Py310,
+    /// Same syntax as 3.10
+    #[value(name = "3.12")]
+    #[serde(rename = "3.12")]
+    Py312,
+}
+
+impl PythonTarget {
+    /// Whether `list[...]`/`dict[...]` can replace `typing.List`/`typing.Dict`
+    fn builtin_generics(self) -> bool {
+        self >= PythonTarget::Py39
+    }
+
+    /// Whether unions can be written with `|`
+    fn pipe_unions(self) -> bool {
+        self >= PythonTarget::Py310
+    }
+}
+
+/// Pydantic major version the models are written for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+pub enum PydanticVersion {
+    /// Constraints as constrained types, e.g. `conint(ge=0)`
+    #[value(name = "1")]
+    #[serde(rename = "1")]
+    V1,
+    /// Constraints as `Annotated` metadata, e.g. `Annotated[int, Field(ge=0)]`
+    #[default]
+    #[value(name = "2")]
+    #[serde(rename = "2")]
+    V2,
+}
+
+/// Kind of class the Python generator emits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PythonStyle {
+    /// Pydantic `BaseModel` subclasses that validate at runtime
+    #[default]
+    Pydantic,
+    /// Standard library `@dataclass` classes
+    Dataclass,
+    /// `TypedDict` subclasses describing the parsed JSON as-is
+    Typeddict,
+}
+
+/// Options of the Python generator
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PythonOptions {
+    /// Python version to target
+    #[serde(rename = "python_target")]
+    pub target: PythonTarget,
+    /// Pydantic version to target
+    #[serde(rename = "pydantic_version")]
+    pub pydantic: PydanticVersion,
+    /// Kind of class to emit
+    #[serde(rename = "python_style")]
+    pub style: PythonStyle,
+}
+
+/// Python generator
+///
+/// Emits Pydantic models by default, or dataclasses or TypedDicts when
+/// `--python-style` asks for them.
 pub struct PythonGenerator {
-    options: GeneratorOptions,
+    options: PythonOptions,
+    shared: SharedOptions,
+    /// Whitespace of one indentation level
+    indent: String,
 }
 
 impl PythonGenerator {
-    /// Create a new Python generator with the given options
-    pub fn new(options: GeneratorOptions) -> Self {
-        Self { options }
+    /// Create a new Python generator with its own and the shared options
+    pub fn new(options: PythonOptions, shared: SharedOptions) -> Self {
+        let indent = shared.indent_unit(Indent::Spaces(4));
+        Self {
+            options,
+            shared,
+            indent,
+        }
     }
 
-    /// Generate a Python class from a type definition
+    /// Generate a Python class from a type definition in the configured style
     fn generate_class(&self, type_def: &TypeDef) -> String {
+        match self.options.style {
+            PythonStyle::Pydantic => self.generate_model(type_def),
+            PythonStyle::Dataclass => self.generate_dataclass(type_def),
+            PythonStyle::Typeddict => self.generate_typeddict(type_def),
+        }
+    }
+
+    /// Class docstring, indented into the class body
+    fn docstring(&self, doc: &str) -> String {
+        format!("{0}\"\"\"\n{0}{1}\n{0}\"\"\"\n", self.indent, doc)
+    }
+
+    /// Generate a Pydantic model
+    fn generate_model(&self, type_def: &TypeDef) -> String {
+        let indent = &self.indent;
         let mut output = String::new();
 
         output.push_str(&format!("class {}(BaseModel):\n", type_def.name));
 
         if let Some(doc) = &type_def.doc {
-            output.push_str(&format!("    \"\"\"\n    {}\n    \"\"\"\n", doc));
+            output.push_str(&self.docstring(doc));
         }
 
         if type_def.fields.is_empty() {
-            output.push_str("    pass\n");
+            output.push_str(&format!("{}pass\n", indent));
             return output;
         }
 
@@ -39,11 +144,11 @@ impl PythonGenerator {
             let snake_name = to_snake_case(raw_name);
             let safe_name = self.to_safe_identifier(&snake_name);
 
-            let python_type_str = self.field_type_to_python(&field.field_type);
-            let is_optional = field.optional || self.options.optional_fields;
+            let python_type_str = self.constrained_type(&field.field_type, &field.constraints);
+            let is_optional = field.optional || self.shared.optional_fields;
 
-            let type_annotation = if is_optional {
-                format!("Optional[{}]", python_type_str)
+            let type_annotation = if is_optional && !is_nullable(&field.field_type) {
+                self.optional(&python_type_str)
             } else {
                 python_type_str
             };
@@ -51,21 +156,26 @@ impl PythonGenerator {
             // Determine if we need an alias (if renamed or contains invalid chars)
             let needs_alias = safe_name != *raw_name;
 
-            if needs_alias {
-                let default_val = if is_optional {
-                    format!("Field(None, alias=\"{}\")", raw_name)
-                } else {
-                    format!("Field(..., alias=\"{}\")", raw_name)
-                };
+            if needs_alias || field.doc.is_some() {
+                let mut args = vec![if is_optional { "None" } else { "..." }.to_string()];
+                if needs_alias {
+                    args.push(format!("alias={}", python_string(raw_name)));
+                }
+                if let Some(doc) = &field.doc {
+                    args.push(format!("description={}", python_string(doc)));
+                }
                 output.push_str(&format!(
-                    "    {}: {} = {}\n",
-                    safe_name, type_annotation, default_val
+                    "{}{}: {} = Field({})\n",
+                    indent,
+                    safe_name,
+                    type_annotation,
+                    args.join(", ")
                 ));
             } else {
                 let default_val = if is_optional { " = None" } else { "" };
                 output.push_str(&format!(
-                    "    {}: {}{}\n",
-                    safe_name, type_annotation, default_val
+                    "{}{}: {}{}\n",
+                    indent, safe_name, type_annotation, default_val
                 ));
             }
         }
@@ -74,6 +184,136 @@ impl PythonGenerator {
         output
     }
 
+    /// Generate a `@dataclass`
+    ///
+    /// Dataclasses cannot alias fields, so renamed keys are noted in a comment.
+    fn generate_dataclass(&self, type_def: &TypeDef) -> String {
+        let indent = &self.indent;
+        let mut output = String::new();
+
+        output.push_str(&format!("@dataclass\nclass {}:\n", type_def.name));
+        if let Some(doc) = &type_def.doc {
+            output.push_str(&self.docstring(doc));
+        }
+        if type_def.fields.is_empty() {
+            output.push_str(&format!("{}pass\n\n", indent));
+            return output;
+        }
+
+        // Keys like `userId` and `user_id` have the same snake_case name
+        let mut used = HashSet::new();
+        let mut fields: Vec<(&Field, String)> = type_def
+            .fields
+            .iter()
+            .map(|field| {
+                let name = self.to_safe_identifier(&to_snake_case(&field.name));
+                (field, unique_name(name, &mut used))
+            })
+            .collect();
+        // Fields with defaults must follow the ones without
+        fields.sort_by_key(|(field, _)| self.is_optional(field));
+
+        for (field, name) in fields {
+            if let Some(comment) = field_comment(field, &name) {
+                output.push_str(&format!("{}# {}\n", indent, comment));
+            }
+            let python_type = self.field_type_to_python(&field.field_type);
+            if self.is_optional(field) {
+                let python_type = if is_nullable(&field.field_type) {
+                    python_type
+                } else {
+                    self.optional(&python_type)
+                };
+                output.push_str(&format!("{}{}: {} = None\n", indent, name, python_type));
+            } else {
+                output.push_str(&format!("{}{}: {}\n", indent, name, python_type));
+            }
+        }
+        output.push('\n');
+
+        output
+    }
+
+    /// Generate a `TypedDict`
+    ///
+    /// Keys are kept as they appear in the JSON. When one of them is not a
+    /// valid identifier the functional `TypedDict("Name", {...})` syntax is
+    /// used instead of a class.
+    fn generate_typeddict(&self, type_def: &TypeDef) -> String {
+        let indent = &self.indent;
+        let mut output = String::new();
+
+        let entries: Vec<(&Field, String)> = type_def
+            .fields
+            .iter()
+            .map(|field| {
+                let python_type = self.field_type_to_python(&field.field_type);
+                if self.is_optional(field) {
+                    (field, format!("NotRequired[{}]", python_type))
+                } else {
+                    (field, python_type)
+                }
+            })
+            .collect();
+
+        if type_def
+            .fields
+            .iter()
+            .all(|field| self.is_identifier(&field.name))
+        {
+            output.push_str(&format!("class {}(TypedDict):\n", type_def.name));
+            if let Some(doc) = &type_def.doc {
+                output.push_str(&self.docstring(doc));
+            }
+            if entries.is_empty() {
+                output.push_str(&format!("{}pass\n", indent));
+            }
+            for (field, python_type) in entries {
+                if let Some(doc) = &field.doc {
+                    output.push_str(&format!("{}# {}\n", indent, doc));
+                }
+                output.push_str(&format!("{}{}: {}\n", indent, field.name, python_type));
+            }
+        } else {
+            if let Some(doc) = &type_def.doc {
+                output.push_str(&format!("# {}\n", doc));
+            }
+            output.push_str(&format!(
+                "{} = TypedDict({}, {{\n",
+                type_def.name,
+                python_string(&type_def.name)
+            ));
+            for (field, python_type) in entries {
+                if let Some(doc) = &field.doc {
+                    output.push_str(&format!("{}# {}\n", indent, doc));
+                }
+                output.push_str(&format!(
+                    "{}{}: {},\n",
+                    indent,
+                    python_string(&field.name),
+                    python_type
+                ));
+            }
+            output.push_str("})\n");
+        }
+        output.push('\n');
+
+        output
+    }
+
+    /// Whether a field may be missing from the data
+    fn is_optional(&self, field: &Field) -> bool {
+        field.optional || self.shared.optional_fields
+    }
+
+    /// Whether a key can be written as an attribute name as it is
+    fn is_identifier(&self, key: &str) -> bool {
+        !key.is_empty()
+            && !key.starts_with(|c: char| c.is_ascii_digit())
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && self.to_safe_identifier(key) == key
+    }
+
     fn to_safe_identifier(&self, name: &str) -> String {
         match name {
             "class" | "def" | "return" | "pass" | "from" | "import" | "type" | "None" | "True"
@@ -85,37 +325,251 @@ impl PythonGenerator {
         }
     }
 
+    /// Python type of a field, carrying its constraints if it has any
+    fn constrained_type(&self, field_type: &FieldType, constraints: &Constraints) -> String {
+        match field_type {
+            FieldType::Optional(inner) => self.optional(&self.constrained_type(inner, constraints)),
+            FieldType::Integer | FieldType::Float | FieldType::String
+                if !constraints.is_empty() =>
+            {
+                let args = constraint_args(constraints).join(", ");
+                match self.options.pydantic {
+                    PydanticVersion::V1 => {
+                        let constrained = match field_type {
+                            FieldType::Integer => "conint",
+                            FieldType::Float => "confloat",
+                            _ => "constr",
+                        };
+                        format!("{}({})", constrained, args)
+                    }
+                    PydanticVersion::V2 => format!(
+                        "Annotated[{}, Field({})]",
+                        self.field_type_to_python(field_type),
+                        args
+                    ),
+                }
+            }
+            _ => self.field_type_to_python(field_type),
+        }
+    }
+
     /// Convert AST FieldType to Python type string
     fn field_type_to_python(&self, field_type: &FieldType) -> String {
+        let target = self.options.target;
+        // A TypedDict describes the decoded JSON, which holds no richer types
+        let raw = self.options.style == PythonStyle::Typeddict;
+
         match field_type {
             FieldType::String => "str".to_string(),
+            FieldType::DateTime(format) if raw && format.is_epoch() => "int".to_string(),
+            FieldType::DateTime(_) | FieldType::Uuid if raw => "str".to_string(),
+            FieldType::Decimal if raw => "float".to_string(),
+            FieldType::Enum(values) => {
+                let values: Vec<String> = values.iter().map(|v| format!("{:?}", v)).collect();
+                let literal = format!("Literal[{}]", values.join(", "));
+                if !self.shared.enum_catchall {
+                    literal
+                } else if target.pipe_unions() {
+                    // Tried first, so only unseen values fall back to `str`
+                    format!("{} | str", literal)
+                } else {
+                    format!("Union[{}, str]", literal)
+                }
+            }
             FieldType::Integer => "int".to_string(),
             FieldType::Float => "float".to_string(),
             FieldType::Boolean => "bool".to_string(),
-            FieldType::Null => "Optional[Any]".to_string(),
+            // Pydantic parses epoch seconds and milliseconds into datetimes
+            FieldType::DateTime(DateTimeFormat::Date) => "date".to_string(),
+            FieldType::DateTime(_) => "datetime".to_string(),
+            FieldType::Decimal => "Decimal".to_string(),
+            FieldType::Uuid => "UUID".to_string(),
+            FieldType::Null => self.optional("Any"),
             FieldType::Array(inner) => {
-                format!("List[{}]", self.field_type_to_python(inner))
+                let list = if target.builtin_generics() {
+                    "list"
+                } else {
+                    "List"
+                };
+                format!("{}[{}]", list, self.field_type_to_python(inner))
+            }
+            FieldType::Set(inner) => {
+                let set = if target.builtin_generics() {
+                    "set"
+                } else {
+                    "Set"
+                };
+                format!("{}[{}]", set, self.field_type_to_python(inner))
+            }
+            FieldType::Optional(inner) => self.optional(&self.field_type_to_python(inner)),
+            FieldType::Reference(name) => {
+                if target.pipe_unions() {
+                    // Annotations are postponed, so forward references need no quotes
+                    name.clone()
+                } else {
+                    format!("'{}'", name) // String forward reference
+                }
             }
-            FieldType::Optional(inner) => self.field_type_to_python(inner),
-            FieldType::Reference(name) => format!("'{}'", name), // String forward reference
             FieldType::Union(types) => {
-                let types_str = types
-                    .iter()
+                let (members, nullable) = split_nullable_union(types);
+                let mut members: Vec<String> = members
+                    .into_iter()
                     .map(|t| self.field_type_to_python(t))
-                    .collect::<Vec<_>>()
-                    .join(", ");
-                format!("Union[{}]", types_str)
+                    .collect();
+                if self.shared.enum_catchall && members.len() > 1 {
+                    // Falls back to the raw value for types matching no member
+                    members.push("Any".to_string());
+                }
+                let union = match members.as_slice() {
+                    [single] => single.clone(),
+                    _ if target.pipe_unions() => members.join(" | "),
+                    _ => format!("Union[{}]", members.join(", ")),
+                };
+                if nullable {
+                    self.optional(&union)
+                } else {
+                    union
+                }
             }
             FieldType::Any => "Any".to_string(),
             FieldType::Map(key, value) => {
+                let dict = if target.builtin_generics() {
+                    "dict"
+                } else {
+                    "Dict"
+                };
                 format!(
-                    "Dict[{}, {}]",
+                    "{}[{}, {}]",
+                    dict,
                     self.field_type_to_python(key),
                     self.field_type_to_python(value)
                 )
             }
         }
     }
+
+    /// Make a rendered type nullable
+    fn optional(&self, inner: &str) -> String {
+        if self.options.target.pipe_unions() {
+            format!("{} | None", inner)
+        } else {
+            format!("Optional[{}]", inner)
+        }
+    }
+
+    /// Import lines for the configured target
+    fn imports(
+        &self,
+        datetimes: &[DateTimeFormat],
+        uses_decimal: bool,
+        uses_uuid: bool,
+        uses_constraints: bool,
+        uses_enums: bool,
+    ) -> String {
+        let mut imports = String::new();
+        let typing = match self.options.target {
+            PythonTarget::Py38 => "from typing import List, Optional, Any, Dict, Set, Union\n",
+            PythonTarget::Py39 => "from typing import Optional, Any, Union\n",
+            PythonTarget::Py310 | PythonTarget::Py312 => {
+                // Must be the first statement in the module
+                imports.push_str("from __future__ import annotations\n\n");
+                "from typing import Any\n"
+            }
+        };
+        let names: Vec<&str> = [
+            ("date", datetimes.contains(&DateTimeFormat::Date)),
+            (
+                "datetime",
+                datetimes.iter().any(|f| *f != DateTimeFormat::Date),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(name, used)| used.then_some(name))
+        .collect();
+        if !names.is_empty() {
+            imports.push_str(&format!("from datetime import {}\n", names.join(", ")));
+        }
+        if uses_decimal {
+            imports.push_str("from decimal import Decimal\n");
+        }
+        if uses_uuid {
+            imports.push_str("from uuid import UUID\n");
+        }
+        imports.push_str(typing);
+        if uses_enums {
+            imports.push_str("from typing import Literal\n");
+        }
+        if uses_constraints && self.options.pydantic == PydanticVersion::V2 {
+            imports.push_str(match self.options.target {
+                PythonTarget::Py38 => "from typing_extensions import Annotated\n",
+                _ => "from typing import Annotated\n",
+            });
+        }
+        imports
+    }
+}
+
+/// Keyword arguments of a constraint, e.g. `["ge=0", "le=100"]`
+fn constraint_args(constraints: &Constraints) -> Vec<String> {
+    let bounds = [
+        ("ge", constraints.min.map(|n| n.to_string())),
+        ("le", constraints.max.map(|n| n.to_string())),
+        ("min_length", constraints.min_length.map(|n| n.to_string())),
+        ("max_length", constraints.max_length.map(|n| n.to_string())),
+    ];
+    bounds
+        .into_iter()
+        .filter_map(|(name, bound)| bound.map(|bound| format!("{}={}", name, bound)))
+        .collect()
+}
+
+/// Comment above a dataclass field: its doc and, when renamed, its JSON key
+fn field_comment(field: &Field, name: &str) -> Option<String> {
+    match (&field.doc, name != field.name) {
+        (Some(doc), true) => Some(format!("{} (JSON key {})", doc, python_string(&field.name))),
+        (Some(doc), false) => Some(doc.clone()),
+        (None, true) => Some(format!("JSON key {}", python_string(&field.name))),
+        (None, false) => None,
+    }
+}
+
+/// Suffix a name with a number until no earlier field uses it
+fn unique_name(name: String, used: &mut HashSet<String>) -> String {
+    let mut candidate = name.clone();
+    let mut counter = 1;
+    while !used.insert(candidate.clone()) {
+        counter += 1;
+        candidate = format!("{}{}", name, counter);
+    }
+    candidate
+}
+
+/// Quote text as a Python string, escaping quotes, backslashes and control
+/// characters
+fn python_string(text: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Whether the rendered type already admits `None`
+fn is_nullable(field_type: &FieldType) -> bool {
+    match field_type {
+        FieldType::Null | FieldType::Optional(_) => true,
+        FieldType::Union(types) => types.iter().any(|t| matches!(t, FieldType::Null)),
+        _ => false,
+    }
 }
 
 impl CodeGenerator for PythonGenerator {
@@ -129,14 +583,83 @@ impl CodeGenerator for PythonGenerator {
         let mut output = String::new();
 
         // Add imports
-        output.push_str("from typing import List, Optional, Any, Dict, Union\n");
-        output.push_str("from pydantic import BaseModel, Field\n\n");
+        let field_types = || {
+            schema
+                .types
+                .iter()
+                .flat_map(|t| &t.fields)
+                .map(|f| &f.field_type)
+        };
+        let style = self.options.style;
+        // TypedDicts write dates, decimals and UUIDs as their JSON types
+        let rich_types = style != PythonStyle::Typeddict;
+        let datetimes: Vec<DateTimeFormat> = field_types()
+            .filter(|_| rich_types)
+            .flat_map(FieldType::datetime_formats)
+            .collect();
+        let uses_decimal = rich_types && field_types().any(FieldType::contains_decimal);
+        let uses_uuid = rich_types && field_types().any(FieldType::contains_uuid);
+        let uses_constraints = style == PythonStyle::Pydantic
+            && schema
+                .types
+                .iter()
+                .flat_map(|t| &t.fields)
+                .any(|f| !f.constraints.is_empty());
+        let uses_enums = field_types().any(FieldType::contains_enum);
+        output.push_str(&self.imports(
+            &datetimes,
+            uses_decimal,
+            uses_uuid,
+            uses_constraints,
+            uses_enums,
+        ));
+        match style {
+            PythonStyle::Pydantic
+                if uses_constraints && self.options.pydantic == PydanticVersion::V1 =>
+            {
+                output.push_str(
+                    "from pydantic import BaseModel, Field, confloat, conint, constr\n\n",
+                );
+            }
+            PythonStyle::Pydantic => output.push_str("from pydantic import BaseModel, Field\n\n"),
+            PythonStyle::Dataclass => output.push_str("from dataclasses import dataclass\n\n"),
+            PythonStyle::Typeddict => {
+                let uses_not_required = self.shared.optional_fields
+                    || schema
+                        .types
+                        .iter()
+                        .flat_map(|t| &t.fields)
+                        .any(|f| f.optional);
+                // `typing.TypedDict` only understands `NotRequired` from 3.11
+                output.push_str(match self.options.target {
+                    PythonTarget::Py312 if uses_not_required => {
+                        "from typing import NotRequired, TypedDict\n\n"
+                    }
+                    _ if uses_not_required => {
+                        "from typing_extensions import NotRequired, TypedDict\n\n"
+                    }
+                    _ => "from typing import TypedDict\n\n",
+                });
+            }
+        }
 
         output.push_str("# Generated by Alchemist\n");
         output.push_str("# Do not edit manually\n\n");
 
         // Generate types
+        let alias = root_alias(schema, &self.shared);
         for type_def in schema.types.iter().rev() {
+            if let Some(alias) = alias.as_ref().filter(|a| a.name == type_def.name) {
+                if let Some(doc) = alias.doc {
+                    output.push_str(&format!("# {}\n", doc));
+                }
+                output.push_str(&format!(
+                    "{} = {}\n",
+                    alias.name,
+                    self.field_type_to_python(alias.field_type)
+                ));
+                continue;
+            }
             output.push_str(&self.generate_class(type_def));
         }
 
@@ -148,6 +671,242 @@ impl CodeGenerator for PythonGenerator {
     }
 
     fn name(&self) -> &'static str {
-        "Python (Pydantic)"
+        match self.options.style {
+            PythonStyle::Pydantic => "Python (Pydantic)",
+            PythonStyle::Dataclass => "Python (dataclasses)",
+            PythonStyle::Typeddict => "Python (TypedDict)",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::GeneratorOptions;
+    use crate::parser::parse_json;
+
+    fn generate_from_json(json: &str) -> String {
+        let options = GeneratorOptions::default();
+        let schema = parse_json(json, &options).unwrap();
+        PythonGenerator::new(options.python, options.shared)
+            .generate(&schema)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_mixed_primitive_array_renders_union() {
+        let output = generate_from_json(r#"{"values": [1, "a", 2.5]}"#);
+
+        assert!(output.contains("    values: List[Union[float, int, str]]\n"));
+    }
+
+    #[test]
+    fn test_nullable_elements_keep_optional() {
+        let output = generate_from_json(r#"{"scores": [1, null], "mixed": [1, "a", null]}"#);
+
+        assert!(output.contains("    scores: List[Optional[int]]\n"));
+        assert!(output.contains("    mixed: List[Optional[Union[int, str]]]\n"));
+    }
+
+    #[test]
+    fn test_python_targets_use_version_syntax() {
+        let json = r#"{"tags": ["a"], "scores": [1, null], "mixed": [1, "a"], "owner": {"id": 1}}"#;
+        let render = |python_target| {
+            let options = GeneratorOptions {
+                python: PythonOptions {
+                    target: python_target,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let schema = parse_json(json, &options).unwrap();
+            PythonGenerator::new(options.python, options.shared)
+                .generate(&schema)
+                .unwrap()
+        };
+
+        let py39 = render(PythonTarget::Py39);
+        assert!(py39.starts_with("from typing import Optional, Any, Union\n"));
+        assert!(py39.contains("    tags: list[str]\n"));
+        assert!(py39.contains("    scores: list[Optional[int]]\n"));
+        assert!(py39.contains("    owner: 'Owner'\n"));
+
+        let py310 = render(PythonTarget::Py310);
+        assert!(py310.starts_with("from __future__ import annotations\n"));
+        assert!(py310.contains("    scores: list[int | None]\n"));
+        assert!(py310.contains("    mixed: list[int | str]\n"));
+        assert!(py310.contains("    owner: Owner\n"));
+        assert!(!py310.contains("Optional"));
+
+        assert_eq!(render(PythonTarget::Py312), py310);
+    }
+
+    #[test]
+    fn test_field_docs_become_descriptions() {
+        let options = GeneratorOptions {
+            descriptions: [("userId".to_string(), "Account \u{1}\"number\"".to_string())].into(),
+            ..Default::default()
+        };
+        let schema = parse_json(r#"{"userId": 1}"#, &options).unwrap();
+        let output = PythonGenerator::new(options.python, options.shared)
+            .generate(&schema)
+            .unwrap();
+
+        assert!(output.contains(
+            "    user_id: int = Field(..., alias=\"userId\", description=\"Account \\u0001\\\"number\\\"\")\n"
+        ));
+    }
+
+    #[test]
+    fn test_decimal_fields_import_decimal() {
+        let options = GeneratorOptions {
+            money_fields: vec!["price".to_string()],
+            ..Default::default()
+        };
+        let schema = parse_json(r#"{"price": 9.99}"#, &options).unwrap();
+        let output = PythonGenerator::new(options.python, options.shared)
+            .generate(&schema)
+            .unwrap();
+
+        assert!(output.contains("from decimal import Decimal\n"));
+        assert!(output.contains("    price: Decimal\n"));
+    }
+
+    #[test]
+    fn test_iso_dates_import_datetime() {
+        let output =
+            generate_from_json(r#"{"born": "1990-05-17", "seen": "2024-01-02T15:04:05Z"}"#);
+
+        assert!(output.contains("from datetime import date, datetime\n"));
+        assert!(output.contains("    born: date = Field("));
+        assert!(output.contains("    seen: datetime = Field("));
+    }
+
+    #[test]
+    fn test_uuids_import_uuid() {
+        let output = generate_from_json(r#"{"id": "550e8400-e29b-41d4-a716-446655440000"}"#);
+
+        assert!(output.contains("from uuid import UUID\n"));
+        assert!(output.contains("    id: UUID\n"));
+    }
+
+    #[test]
+    fn test_enum_catchall_falls_back_to_any() {
+        let options = GeneratorOptions {
+            shared: SharedOptions {
+                enum_catchall: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let schema = parse_json(
+            r#"{"mixed": [1, "a", null], "scores": [1, null]}"#,
+            &options,
+        )
+        .unwrap();
+        let output = PythonGenerator::new(options.python, options.shared)
+            .generate(&schema)
+            .unwrap();
+
+        assert!(output.contains("    mixed: List[Optional[Union[int, str, Any]]]\n"));
+        assert!(output.contains("    scores: List[Optional[int]]\n"));
+    }
+
+    #[test]
+    fn test_constraints_follow_pydantic_version() {
+        let mut options = GeneratorOptions {
+            infer_constraints: true,
+            ..Default::default()
+        };
+        let schema = parse_json(
+            r#"[{"age": 3, "name": "ab", "note": null}, {"age": 40, "name": "abcd", "note": "x"}]"#,
+            &options,
+        )
+        .unwrap();
+
+        let output = PythonGenerator::new(options.python.clone(), options.shared.clone())
+            .generate(&schema)
+            .unwrap();
+        assert!(output.contains("from typing_extensions import Annotated\n"));
+        assert!(output.contains("    age: Annotated[int, Field(ge=3, le=40)]\n"));
+        assert!(output.contains("    name: Annotated[str, Field(min_length=2, max_length=4)]\n"));
+        assert!(output
+            .contains("    note: Optional[Annotated[str, Field(min_length=1, max_length=1)]]\n"));
+
+        options.python.pydantic = PydanticVersion::V1;
+        let output = PythonGenerator::new(options.python, options.shared)
+            .generate(&schema)
+            .unwrap();
+        assert!(
+            output.contains("from pydantic import BaseModel, Field, confloat, conint, constr\n")
+        );
+        assert!(output.contains("    age: conint(ge=3, le=40)\n"));
+        assert!(!output.contains("Annotated"));
+    }
+
+    #[test]
+    fn test_string_enums_become_literals() {
+        let output =
+            generate_from_json(r#"[{"status": "open"}, {"status": "done"}, {"status": "open"}]"#);
+
+        assert!(output.contains("from typing import Literal\n"));
+        assert!(output.contains("    status: Literal[\"open\", \"done\"]\n"));
+    }
+
+    #[test]
+    fn test_dataclass_style_defaults_optional_fields() {
+        let options = GeneratorOptions {
+            python: PythonOptions {
+                style: PythonStyle::Dataclass,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let schema = parse_json(
+            r#"[{"nick": "a", "userId": 1, "price": 9.5, "user_id": 3}, {"userId": 2, "price": 1.0, "user_id": 4}]"#,
+            &options,
+        )
+        .unwrap();
+        let output = PythonGenerator::new(options.python, options.shared)
+            .generate(&schema)
+            .unwrap();
+
+        assert!(output.contains("from dataclasses import dataclass\n"));
+        assert!(!output.contains("pydantic"));
+        assert!(output.contains(
+            "@dataclass\nclass RootItem:\n    # JSON key \"userId\"\n    user_id: int\n    price: float\n    # JSON key \"user_id\"\n    user_id2: int\n    nick: Optional[str] = None\n"
+        ));
+    }
+
+    #[test]
+    fn test_typeddict_style_keeps_json_keys() {
+        let render = |json: &str| {
+            let options = GeneratorOptions {
+                python: PythonOptions {
+                    style: PythonStyle::Typeddict,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let schema = parse_json(json, &options).unwrap();
+            PythonGenerator::new(options.python, options.shared)
+                .generate(&schema)
+                .unwrap()
+        };
+
+        let output = render(r#"[{"userId": 1, "seen": "2024-01-02T15:04:05Z"}, {"userId": 2}]"#);
+        assert!(output.contains("from typing_extensions import NotRequired, TypedDict\n"));
+        assert!(!output.contains("datetime"));
+        assert!(output
+            .contains("class RootItem(TypedDict):\n    userId: int\n    # ISO 8601 date and time with offset\n    seen: NotRequired[str]\n"));
+
+        let output = render(r#"{"first-name": "x", "class": 1}"#);
+        assert!(output.contains("from typing import TypedDict\n"));
+        assert!(output.contains(
+            "Root = TypedDict(\"Root\", {\n    \"first-name\": str,\n    \"class\": int,\n})\n"
+        ));
+
+        let output = render(r#"{"k\u0001\"": 1}"#);
+        assert!(output.contains("    \"k\\u0001\\\"\": int,\n"));
     }
 }
@@ -0,0 +1,333 @@
+//! Ruby Struct and dry-struct generator
+
+use crate::ast::{Field, FieldType, Schema, TypeDef};
+use crate::error::{AlchemistError, Result};
+use crate::generators::{split_nullable_union, CodeGenerator, Indent, SharedOptions};
+use clap::ValueEnum;
+use serde::Deserialize;
+
+/// Kind of class emitted by the Ruby generator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RubyStyle {
+    /// Plain `Struct.new` with keyword arguments, types noted in comments
+    #[default]
+    Struct,
+    /// `Dry::Struct` classes with `dry-types` attribute types
+    DryStruct,
+}
+
+/// Options of the Ruby generator
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct RubyOptions {
+    /// Kind of class to emit
+    #[serde(rename = "ruby_style")]
+    pub style: RubyStyle,
+}
+
+/// Ruby generator
+pub struct RubyGenerator {
+    options: RubyOptions,
+    shared: SharedOptions,
+    /// Whitespace of one indentation level
+    indent: String,
+}
+
+impl RubyGenerator {
+    /// Create a new Ruby generator with its own and the shared options
+    pub fn new(options: RubyOptions, shared: SharedOptions) -> Self {
+        let indent = shared.indent_unit(Indent::Spaces(2));
+        Self {
+            options,
+            shared,
+            indent,
+        }
+    }
+
+    /// Generate a `Struct.new` constant from a type definition
+    fn generate_struct(&self, type_def: &TypeDef) -> String {
+        let mut output = String::new();
+
+        if let Some(doc) = &type_def.doc {
+            output.push_str(&format!("# {}\n", doc));
+        }
+        output.push_str(&format!("{} = Struct.new(\n", type_def.name));
+
+        for field in &type_def.fields {
+            let mut comment = self.field_type_to_yard(&field.field_type);
+            if self.is_optional(field) && !comment.ends_with(", nil") {
+                comment.push_str(", nil");
+            }
+            if let Some(doc) = &field.doc {
+                comment = format!("{} - {}", comment, doc);
+            }
+            output.push_str(&format!(
+                "{}:{}, # {}\n",
+                self.indent,
+                member_name(field),
+                comment
+            ));
+        }
+
+        output.push_str(&format!("{}keyword_init: true\n", self.indent));
+        output.push_str(")\n");
+        output
+    }
+
+    /// Generate a `Dry::Struct` class from a type definition
+    fn generate_dry_struct(&self, type_def: &TypeDef) -> String {
+        let mut output = String::new();
+
+        if let Some(doc) = &type_def.doc {
+            output.push_str(&format!("# {}\n", doc));
+        }
+        output.push_str(&format!("class {} < Dry::Struct\n", type_def.name));
+        // `JSON.parse` produces string keys
+        output.push_str(&format!("{}transform_keys(&:to_sym)\n\n", self.indent));
+
+        for field in &type_def.fields {
+            if let Some(doc) = &field.doc {
+                output.push_str(&format!("{}# {}\n", self.indent, doc));
+            }
+
+            let mut dry_type = self.field_type_to_dry(&field.field_type);
+            let optional = self.is_optional(field);
+            if optional && !dry_type.ends_with(".optional") {
+                dry_type = optional_dry(&dry_type);
+            }
+            // `attribute?` allows the key to be missing, `.optional` allows nil
+            let attribute = if optional { "attribute?" } else { "attribute" };
+            output.push_str(&format!(
+                "{}{} {}, {}\n",
+                self.indent,
+                attribute,
+                symbol(&field.name),
+                dry_type
+            ));
+        }
+
+        output.push_str("end\n");
+        output
+    }
+
+    /// Whether a field may be missing from the data
+    fn is_optional(&self, field: &Field) -> bool {
+        field.optional || self.shared.optional_fields
+    }
+
+    /// Convert AST FieldType to a dry-types type expression
+    fn field_type_to_dry(&self, field_type: &FieldType) -> String {
+        match field_type {
+            FieldType::String | FieldType::Enum(_) | FieldType::Uuid => "Types::String".to_string(),
+            FieldType::DateTime(format) if !format.is_epoch() => "Types::String".to_string(),
+            FieldType::Integer | FieldType::DateTime(_) => "Types::Integer".to_string(),
+            FieldType::Float => "Types::Float".to_string(),
+            // JSON numbers arrive as Float or Integer, so coerce them
+            FieldType::Decimal => "Types::Coercible::Decimal".to_string(),
+            FieldType::Boolean => "Types::Bool".to_string(),
+            FieldType::Null | FieldType::Any => "Types::Any".to_string(),
+            FieldType::Array(inner) | FieldType::Set(inner) => {
+                format!("Types::Array.of({})", self.field_type_to_dry(inner))
+            }
+            FieldType::Optional(inner) => optional_dry(&self.field_type_to_dry(inner)),
+            FieldType::Reference(name) => name.clone(),
+            FieldType::Union(types) => {
+                let (members, nullable) = split_nullable_union(types);
+                let members: Vec<String> = members
+                    .into_iter()
+                    .map(|t| self.field_type_to_dry(t))
+                    .collect();
+                let union = match members.as_slice() {
+                    [] => return "Types::Any".to_string(),
+                    [single] => single.clone(),
+                    _ => members.join(" | "),
+                };
+                if nullable {
+                    optional_dry(&union)
+                } else {
+                    union
+                }
+            }
+            FieldType::Map(key, value) => {
+                // Keys of parsed JSON objects are always strings
+                let key = match key.as_ref() {
+                    FieldType::Integer => "Types::String".to_string(),
+                    other => self.field_type_to_dry(other),
+                };
+                format!(
+                    "Types::Hash.map({}, {})",
+                    key,
+                    self.field_type_to_dry(value)
+                )
+            }
+        }
+    }
+
+    /// Convert AST FieldType to a YARD type, used to document Struct members
+    fn field_type_to_yard(&self, field_type: &FieldType) -> String {
+        match field_type {
+            FieldType::String | FieldType::Enum(_) | FieldType::Uuid => "String".to_string(),
+            FieldType::DateTime(format) if !format.is_epoch() => "String".to_string(),
+            FieldType::Integer | FieldType::DateTime(_) => "Integer".to_string(),
+            FieldType::Float => "Float".to_string(),
+            FieldType::Decimal => "BigDecimal".to_string(),
+            FieldType::Boolean => "Boolean".to_string(),
+            FieldType::Null => "nil".to_string(),
+            FieldType::Any => "Object".to_string(),
+            FieldType::Array(inner) | FieldType::Set(inner) => {
+                format!("Array<{}>", self.field_type_to_yard(inner))
+            }
+            FieldType::Optional(inner) => format!("{}, nil", self.field_type_to_yard(inner)),
+            FieldType::Reference(name) => name.clone(),
+            FieldType::Union(types) => {
+                let (members, nullable) = split_nullable_union(types);
+                let mut rendered: Vec<String> = members
+                    .into_iter()
+                    .map(|t| self.field_type_to_yard(t))
+                    .collect();
+                if nullable {
+                    rendered.push("nil".to_string());
+                }
+                rendered.join(", ")
+            }
+            FieldType::Map(_, value) => {
+                format!("Hash{{String => {}}}", self.field_type_to_yard(value))
+            }
+        }
+    }
+}
+
+/// Make a dry-types expression accept nil, parenthesizing sum types
+fn optional_dry(dry_type: &str) -> String {
+    if dry_type.ends_with(".optional") {
+        dry_type.to_string()
+    } else if dry_type.contains(" | ") {
+        format!("({}).optional", dry_type)
+    } else {
+        format!("{}.optional", dry_type)
+    }
+}
+
+/// Render a field name as a symbol literal, quoting it when needed
+fn symbol(name: &str) -> String {
+    let is_identifier = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    if is_identifier {
+        format!(":{}", name)
+    } else {
+        format!(":{:?}", name)
+    }
+}
+
+/// Get the Struct member name for a field, which must be a method name
+///
+/// Names are kept as-is where possible so `new(**hash)` works with the
+/// parsed data.
+fn member_name(field: &Field) -> String {
+    let mut name: String = field
+        .name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !name.starts_with(|c: char| c.is_ascii_lowercase() || c == '_') {
+        name.insert(0, '_');
+    }
+    name
+}
+
+impl CodeGenerator for RubyGenerator {
+    fn generate(&self, schema: &Schema) -> Result<String> {
+        if schema.types.is_empty() {
+            return Err(AlchemistError::GenerationError(
+                "Schema has no types to generate".to_string(),
+            ));
+        }
+
+        let mut output = String::new();
+
+        output.push_str("# frozen_string_literal: true\n\n");
+        output.push_str("# Generated by Alchemist\n");
+        output.push_str("# Do not edit manually\n\n");
+
+        if self.options.style == RubyStyle::DryStruct {
+            output.push_str("require 'dry-struct'\n\n");
+            output.push_str(&format!(
+                "module Types\n{}include Dry.Types()\nend\n\n",
+                self.indent
+            ));
+        }
+
+        // Attribute types are constants resolved when the class body runs, so
+        // referenced classes must be defined first
+        let classes: Vec<String> = schema
+            .iter_topological(false)
+            .map(|type_def| match self.options.style {
+                RubyStyle::Struct => self.generate_struct(type_def),
+                RubyStyle::DryStruct => self.generate_dry_struct(type_def),
+            })
+            .collect();
+        output.push_str(&classes.join("\n"));
+
+        Ok(output)
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "rb"
+    }
+
+    fn name(&self) -> &'static str {
+        match self.options.style {
+            RubyStyle::Struct => "Ruby (Struct)",
+            RubyStyle::DryStruct => "Ruby (dry-struct)",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::GeneratorOptions;
+    use crate::parser::parse_json;
+
+    fn generate(json: &str, ruby_style: RubyStyle) -> String {
+        let options = GeneratorOptions {
+            ruby: RubyOptions { style: ruby_style },
+            ..Default::default()
+        };
+        let schema = parse_json(json, &options).unwrap();
+        RubyGenerator::new(options.ruby, options.shared)
+            .generate(&schema)
+            .unwrap()
+    }
+
+    const USERS: &str =
+        r#"{"users": [{"id": 1, "tags": ["a"]}, {"id": 2, "nickName": "b"}], "score": [1, null]}"#;
+
+    #[test]
+    fn test_generate_struct() {
+        let output = generate(USERS, RubyStyle::Struct);
+
+        assert!(output.contains("Users = Struct.new(\n  :id, # Integer\n"));
+        assert!(output.contains("  :tags, # Array<String>, nil\n"));
+        assert!(output.contains("  :nickName, # String, nil\n  keyword_init: true\n)\n"));
+        assert!(!output.contains("dry"));
+    }
+
+    #[test]
+    fn test_generate_dry_struct() {
+        let output = generate(USERS, RubyStyle::DryStruct);
+
+        assert!(output.contains("require 'dry-struct'\n"));
+        assert!(output.contains("class Users < Dry::Struct\n  transform_keys(&:to_sym)\n\n  attribute :id, Types::Integer\n"));
+        assert!(output.contains("  attribute? :tags, Types::Array.of(Types::String).optional\n"));
+        assert!(output.contains("  attribute? :nickName, Types::String.optional\n"));
+        assert!(output.contains("  attribute :score, Types::Array.of(Types::Integer.optional)\n"));
+        // Referenced classes are defined before use
+        assert!(output.find("class Users").unwrap() < output.find("class Root").unwrap());
+    }
+}
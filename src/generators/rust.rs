@@ -1,23 +1,228 @@
 //! Rust struct generator
 
-use crate::ast::{FieldType, Schema, TypeDef};
+use crate::ast::{DateTimeFormat, Field, FieldType, Schema, TypeDef, TypeOrigin};
 use crate::error::{AlchemistError, Result};
-use crate::generators::{CodeGenerator, GeneratorOptions};
-use crate::utils::to_snake_case;
+use crate::generators::{root_alias, CodeGenerator, Indent, SharedOptions};
+use crate::utils::{to_pascal_case, to_snake_case};
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+/// How unions of several types are represented in Rust
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UnionRepr {
+    /// A generated enum with one variant per member, `#[serde(untagged)]`
+    /// unless the members are the variants of a `--tagged-union`
+    #[default]
+    Enum,
+    /// Plain `serde_json::Value`, which accepts anything
+    Value,
+}
+
+/// Rust collection type used for arrays
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RustArrayType {
+    /// `Vec<T>`
+    #[default]
+    Vec,
+    /// `Box<[T]>`, for arrays that are not resized after loading
+    BoxedSlice,
+    /// `smallvec::SmallVec<[T; 4]>`, which needs the `smallvec` crate with
+    /// its `serde` feature
+    #[value(name = "smallvec")]
+    #[serde(rename = "smallvec")]
+    SmallVec,
+}
+
+impl RustArrayType {
+    /// Render the collection type for an element type
+    fn render(self, element: &str) -> String {
+        match self {
+            RustArrayType::Vec => format!("Vec<{}>", element),
+            RustArrayType::BoxedSlice => format!("Box<[{}]>", element),
+            RustArrayType::SmallVec => format!("smallvec::SmallVec<[{}; 4]>", element),
+        }
+    }
+}
+
+/// Rust type used for string fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RustStringType {
+    /// Owned `String`
+    #[default]
+    String,
+    /// `Cow<'a, str>`, borrowed from the input where possible; types holding
+    /// strings get an `'a` lifetime parameter and the fields that mention it
+    /// `#[serde(borrow)]`
+    Cow,
+}
+
+/// How many fields a `rename_all` convention matches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RenameCoverage {
+    /// Fields whose key is produced by the convention
+    pub covered: usize,
+    /// Fields that still need their own `#[serde(rename)]`
+    pub renamed: usize,
+}
+
+/// Options of the Rust generator
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RustOptions {
+    /// Derive macros to add
+    pub derive_macros: Vec<String>,
+    /// Whether to emit `TryFrom<serde_json::Value>` impls
+    pub with_tryfrom: bool,
+    /// Whether to reject unknown fields on structs inferred from a single object
+    pub deny_unknown: bool,
+    /// Whether single-field types become transparent newtype structs
+    pub newtype_wrappers: bool,
+    /// Representation of unions of several types
+    pub union_repr: UnionRepr,
+    /// Collection type used for arrays
+    #[serde(rename = "rust_array_type")]
+    pub array_type: RustArrayType,
+    /// Type used for string fields
+    #[serde(rename = "rust_string_type")]
+    pub string_type: RustStringType,
+}
+
+impl Default for RustOptions {
+    fn default() -> Self {
+        Self {
+            derive_macros: vec![
+                "Debug".to_string(),
+                "Clone".to_string(),
+                "Serialize".to_string(),
+                "Deserialize".to_string(),
+            ],
+            with_tryfrom: false,
+            deny_unknown: false,
+            newtype_wrappers: false,
+            union_repr: UnionRepr::default(),
+            array_type: RustArrayType::default(),
+            string_type: RustStringType::default(),
+        }
+    }
+}
 
 /// Rust struct generator
 pub struct RustGenerator {
-    options: GeneratorOptions,
+    options: RustOptions,
+    shared: SharedOptions,
+    /// Whitespace of one indentation level
+    indent: String,
+    /// Structs and enums that take the `'a` lifetime, set per `generate` call
+    borrowed_types: RefCell<HashSet<String>>,
+    /// Names of the enums generated for string enums, set per `generate` call
+    enum_names: RefCell<HashMap<Vec<String>, String>>,
 }
 
 impl RustGenerator {
-    /// Create a new Rust generator with the given options
-    pub fn new(options: GeneratorOptions) -> Self {
-        Self { options }
+    /// Create a new Rust generator with its own and the shared options
+    pub fn new(options: RustOptions, shared: SharedOptions) -> Self {
+        let indent = shared.indent_unit(Indent::Spaces(4));
+        Self {
+            options,
+            shared,
+            indent,
+            borrowed_types: RefCell::new(HashSet::new()),
+            enum_names: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Whitespace of `levels` indentation levels
+    fn level(&self, levels: usize) -> String {
+        self.indent.repeat(levels)
+    }
+
+    /// Find the structs and union enums that hold a `Cow<'a, str>`, directly
+    /// or through a nested type, and so need the `'a` lifetime
+    fn find_borrowed_types(&self, schema: &Schema, enums: &[Vec<FieldType>]) -> HashSet<String> {
+        let mut borrowed = HashSet::new();
+        if self.options.string_type != RustStringType::Cow {
+            return borrowed;
+        }
+        // Lifetimes propagate up through references, so repeat until stable
+        loop {
+            let mut changed = false;
+            for type_def in &schema.types {
+                if !borrowed.contains(&type_def.name)
+                    && type_def
+                        .fields
+                        .iter()
+                        .any(|f| self.borrows(&f.field_type, &borrowed))
+                {
+                    borrowed.insert(type_def.name.clone());
+                    changed = true;
+                }
+            }
+            for members in enums {
+                let name = union_enum_name(members);
+                if !borrowed.contains(&name) && members.iter().any(|m| self.borrows(m, &borrowed)) {
+                    borrowed.insert(name);
+                    changed = true;
+                }
+            }
+            if !changed {
+                return borrowed;
+            }
+        }
+    }
+
+    /// Whether the Rust type for `field_type` mentions the `'a` lifetime,
+    /// given the types known to take it
+    fn borrows(&self, field_type: &FieldType, borrowed: &HashSet<String>) -> bool {
+        match field_type {
+            FieldType::String => self.options.string_type == RustStringType::Cow,
+            FieldType::Array(inner) | FieldType::Set(inner) | FieldType::Optional(inner) => {
+                self.borrows(inner, borrowed)
+            }
+            FieldType::Map(key, value) => {
+                self.borrows(key, borrowed) || self.borrows(value, borrowed)
+            }
+            FieldType::Reference(name) => borrowed.contains(name),
+            FieldType::Union(types) => match split_union(types).0.as_slice() {
+                [single] => self.borrows(single, borrowed),
+                _ if self.options.union_repr == UnionRepr::Value => false,
+                members => borrowed.contains(&union_enum_name(members)),
+            },
+            _ => false,
+        }
+    }
+
+    /// Whether a type name takes the `'a` lifetime
+    fn is_borrowed(&self, name: &str) -> bool {
+        self.borrowed_types.borrow().contains(name)
+    }
+
+    /// A struct or enum name with its lifetime parameter, if it has one
+    fn with_lifetime(&self, name: &str) -> String {
+        if self.is_borrowed(name) {
+            format!("{}<'a>", name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    /// `#[serde(borrow)]` for a field whose type mentions the `'a` lifetime
+    ///
+    /// serde only borrows from the input through fields marked this way.
+    fn borrow_attribute(&self, field_type: &FieldType) -> Option<&'static str> {
+        self.borrows(field_type, &self.borrowed_types.borrow())
+            .then_some("#[serde(borrow)]")
     }
 
     /// Generate a Rust struct from a type definition
-    fn generate_struct(&self, type_def: &TypeDef) -> String {
+    ///
+    /// With `newtype_field` set the type becomes a transparent tuple struct
+    /// around that field.
+    fn generate_struct(&self, type_def: &TypeDef, newtype_field: Option<&Field>) -> String {
         let mut output = String::new();
 
         // Add documentation comment if present
@@ -33,34 +238,70 @@ impl RustGenerator {
             ));
         }
 
+        if let Some(field) = newtype_field {
+            output.push_str("#[serde(transparent)]\n");
+            output.push_str(&format!(
+                "pub struct {}({}{});\n",
+                self.with_lifetime(&type_def.name),
+                serde_with_attribute(field)
+                    .or_else(|| self.borrow_attribute(&field.field_type).map(str::to_string))
+                    .map(|attribute| attribute + " ")
+                    .unwrap_or_default(),
+                self.visible(&self.field_type_to_rust(&field.field_type, field.optional))
+            ));
+            return output;
+        }
+
+        // Types merged from array elements are expected to see fields they lack
+        if self.options.deny_unknown && type_def.origin == TypeOrigin::Object {
+            output.push_str("#[serde(deny_unknown_fields)]\n");
+        }
+
+        if let Some(rename_all) = self.shared.rename_all {
+            output.push_str(&format!(
+                "#[serde(rename_all = \"{}\")]\n",
+                rename_all.serde_name()
+            ));
+        }
+
         // Struct declaration
-        output.push_str(&format!("pub struct {} {{\n", type_def.name));
+        output.push_str(&format!(
+            "pub struct {} {{\n",
+            self.with_lifetime(&type_def.name)
+        ));
 
         // Generate fields
         for field in &type_def.fields {
+            if self.is_tag_field(type_def, field) {
+                continue;
+            }
+
             // Add field documentation if present
             if let Some(doc) = &field.doc {
-                output.push_str(&format!("    /// {}\n", doc));
+                output.push_str(&format!("{}/// {}\n", self.indent, doc));
             }
 
             // Add serde rename if needed
-            let original_name = &field.name;
-            let code_name = field.code_name();
-            if original_name != code_name {
-                output.push_str(&format!("    #[serde(rename = \"{}\")]\n", original_name));
+            if self.needs_rename(field) {
+                output.push_str(&format!(
+                    "{}#[serde(rename = \"{}\")]\n",
+                    self.indent, field.name
+                ));
+            }
+
+            if let Some(attribute) = serde_with_attribute(field) {
+                output.push_str(&format!("{}{}\n", self.indent, attribute));
+            }
+            if let Some(attribute) = self.borrow_attribute(&field.field_type) {
+                output.push_str(&format!("{}{}\n", self.indent, attribute));
             }
 
-            let visibility = if self.options.public_fields {
-                "pub "
-            } else {
-                ""
-            };
             let rust_type = self.field_type_to_rust(&field.field_type, field.optional);
 
             output.push_str(&format!(
-                "    {}{}: {},\n",
-                visibility,
-                to_snake_case(code_name),
+                "{}{}: {},\n",
+                self.indent,
+                self.visible(&to_snake_case(field.code_name())),
                 rust_type
             ));
         }
@@ -69,36 +310,552 @@ impl RustGenerator {
         output
     }
 
+    /// Whether a field is the tag of a tagged union variant, which the enum
+    /// reads and writes in place of the struct
+    fn is_tag_field(&self, type_def: &TypeDef, field: &Field) -> bool {
+        self.options.union_repr == UnionRepr::Enum
+            && type_def.tag.as_deref() == Some(field.name.as_str())
+    }
+
+    /// Whether a field's key differs from the one serde derives from its
+    /// identifier, after any `rename_all` convention
+    fn needs_rename(&self, field: &Field) -> bool {
+        let ident = to_snake_case(field.code_name());
+        let ident = ident.strip_prefix("r#").unwrap_or(&ident);
+        let key = match self.shared.rename_all {
+            Some(rename_all) => rename_all.apply(ident),
+            None => ident.to_string(),
+        };
+        key != field.name
+    }
+
+    /// Count the struct fields covered by `rename_all` and those that still
+    /// need a per-field rename
+    pub fn rename_coverage(&self, schema: &Schema) -> RenameCoverage {
+        let mut coverage = RenameCoverage::default();
+        let fields = schema.types.iter().flat_map(|type_def| {
+            type_def
+                .fields
+                .iter()
+                .filter(|field| !self.is_tag_field(type_def, field))
+        });
+        for field in fields {
+            if self.needs_rename(field) {
+                coverage.renamed += 1;
+            } else {
+                coverage.covered += 1;
+            }
+        }
+        coverage
+    }
+
+    /// Prefix a field with `pub` if fields are public
+    fn visible(&self, field: &str) -> String {
+        if self.shared.public_fields {
+            format!("pub {}", field)
+        } else {
+            field.to_string()
+        }
+    }
+
+    /// The single field a type wraps, if it should become a newtype struct
+    ///
+    /// Types that appear in an enum are kept as structs, since an enum
+    /// variant is where the object's keys identify which type matched.
+    fn newtype_field<'a>(
+        &self,
+        type_def: &'a TypeDef,
+        enums: &[Vec<FieldType>],
+    ) -> Option<&'a Field> {
+        if !self.options.newtype_wrappers {
+            return None;
+        }
+        let in_enum = enums
+            .iter()
+            .flatten()
+            .any(|member| matches!(member, FieldType::Reference(name) if *name == type_def.name));
+        match type_def.fields.as_slice() {
+            [field] if !in_enum => Some(field),
+            _ => None,
+        }
+    }
+
+    /// Generate a `TryFrom<serde_json::Value>` impl for a newtype struct,
+    /// which converts the value as a whole like `#[serde(transparent)]`
+    fn generate_newtype_try_from(&self, type_def: &TypeDef, field: &Field) -> String {
+        let name = &type_def.name;
+        let mut output = String::new();
+
+        output.push_str(&self.try_from_header(name));
+        let conversion = self.conversion_expr(&field.field_type, name);
+        if field.optional && !is_nullable(&field.field_type) {
+            output.push_str(&format!(
+                "{}if value.is_null() {{ Ok(Self(None)) }} else {{ {}.map(|value| Self(Some(value))) }}\n",
+                self.level(2),
+                conversion
+            ));
+        } else {
+            output.push_str(&format!("{}{}.map(Self)\n", self.level(2), conversion));
+        }
+        output.push_str(&format!("{}}}\n", self.indent));
+        output.push_str("}\n");
+        output
+    }
+
+    /// Generate an enum with one variant per union member
+    ///
+    /// Members that are the variants of a tagged union make an internally
+    /// tagged enum, otherwise the enum is untagged.
+    fn generate_union_enum(&self, schema: &Schema, members: &[FieldType]) -> String {
+        let mut output = String::new();
+
+        if !self.options.derive_macros.is_empty() {
+            output.push_str(&format!(
+                "#[derive({})]\n",
+                self.options.derive_macros.join(", ")
+            ));
+        }
+        let tagged = tagged_variants(schema, members);
+        match &tagged {
+            Some((tag, _)) => output.push_str(&format!("#[serde(tag = {:?})]\n", tag)),
+            None => output.push_str("#[serde(untagged)]\n"),
+        }
+        output.push_str(&format!(
+            "pub enum {} {{\n",
+            self.with_lifetime(&union_enum_name(members))
+        ));
+        for (i, member) in members.iter().enumerate() {
+            if let Some((_, values)) = &tagged {
+                if variant_name(member) != values[i] {
+                    output.push_str(&format!(
+                        "{}#[serde(rename = {:?})]\n",
+                        self.indent, values[i]
+                    ));
+                }
+            }
+            output.push_str(&format!(
+                "{}{}({}{}),\n",
+                self.indent,
+                variant_name(member),
+                self.borrow_attribute(member)
+                    .map(|attribute| format!("{} ", attribute))
+                    .unwrap_or_default(),
+                self.field_type_to_rust(member, false)
+            ));
+        }
+        if self.shared.enum_catchall && tagged.is_some() {
+            // Any tag not listed above deserializes to this variant
+            output.push_str(&format!(
+                "{0}#[serde(other)]\n{0}{1},\n",
+                self.indent,
+                catchall_variant_name(members)
+            ));
+        } else if self.shared.enum_catchall {
+            // Untagged enums cannot use `#[serde(other)]`; a last variant
+            // holding the raw value catches whatever matched no other one
+            output.push_str(&format!(
+                "{}{}(serde_json::Value),\n",
+                self.indent,
+                catchall_variant_name(members)
+            ));
+        }
+        output.push_str("}\n");
+        output
+    }
+
+    /// Name each distinct string enum after the first field it appears in,
+    /// avoiding the names of structs and of other enums
+    fn name_string_enums(&self, schema: &Schema) -> Vec<(String, Vec<String>)> {
+        let mut used: HashSet<String> = schema.types.iter().map(|t| t.name.clone()).collect();
+        let mut named: Vec<(String, Vec<String>)> = Vec::new();
+        for type_def in &schema.types {
+            for field in &type_def.fields {
+                if self.is_tag_field(type_def, field) {
+                    continue;
+                }
+                let mut values = Vec::new();
+                collect_string_enums(&field.field_type, &mut values);
+                let Some(values) = values
+                    .into_iter()
+                    .find(|values| !named.iter().any(|(_, seen)| seen == *values))
+                else {
+                    continue;
+                };
+                let field_name = to_pascal_case(&to_snake_case(field.code_name()));
+                let mut name = field_name.clone();
+                if used.contains(&name) {
+                    name = format!("{}{}", type_def.name, field_name);
+                }
+                let base = name.clone();
+                let mut counter = 1;
+                while !used.insert(name.clone()) {
+                    counter += 1;
+                    name = format!("{}{}", base, counter);
+                }
+                named.push((name, values.clone()));
+            }
+        }
+        named
+    }
+
+    /// Generate a unit enum for a string enum, one variant per value
+    fn generate_string_enum(&self, name: &str, values: &[String]) -> String {
+        let mut output = String::new();
+
+        if !self.options.derive_macros.is_empty() {
+            output.push_str(&format!(
+                "#[derive({})]\n",
+                self.options.derive_macros.join(", ")
+            ));
+        }
+        output.push_str(&format!("pub enum {} {{\n", name));
+        let mut used = HashSet::new();
+        for value in values {
+            let base = enum_variant_name(value);
+            let mut variant = base.clone();
+            let mut counter = 1;
+            while !used.insert(variant.clone()) {
+                counter += 1;
+                variant = format!("{}{}", base, counter);
+            }
+            if variant != *value {
+                output.push_str(&format!("{}#[serde(rename = {:?})]\n", self.indent, value));
+            }
+            output.push_str(&format!("{}{},\n", self.indent, variant));
+        }
+        if self.shared.enum_catchall {
+            // Any value not listed above deserializes to this variant
+            let catchall = if used.contains("Unknown") {
+                "Other"
+            } else {
+                "Unknown"
+            };
+            output.push_str(&format!(
+                "{0}#[serde(other)]\n{0}{1},\n",
+                self.indent, catchall
+            ));
+        }
+        output.push_str("}\n");
+        output
+    }
+
+    /// Generate a hand-written `TryFrom<serde_json::Value>` impl for a struct
+    ///
+    /// Each field is extracted from the object and checked against its inferred
+    /// type, so a mismatch is reported with the offending field path instead of
+    /// serde's positional error.
+    fn generate_try_from(&self, type_def: &TypeDef) -> String {
+        let name = &type_def.name;
+        let mut output = String::new();
+
+        output.push_str(&self.try_from_header(name));
+
+        let [i2, i3, i4, i5] = [2, 3, 4, 5].map(|levels| self.level(levels));
+        let not_object = error_literal(&format!("{}: expected an object", name));
+        if type_def.fields.is_empty() {
+            output.push_str(&format!("{}match value {{\n", i2));
+            output.push_str(&format!(
+                "{}serde_json::Value::Object(_) => Ok(Self {{}}),\n",
+                i3
+            ));
+            output.push_str(&format!("{}_ => Err({}),\n", i3, not_object));
+            output.push_str(&format!("{}}}\n", i2));
+        } else {
+            output.push_str(&format!("{}let mut object = match value {{\n", i2));
+            output.push_str(&format!(
+                "{}serde_json::Value::Object(object) => object,\n",
+                i3
+            ));
+            output.push_str(&format!("{}_ => return Err({}),\n", i3, not_object));
+            output.push_str(&format!("{}}};\n\n", i2));
+            output.push_str(&format!("{}Ok(Self {{\n", i2));
+
+            for field in &type_def.fields {
+                if self.is_tag_field(type_def, field) {
+                    continue;
+                }
+                let path = format!("{}.{}", name, field.name);
+                let key = format!("{:?}", field.name);
+
+                output.push_str(&format!("{}{}: {{\n", i3, to_snake_case(field.code_name())));
+                if field.optional && !matches!(field.field_type, FieldType::Optional(_)) {
+                    output.push_str(&format!("{}match object.remove({}) {{\n", i4, key));
+                    output.push_str(&format!(
+                        "{}None | Some(serde_json::Value::Null) => None,\n",
+                        i5
+                    ));
+                    output.push_str(&format!(
+                        "{}Some(value) => Some({}?),\n",
+                        i5,
+                        self.conversion_expr(&field.field_type, &path)
+                    ));
+                    output.push_str(&format!("{}}}\n", i4));
+                } else {
+                    if is_nullable(&field.field_type) {
+                        // serde treats a missing `Option` field as `None`
+                        output.push_str(&format!(
+                            "{}let value = object.remove({}).unwrap_or(serde_json::Value::Null);\n",
+                            i4, key
+                        ));
+                    } else {
+                        output.push_str(&format!(
+                            "{}let value = object.remove({}).ok_or_else(|| {})?;\n",
+                            i4,
+                            key,
+                            error_literal(&format!("{}: missing field '{}'", name, field.name))
+                        ));
+                    }
+                    output.push_str(&format!(
+                        "{}{}?\n",
+                        i4,
+                        self.conversion_expr(&field.field_type, &path)
+                    ));
+                }
+                output.push_str(&format!("{}}},\n", i3));
+            }
+
+            output.push_str(&format!("{}}})\n", i2));
+        }
+
+        output.push_str(&format!("{}}}\n", self.indent));
+        output.push_str("}\n");
+        output
+    }
+
+    /// Generate [`DECIMAL_SEQ_MODULE`], which wraps each element in
+    /// `rust_decimal::serde::float` and works with any of the array types
+    fn decimal_seq_module(&self) -> String {
+        let [i1, i2, i3] = [1, 2, 3].map(|levels| self.level(levels));
+        let lines = [
+            "/// (De)serializes an array of decimals as JSON numbers".to_string(),
+            format!("mod {} {{", DECIMAL_SEQ_MODULE),
+            format!("{i1}use rust_decimal::Decimal;"),
+            format!("{i1}use serde::{{Deserialize, Deserializer, Serialize, Serializer}};"),
+            String::new(),
+            format!("{i1}#[derive(Serialize, Deserialize)]"),
+            format!("{i1}struct Float(#[serde(with = \"rust_decimal::serde::float\")] Decimal);"),
+            String::new(),
+            format!("{i1}struct Floats<'a>(&'a [Decimal]);"),
+            String::new(),
+            format!("{i1}impl Serialize for Floats<'_> {{"),
+            format!("{i2}fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {{"),
+            format!("{i3}serializer.collect_seq(self.0.iter().map(|value| Float(*value)))"),
+            format!("{i2}}}"),
+            format!("{i1}}}"),
+            String::new(),
+            format!("{i1}pub fn serialize<C: AsRef<[Decimal]>, S: Serializer>(values: &C, serializer: S) -> Result<S::Ok, S::Error> {{"),
+            format!("{i2}Floats(values.as_ref()).serialize(serializer)"),
+            format!("{i1}}}"),
+            String::new(),
+            format!("{i1}pub fn deserialize<'de, C: FromIterator<Decimal>, D: Deserializer<'de>>(deserializer: D) -> Result<C, D::Error> {{"),
+            format!("{i2}let values = Vec::<Float>::deserialize(deserializer)?;"),
+            format!("{i2}Ok(values.into_iter().map(|Float(value)| value).collect())"),
+            format!("{i1}}}"),
+            String::new(),
+            format!("{i1}pub mod option {{"),
+            format!("{i2}use super::*;"),
+            String::new(),
+            format!("{i2}pub fn serialize<C: AsRef<[Decimal]>, S: Serializer>(values: &Option<C>, serializer: S) -> Result<S::Ok, S::Error> {{"),
+            format!("{i3}values.as_ref().map(|values| Floats(values.as_ref())).serialize(serializer)"),
+            format!("{i2}}}"),
+            String::new(),
+            format!("{i2}pub fn deserialize<'de, C: FromIterator<Decimal>, D: Deserializer<'de>>(deserializer: D) -> Result<Option<C>, D::Error> {{"),
+            format!("{i3}let values = Option::<Vec<Float>>::deserialize(deserializer)?;"),
+            format!("{i3}Ok(values.map(|values| values.into_iter().map(|Float(value)| value).collect()))"),
+            format!("{i2}}}"),
+            format!("{i1}}}"),
+            "}".to_string(),
+        ];
+        lines.join("\n") + "\n"
+    }
+
+    /// Opening lines of a `TryFrom<serde_json::Value>` impl for a type, up to
+    /// the body of `try_from`
+    fn try_from_header(&self, name: &str) -> String {
+        let lifetime = if self.is_borrowed(name) { "<'a>" } else { "" };
+        format!(
+            "impl{0} TryFrom<serde_json::Value> for {1} {{\n{2}type Error = String;\n\n{2}fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {{\n",
+            lifetime,
+            self.with_lifetime(name),
+            self.indent
+        )
+    }
+
+    /// Build an expression converting the `serde_json::Value` bound to `value`
+    /// into the Rust type for `field_type`, evaluating to `Result<T, String>`
+    fn conversion_expr(&self, field_type: &FieldType, path: &str) -> String {
+        let expected = |what: &str| error_literal(&format!("{}: expected {}", path, what));
+
+        match field_type {
+            FieldType::String => {
+                let owned = match self.options.string_type {
+                    RustStringType::String => "str::to_string",
+                    RustStringType::Cow => "|s| Cow::Owned(s.to_string())",
+                };
+                format!(
+                    "value.as_str().map({}).ok_or_else(|| {})",
+                    owned,
+                    expected("a string")
+                )
+            }
+            FieldType::Enum(_) => format!(
+                "serde_json::from_value(value).map_err(|e| format!(\"{{}}: {{}}\", {:?}, e))",
+                path
+            ),
+            FieldType::Integer => format!(
+                "value.as_i64().ok_or_else(|| {})",
+                expected("an integer")
+            ),
+            FieldType::Float => format!("value.as_f64().ok_or_else(|| {})", expected("a number")),
+            // Parsing the number's text keeps every digit of the source value
+            FieldType::Decimal => format!(
+                "value.as_number().and_then(|n| n.to_string().parse::<rust_decimal::Decimal>().ok()).ok_or_else(|| {})",
+                expected("a decimal number")
+            ),
+            FieldType::Uuid => format!(
+                "value.as_str().and_then(|text| text.parse::<uuid::Uuid>().ok()).ok_or_else(|| {})",
+                expected("a UUID")
+            ),
+            FieldType::Boolean => {
+                format!("value.as_bool().ok_or_else(|| {})", expected("a boolean"))
+            }
+            FieldType::DateTime(DateTimeFormat::EpochSeconds) => format!(
+                "value.as_i64().and_then(|seconds| chrono::DateTime::from_timestamp(seconds, 0)).ok_or_else(|| {})",
+                expected("a Unix timestamp in seconds")
+            ),
+            FieldType::DateTime(DateTimeFormat::EpochMillis) => format!(
+                "value.as_i64().and_then(chrono::DateTime::from_timestamp_millis).ok_or_else(|| {})",
+                expected("a Unix timestamp in milliseconds")
+            ),
+            FieldType::DateTime(format) => format!(
+                "value.as_str().and_then(|text| text.parse::<{}>().ok()).ok_or_else(|| {})",
+                datetime_type(*format),
+                expected(&format!("an {}", format.description()))
+            ),
+            FieldType::Null => format!(
+                "if value.is_null() {{ Ok(()) }} else {{ Err({}) }}",
+                expected("null")
+            ),
+            FieldType::Array(inner) => format!(
+                "match value {{ serde_json::Value::Array(items) => items.into_iter().map(|value| {}).collect::<Result<{}, String>>(), _ => Err({}) }}",
+                self.conversion_expr(inner, &format!("{}[]", path)),
+                self.options.array_type.render("_"),
+                expected("an array")
+            ),
+            FieldType::Set(inner) => format!(
+                "match value {{ serde_json::Value::Array(items) => items.into_iter().map(|value| {}).collect::<Result<std::collections::HashSet<_>, String>>(), _ => Err({}) }}",
+                self.conversion_expr(inner, &format!("{}[]", path)),
+                expected("an array")
+            ),
+            FieldType::Optional(inner) => format!(
+                "if value.is_null() {{ Ok(None) }} else {{ {}.map(Some) }}",
+                self.conversion_expr(inner, path)
+            ),
+            FieldType::Reference(name) => format!(
+                "{}::try_from(value).map_err(|e| format!(\"{{}}: {{}}\", {:?}, e))",
+                name, path
+            ),
+            FieldType::Union(types) => {
+                let (members, nullable) = split_union(types);
+                let inner = match members.as_slice() {
+                    [single] => self.conversion_expr(single, path),
+                    _ if self.options.union_repr == UnionRepr::Value => {
+                        "Ok::<_, String>(value)".to_string()
+                    }
+                    // The generated untagged enum picks the matching variant.
+                    // `from_value` needs an owned type, so borrowing enums
+                    // deserialize from the value directly
+                    members => {
+                        let deserialize = if self.is_borrowed(&union_enum_name(members)) {
+                            "serde::Deserialize::deserialize"
+                        } else {
+                            "serde_json::from_value"
+                        };
+                        format!(
+                            "{}(value).map_err(|e| format!(\"{{}}: {{}}\", {:?}, e))",
+                            deserialize, path
+                        )
+                    }
+                };
+                if nullable {
+                    format!(
+                        "if value.is_null() {{ Ok(None) }} else {{ {}.map(Some) }}",
+                        inner
+                    )
+                } else {
+                    inner
+                }
+            }
+            // Unknown values are kept as raw JSON
+            FieldType::Any => "Ok::<_, String>(value)".to_string(),
+            FieldType::Map(key, inner) => {
+                let key = match key.as_ref() {
+                    FieldType::Integer => format!(
+                        "key.parse::<i64>().map_err(|_| {})?",
+                        expected("integer keys")
+                    ),
+                    _ => "key".to_string(),
+                };
+                format!(
+                    "match value {{ serde_json::Value::Object(entries) => entries.into_iter().map(|(key, value)| Ok(({}, {}?))).collect::<Result<std::collections::HashMap<_, _>, String>>(), _ => Err({}) }}",
+                    key,
+                    self.conversion_expr(inner, &format!("{}.*", path)),
+                    expected("an object")
+                )
+            }
+        }
+    }
+
     /// Convert AST FieldType to Rust type string
     fn field_type_to_rust(&self, field_type: &FieldType, optional: bool) -> String {
         let base_type = match field_type {
-            FieldType::String => "String".to_string(),
+            FieldType::String => match self.options.string_type {
+                RustStringType::String => "String".to_string(),
+                RustStringType::Cow => "Cow<'a, str>".to_string(),
+            },
+            FieldType::Enum(values) => self
+                .enum_names
+                .borrow()
+                .get(values)
+                .cloned()
+                .unwrap_or_else(|| "String".to_string()),
             FieldType::Integer => "i64".to_string(),
             FieldType::Float => "f64".to_string(),
             FieldType::Boolean => "bool".to_string(),
             FieldType::Null => "()".to_string(),
             FieldType::Array(inner) => {
-                format!("Vec<{}>", self.field_type_to_rust(inner, false))
+                let element = self.field_type_to_rust(inner, false);
+                self.options.array_type.render(&element)
+            }
+            FieldType::Set(inner) => {
+                format!(
+                    "std::collections::HashSet<{}>",
+                    self.field_type_to_rust(inner, false)
+                )
             }
             FieldType::Optional(inner) => {
                 format!("Option<{}>", self.field_type_to_rust(inner, false))
             }
-            FieldType::Reference(name) => name.clone(),
+            FieldType::Reference(name) => self.with_lifetime(name),
             FieldType::Union(types) => {
-                // For union types, we'd need to generate an enum
-                // For now, use serde_json::Value as a fallback
-                if types.len() == 2 && types.iter().any(|t| matches!(t, FieldType::Null)) {
-                    // Handle nullable types
-                    let non_null = types
-                        .iter()
-                        .find(|t| !matches!(t, FieldType::Null))
-                        .unwrap();
-                    format!("Option<{}>", self.field_type_to_rust(non_null, false))
+                let (members, nullable) = split_union(types);
+                let inner = match members.as_slice() {
+                    [single] => self.field_type_to_rust(single, false),
+                    _ if self.options.union_repr == UnionRepr::Value => {
+                        "serde_json::Value".to_string()
+                    }
+                    _ => self.with_lifetime(&union_enum_name(&members)),
+                };
+                if nullable {
+                    format!("Option<{}>", inner)
                 } else {
-                    "serde_json::Value".to_string()
+                    inner
                 }
             }
             FieldType::Any => "serde_json::Value".to_string(),
+            FieldType::DateTime(format) => datetime_type(*format).to_string(),
+            FieldType::Decimal => "rust_decimal::Decimal".to_string(),
+            FieldType::Uuid => "uuid::Uuid".to_string(),
             FieldType::Map(key, value) => {
                 format!(
                     "std::collections::HashMap<{}, {}>",
@@ -116,6 +873,223 @@ impl RustGenerator {
     }
 }
 
+/// Whether the Rust type for `field_type` is an `Option`
+fn is_nullable(field_type: &FieldType) -> bool {
+    match field_type {
+        FieldType::Optional(_) => true,
+        FieldType::Union(types) => split_union(types).1,
+        _ => false,
+    }
+}
+
+/// Split union members into the non-null members, in variant order, and
+/// whether `null` was one of them
+fn split_union(types: &[FieldType]) -> (Vec<FieldType>, bool) {
+    let mut members: Vec<FieldType> = types
+        .iter()
+        .filter(|t| !matches!(t, FieldType::Null))
+        .cloned()
+        .collect();
+    // Untagged enums try variants in order, so narrower types go first:
+    // an integer must be tried before a float, which would also accept it
+    members.sort_by_key(variant_rank);
+    members.dedup();
+    let nullable = types.iter().any(|t| matches!(t, FieldType::Null));
+    (members, nullable)
+}
+
+/// Position of a union member in the generated enum
+fn variant_rank(field_type: &FieldType) -> u8 {
+    match field_type {
+        FieldType::Boolean => 0,
+        FieldType::Integer => 1,
+        FieldType::Float => 2,
+        FieldType::String => 3,
+        FieldType::Reference(_) => 4,
+        FieldType::Array(_) | FieldType::Set(_) => 5,
+        FieldType::Map(_, _) => 6,
+        _ => 7,
+    }
+}
+
+/// Name of the variant wrapping a union member
+fn variant_name(field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::String => "String".to_string(),
+        FieldType::Enum(_) => "Enum".to_string(),
+        FieldType::Integer => "Integer".to_string(),
+        FieldType::Float => "Float".to_string(),
+        FieldType::Boolean => "Boolean".to_string(),
+        FieldType::Null => "Null".to_string(),
+        FieldType::Reference(name) => name.clone(),
+        FieldType::Array(inner) => format!("{}Array", variant_name(inner)),
+        FieldType::Set(inner) => format!("{}Set", variant_name(inner)),
+        FieldType::Optional(inner) => format!("Optional{}", variant_name(inner)),
+        FieldType::Map(_, value) => format!("{}Map", variant_name(value)),
+        FieldType::Union(types) => union_enum_name(&split_union(types).0),
+        FieldType::Any => "Value".to_string(),
+        FieldType::DateTime(_) => "DateTime".to_string(),
+        FieldType::Decimal => "Decimal".to_string(),
+        FieldType::Uuid => "Uuid".to_string(),
+    }
+}
+
+/// chrono type of a date/time; those with an offset or epoch are read as UTC
+fn datetime_type(format: DateTimeFormat) -> &'static str {
+    match format {
+        DateTimeFormat::EpochSeconds | DateTimeFormat::EpochMillis | DateTimeFormat::Rfc3339 => {
+            "chrono::DateTime<chrono::Utc>"
+        }
+        DateTimeFormat::LocalDateTime => "chrono::NaiveDateTime",
+        DateTimeFormat::Date => "chrono::NaiveDate",
+    }
+}
+
+/// Name of the catch-all variant of a union enum, `Other` unless a member
+/// already uses that name
+fn catchall_variant_name(members: &[FieldType]) -> &'static str {
+    if members.iter().any(|member| variant_name(member) == "Other") {
+        "Unknown"
+    } else {
+        "Other"
+    }
+}
+
+/// The tag field shared by union members that are all variants of one
+/// tagged union, and each member's tag value
+fn tagged_variants<'a>(
+    schema: &'a Schema,
+    members: &[FieldType],
+) -> Option<(&'a str, Vec<&'a str>)> {
+    let mut tag = None;
+    let mut values = Vec::new();
+    for member in members {
+        let FieldType::Reference(name) = member else {
+            return None;
+        };
+        let type_def = schema.types.iter().find(|t| t.name == *name)?;
+        let member_tag = type_def.tag.as_deref()?;
+        if tag.is_some_and(|tag| tag != member_tag) {
+            return None;
+        }
+        tag = Some(member_tag);
+        let field = type_def.fields.iter().find(|f| f.name == member_tag)?;
+        match &field.field_type {
+            FieldType::Enum(tag_values) => values.push(tag_values.first()?.as_str()),
+            _ => return None,
+        }
+    }
+    tag.map(|tag| (tag, values))
+}
+
+/// Name of the enum generated for a union, e.g. `IntegerOrString`
+fn union_enum_name(members: &[FieldType]) -> String {
+    members
+        .iter()
+        .map(variant_name)
+        .collect::<Vec<_>>()
+        .join("Or")
+}
+
+/// Collect the member lists of all unions that need an enum, innermost first
+fn collect_unions(field_type: &FieldType, enums: &mut Vec<Vec<FieldType>>) {
+    match field_type {
+        FieldType::Array(inner)
+        | FieldType::Set(inner)
+        | FieldType::Optional(inner)
+        | FieldType::Map(_, inner) => collect_unions(inner, enums),
+        FieldType::Union(types) => {
+            let (members, _) = split_union(types);
+            for member in &members {
+                collect_unions(member, enums);
+            }
+            if members.len() > 1 && !enums.contains(&members) {
+                enums.push(members);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Collect the value lists of all string enums in a type
+fn collect_string_enums<'a>(field_type: &'a FieldType, enums: &mut Vec<&'a Vec<String>>) {
+    match field_type {
+        FieldType::Enum(values) => enums.push(values),
+        FieldType::Array(inner) | FieldType::Set(inner) | FieldType::Optional(inner) => {
+            collect_string_enums(inner, enums)
+        }
+        FieldType::Map(key, value) => {
+            collect_string_enums(key, enums);
+            collect_string_enums(value, enums);
+        }
+        FieldType::Union(types) => {
+            for member in types {
+                collect_string_enums(member, enums);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Variant name of a string enum value, e.g. `InProgress` for `in-progress`
+fn enum_variant_name(value: &str) -> String {
+    let words: String = value
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let mut name = to_pascal_case(&to_snake_case(&words));
+    if name.is_empty() {
+        name.push_str("Value");
+    } else if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, 'V');
+    }
+    name
+}
+
+/// Generated module (de)serializing an array of decimals as JSON numbers,
+/// which `rust_decimal::serde::float` only does for a single value
+const DECIMAL_SEQ_MODULE: &str = "decimal_float_seq";
+
+/// The serde module that (de)serializes a field in its source encoding, if
+/// the field needs one: `chrono::serde` for date-times, and
+/// `rust_decimal::serde` for decimals so they stay JSON numbers, or
+/// [`DECIMAL_SEQ_MODULE`] for arrays of them
+fn serde_with_module(field: &Field) -> Option<String> {
+    let (field_type, optional) = match &field.field_type {
+        FieldType::Optional(inner) => (inner.as_ref(), true),
+        other => (other, field.optional),
+    };
+
+    let module = match field_type {
+        FieldType::DateTime(DateTimeFormat::EpochSeconds) => "chrono::serde::ts_seconds",
+        FieldType::DateTime(DateTimeFormat::EpochMillis) => "chrono::serde::ts_milliseconds",
+        FieldType::Decimal => "rust_decimal::serde::float",
+        FieldType::Array(element) if **element == FieldType::Decimal => {
+            let suffix = if optional { "::option" } else { "" };
+            return Some(format!("{}{}", DECIMAL_SEQ_MODULE, suffix));
+        }
+        _ => return None,
+    };
+    let suffix = if optional { "_option" } else { "" };
+    Some(format!("{}{}", module, suffix))
+}
+
+/// The `#[serde(with = ...)]` attribute for a field, see `serde_with_module`
+fn serde_with_attribute(field: &Field) -> Option<String> {
+    serde_with_module(field).map(|module| {
+        if module.ends_with("option") {
+            format!("#[serde(default, with = \"{}\")]", module)
+        } else {
+            format!("#[serde(with = \"{}\")]", module)
+        }
+    })
+}
+
+/// Render an error message as an owned `String` expression
+fn error_literal(message: &str) -> String {
+    format!("{:?}.to_string()", message)
+}
+
 impl CodeGenerator for RustGenerator {
     fn generate(&self, schema: &Schema) -> Result<String> {
         if schema.types.is_empty() {
@@ -132,12 +1106,69 @@ impl CodeGenerator for RustGenerator {
 
         // Add common imports
         output.push_str("use serde::{Deserialize, Serialize};\n");
+        if self.options.string_type == RustStringType::Cow {
+            output.push_str("use std::borrow::Cow;\n");
+        }
         output.push_str("use std::collections::HashMap;\n\n");
 
+        // Unions become untagged enums, one per distinct set of members
+        let mut enums: Vec<Vec<FieldType>> = Vec::new();
+        if self.options.union_repr == UnionRepr::Enum {
+            for type_def in &schema.types {
+                for field in &type_def.fields {
+                    collect_unions(&field.field_type, &mut enums);
+                }
+            }
+        }
+        *self.borrowed_types.borrow_mut() = self.find_borrowed_types(schema, &enums);
+
+        let string_enums = self.name_string_enums(schema);
+        *self.enum_names.borrow_mut() = string_enums
+            .iter()
+            .map(|(name, values)| (values.clone(), name.clone()))
+            .collect();
+        for (name, values) in &string_enums {
+            output.push_str(&self.generate_string_enum(name, values));
+            output.push('\n');
+        }
+
+        for members in &enums {
+            output.push_str(&self.generate_union_enum(schema, members));
+            output.push('\n');
+        }
+
         // Generate all type definitions (in reverse order so nested types come first)
+        let alias = root_alias(schema, &self.shared);
         for type_def in schema.types.iter().rev() {
-            output.push_str(&self.generate_struct(type_def));
+            if let Some(alias) = alias.as_ref().filter(|a| a.name == type_def.name) {
+                if let Some(doc) = alias.doc {
+                    output.push_str(&format!("/// {}\n", doc));
+                }
+                output.push_str(&format!(
+                    "pub type {} = {};\n\n",
+                    self.with_lifetime(alias.name),
+                    self.field_type_to_rust(alias.field_type, false)
+                ));
+                continue;
+            }
+            let newtype_field = self.newtype_field(type_def, &enums);
+            output.push_str(&self.generate_struct(type_def, newtype_field));
             output.push('\n');
+            if self.options.with_tryfrom {
+                let try_from = match newtype_field {
+                    Some(field) => self.generate_newtype_try_from(type_def, field),
+                    None => self.generate_try_from(type_def),
+                };
+                output.push_str(&try_from);
+                output.push('\n');
+            }
+        }
+
+        let uses_decimal_seq = schema.types.iter().flat_map(|t| &t.fields).any(|field| {
+            serde_with_module(field).is_some_and(|module| module.starts_with(DECIMAL_SEQ_MODULE))
+        });
+        if uses_decimal_seq {
+            output.push_str(&self.decimal_seq_module());
         }
 
         Ok(output.trim_end().to_string() + "\n")
@@ -156,6 +1187,30 @@ impl CodeGenerator for RustGenerator {
 mod tests {
     use super::*;
     use crate::ast::Field;
+    use crate::generators::GeneratorOptions;
+    use crate::generators::RenameAll;
+
+    #[test]
+    fn test_decimal_arrays_use_seq_module() {
+        let mut schema = Schema::new("Order");
+        let mut order = TypeDef::new("Order");
+        let decimals = || FieldType::Array(Box::new(FieldType::Decimal));
+        order.add_field(Field::new("prices", decimals()));
+        order.add_field(Field::new("refunds", decimals()).optional());
+        schema.add_type(order);
+
+        let generator = RustGenerator::new(RustOptions::default(), SharedOptions::default());
+        let output = generator.generate(&schema).unwrap();
+
+        assert!(output.contains(
+            "    #[serde(with = \"decimal_float_seq\")]\n    pub prices: Vec<rust_decimal::Decimal>,\n"
+        ));
+        assert!(output.contains(
+            "    #[serde(default, with = \"decimal_float_seq::option\")]\n    pub refunds: Option<Vec<rust_decimal::Decimal>>,\n"
+        ));
+        assert_eq!(output.matches("mod decimal_float_seq {").count(), 1);
+        assert!(output.contains("    pub mod option {\n"));
+    }
 
     #[test]
     fn test_generate_simple_struct() {
@@ -165,11 +1220,552 @@ mod tests {
         type_def.add_field(Field::new("age", FieldType::Integer));
         schema.add_type(type_def);
 
-        let generator = RustGenerator::new(GeneratorOptions::default());
+        let generator = RustGenerator::new(RustOptions::default(), SharedOptions::default());
         let output = generator.generate(&schema).unwrap();
 
         assert!(output.contains("pub struct User {"));
         assert!(output.contains("pub name: String,"));
         assert!(output.contains("pub age: i64,"));
     }
+
+    #[test]
+    fn test_acronym_field_names() {
+        let mut schema = Schema::new("Root");
+        let mut type_def = TypeDef::new("Root");
+        type_def.add_field(Field::new("userID", FieldType::Integer));
+        type_def.add_field(Field::new("HTMLParser", FieldType::String));
+        schema.add_type(type_def);
+
+        let output = RustGenerator::new(RustOptions::default(), SharedOptions::default())
+            .generate(&schema)
+            .unwrap();
+
+        assert!(output.contains("    #[serde(rename = \"userID\")]\n    pub user_id: i64,\n"));
+        assert!(output
+            .contains("    #[serde(rename = \"HTMLParser\")]\n    pub html_parser: String,\n"));
+    }
+
+    #[test]
+    fn test_generate_try_from_impl() {
+        let mut schema = Schema::new("User");
+        let mut type_def = TypeDef::new("User");
+        type_def.add_field(Field::new("name", FieldType::String));
+        type_def.add_field(Field::new(
+            "tags",
+            FieldType::Array(Box::new(FieldType::String)),
+        ));
+        type_def.add_field(Field::new("nickname", FieldType::String).optional());
+        schema.add_type(type_def);
+
+        let generator = RustGenerator::new(
+            RustOptions {
+                with_tryfrom: true,
+                ..Default::default()
+            },
+            SharedOptions::default(),
+        );
+        let output = generator.generate(&schema).unwrap();
+
+        assert!(output.contains("impl TryFrom<serde_json::Value> for User {"));
+        assert!(output.contains("\"User: missing field 'name'\""));
+        assert!(output.contains("\"User.tags[]: expected a string\""));
+        assert!(output.contains("None | Some(serde_json::Value::Null) => None,"));
+
+        let plain = RustGenerator::new(RustOptions::default(), SharedOptions::default())
+            .generate(&schema)
+            .unwrap();
+        assert!(!plain.contains("TryFrom"));
+    }
+
+    #[test]
+    fn test_integer_map_keys_parse_in_try_from() {
+        let mut schema = Schema::new("Root");
+        let mut root = TypeDef::new("Root");
+        root.add_field(Field::new(
+            "scores",
+            FieldType::Map(Box::new(FieldType::Integer), Box::new(FieldType::Float)),
+        ));
+        schema.add_type(root);
+
+        let output = RustGenerator::new(
+            RustOptions {
+                with_tryfrom: true,
+                ..Default::default()
+            },
+            SharedOptions::default(),
+        )
+        .generate(&schema)
+        .unwrap();
+
+        assert!(output.contains("pub scores: std::collections::HashMap<i64, f64>,"));
+        assert!(output.contains(
+            "Ok((key.parse::<i64>().map_err(|_| \"Root.scores: expected integer keys\".to_string())?, "
+        ));
+    }
+
+    #[test]
+    fn test_deny_unknown_skips_merged_types() {
+        let options = GeneratorOptions {
+            rust: RustOptions {
+                deny_unknown: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let schema = crate::parser::parse_json(
+            r#"{"owner": {"id": 1}, "items": [{"a": 1}, {"b": 2}]}"#,
+            &options,
+        )
+        .unwrap();
+        let output = RustGenerator::new(options.rust, options.shared)
+            .generate(&schema)
+            .unwrap();
+
+        assert!(output.contains("#[serde(deny_unknown_fields)]\npub struct Owner {"));
+        assert!(output.contains("#[serde(deny_unknown_fields)]\npub struct Root {"));
+        assert!(output.contains(")]\npub struct Items {"));
+    }
+
+    #[test]
+    fn test_newtype_wrappers_for_single_field_types() {
+        let options = GeneratorOptions {
+            rust: RustOptions {
+                newtype_wrappers: true,
+                with_tryfrom: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let schema = crate::parser::parse_json(
+            r#"{"id": {"value": 7}, "shape": [{"side": 1}, 2], "name": "x"}"#,
+            &options,
+        )
+        .unwrap();
+        let output = RustGenerator::new(options.rust, options.shared)
+            .generate(&schema)
+            .unwrap();
+
+        assert!(output.contains("#[serde(transparent)]\npub struct Id(pub i64);"));
+        assert!(output.contains("impl TryFrom<serde_json::Value> for Id {"));
+        // Enum variants keep their object shape
+        assert!(output.contains("pub struct Shape {\n    pub side: i64,\n}"));
+        assert!(output.contains("pub struct Root {"));
+    }
+
+    #[test]
+    fn test_union_repr_value_uses_json_value() {
+        let mut schema = Schema::new("Root");
+        let mut root = TypeDef::new("Root");
+        root.add_field(Field::new(
+            "id",
+            FieldType::Union(vec![FieldType::Integer, FieldType::String]),
+        ));
+        root.add_field(Field::new(
+            "score",
+            FieldType::Union(vec![FieldType::Float, FieldType::Null]),
+        ));
+        schema.add_type(root);
+
+        let generator = RustGenerator::new(
+            RustOptions {
+                union_repr: UnionRepr::Value,
+                ..Default::default()
+            },
+            SharedOptions::default(),
+        );
+        let output = generator.generate(&schema).unwrap();
+
+        assert!(output.contains("pub id: serde_json::Value,"));
+        // Single-member unions are not affected
+        assert!(output.contains("pub score: Option<f64>,"));
+        assert!(!output.contains("enum"));
+    }
+
+    #[test]
+    fn test_rust_array_type() {
+        let mut schema = Schema::new("Root");
+        let mut root = TypeDef::new("Root");
+        root.add_field(Field::new(
+            "tags",
+            FieldType::Array(Box::new(FieldType::String)),
+        ));
+        schema.add_type(root);
+        let render = |rust_array_type| {
+            RustGenerator::new(
+                RustOptions {
+                    array_type: rust_array_type,
+                    with_tryfrom: true,
+                    ..Default::default()
+                },
+                SharedOptions::default(),
+            )
+            .generate(&schema)
+            .unwrap()
+        };
+
+        assert!(render(RustArrayType::Vec).contains("pub tags: Vec<String>,"));
+        let boxed = render(RustArrayType::BoxedSlice);
+        assert!(boxed.contains("pub tags: Box<[String]>,"));
+        assert!(boxed.contains(".collect::<Result<Box<[_]>, String>>()"));
+        assert!(
+            render(RustArrayType::SmallVec).contains("pub tags: smallvec::SmallVec<[String; 4]>,")
+        );
+    }
+
+    #[test]
+    fn test_union_of_two_objects_becomes_untagged_enum() {
+        let mut schema = Schema::new("Root");
+        let mut root = TypeDef::new("Root");
+        root.add_field(Field::new(
+            "shape",
+            FieldType::Union(vec![
+                FieldType::Reference("Circle".to_string()),
+                FieldType::Reference("Square".to_string()),
+            ]),
+        ));
+        root.add_field(Field::new(
+            "shapes",
+            FieldType::Array(Box::new(FieldType::Union(vec![
+                FieldType::Reference("Circle".to_string()),
+                FieldType::Reference("Square".to_string()),
+                FieldType::Null,
+            ]))),
+        ));
+        schema.add_type(root);
+        let mut circle = TypeDef::new("Circle");
+        circle.add_field(Field::new("radius", FieldType::Float));
+        schema.add_type(circle);
+        let mut square = TypeDef::new("Square");
+        square.add_field(Field::new("side", FieldType::Float));
+        schema.add_type(square);
+
+        let generator = RustGenerator::new(RustOptions::default(), SharedOptions::default());
+        let output = generator.generate(&schema).unwrap();
+
+        assert!(output.contains(
+            "#[serde(untagged)]\npub enum CircleOrSquare {\n    Circle(Circle),\n    Square(Square),\n}"
+        ));
+        assert_eq!(output.matches("pub enum CircleOrSquare").count(), 1);
+        assert!(output.contains("pub shape: CircleOrSquare,"));
+        assert!(output.contains("pub shapes: Vec<Option<CircleOrSquare>>,"));
+    }
+
+    #[test]
+    fn test_union_variants_try_integer_before_float() {
+        let mut schema = Schema::new("Root");
+        let mut root = TypeDef::new("Root");
+        root.add_field(Field::new(
+            "value",
+            FieldType::Union(vec![
+                FieldType::Float,
+                FieldType::Integer,
+                FieldType::String,
+            ]),
+        ));
+        schema.add_type(root);
+
+        let generator = RustGenerator::new(RustOptions::default(), SharedOptions::default());
+        let output = generator.generate(&schema).unwrap();
+
+        assert!(output.contains(
+            "pub enum IntegerOrFloatOrString {\n    Integer(i64),\n    Float(f64),\n    String(String),\n}"
+        ));
+    }
+
+    #[test]
+    fn test_epoch_datetime_fields_use_chrono() {
+        let mut schema = Schema::new("Event");
+        let mut type_def = TypeDef::new("Event");
+        type_def.add_field(Field::new(
+            "created_at",
+            FieldType::DateTime(DateTimeFormat::EpochSeconds),
+        ));
+        type_def.add_field(
+            Field::new(
+                "deletedAt",
+                FieldType::DateTime(DateTimeFormat::EpochMillis),
+            )
+            .optional(),
+        );
+        schema.add_type(type_def);
+
+        let generator = RustGenerator::new(RustOptions::default(), SharedOptions::default());
+        let output = generator.generate(&schema).unwrap();
+
+        assert!(output.contains(
+            "    #[serde(with = \"chrono::serde::ts_seconds\")]\n    pub created_at: chrono::DateTime<chrono::Utc>,"
+        ));
+        assert!(
+            output.contains("#[serde(default, with = \"chrono::serde::ts_milliseconds_option\")]")
+        );
+        assert!(output.contains("Option<chrono::DateTime<chrono::Utc>>"));
+    }
+
+    #[test]
+    fn test_rename_all_and_per_field_renames() {
+        let mut schema = Schema::new("User");
+        let mut type_def = TypeDef::new("User");
+        type_def.add_field(Field::new("userName", FieldType::String));
+        type_def.add_field(Field::new("id", FieldType::Integer));
+        type_def.add_field(Field::new("created_at", FieldType::String));
+        schema.add_type(type_def);
+
+        // Without rename_all, keys that snake_case changes are renamed
+        let output = RustGenerator::new(RustOptions::default(), SharedOptions::default())
+            .generate(&schema)
+            .unwrap();
+        assert!(output.contains("    #[serde(rename = \"userName\")]\n    pub user_name: String,"));
+        assert!(!output.contains("rename = \"created_at\""));
+
+        let generator = RustGenerator::new(
+            RustOptions::default(),
+            SharedOptions {
+                rename_all: Some(RenameAll::CamelCase),
+                ..Default::default()
+            },
+        );
+        let output = generator.generate(&schema).unwrap();
+        assert!(output.contains("#[serde(rename_all = \"camelCase\")]\npub struct User {"));
+        assert!(!output.contains("rename = \"userName\""));
+        assert!(
+            output.contains("    #[serde(rename = \"created_at\")]\n    pub created_at: String,")
+        );
+        assert_eq!(
+            generator.rename_coverage(&schema),
+            RenameCoverage {
+                covered: 2,
+                renamed: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_rename_all_applies_to_nested_types() {
+        let options = GeneratorOptions {
+            shared: SharedOptions {
+                rename_all: Some(RenameAll::CamelCase),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let schema = crate::parser::parse_json(
+            r#"{"userId": 1, "owner": {"displayName": "a", "created_at": "x"}, "items": [{"unitPrice": 1.5}]}"#,
+            &options,
+        )
+        .unwrap();
+        let generator = RustGenerator::new(options.rust, options.shared);
+        let output = generator.generate(&schema).unwrap();
+
+        for name in ["Root", "Owner", "Items"] {
+            assert!(
+                output.contains(&format!(
+                    "#[serde(rename_all = \"camelCase\")]\npub struct {} {{",
+                    name
+                )),
+                "{} lacks rename_all",
+                name
+            );
+        }
+        assert!(output.contains("    pub display_name: String,\n"));
+        assert!(output.contains("    pub unit_price: f64,\n"));
+        // A nested key that breaks the convention keeps its own rename
+        assert!(
+            output.contains("    #[serde(rename = \"created_at\")]\n    pub created_at: String,")
+        );
+        assert_eq!(
+            generator.rename_coverage(&schema),
+            RenameCoverage {
+                covered: 5,
+                renamed: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_cow_string_type_propagates_lifetime() {
+        let options = GeneratorOptions::default();
+        let schema = crate::parser::parse_json(
+            r#"{"id": 1, "owner": {"login": "a", "n": {"s": "z"}}, "meta": {"k": 1}, "mixed": [1, "a"]}"#,
+            &options,
+        )
+        .unwrap();
+        let output = RustGenerator::new(
+            RustOptions {
+                string_type: RustStringType::Cow,
+                ..Default::default()
+            },
+            SharedOptions::default(),
+        )
+        .generate(&schema)
+        .unwrap();
+
+        assert!(output.contains("use std::borrow::Cow;"));
+        assert!(output.contains(
+            "pub struct Owner<'a> {\n    #[serde(borrow)]\n    pub login: Cow<'a, str>,\n    #[serde(borrow)]\n    pub n: N<'a>,\n}"
+        ));
+        assert!(output.contains("    String(#[serde(borrow)] Cow<'a, str>),"));
+        assert!(output.contains("pub struct Root<'a> {"));
+        // Types without strings keep no lifetime
+        assert!(output.contains("pub struct Meta {\n    pub k: i64,\n}"));
+    }
+
+    #[test]
+    fn test_serde_borrow_only_on_borrowed_fields() {
+        let schema = crate::parser::parse_json(
+            r#"{"id": 1, "name": "a", "tags": ["x"], "meta": {"k": 1}, "items": [{"n": "q"}]}"#,
+            &GeneratorOptions::default(),
+        )
+        .unwrap();
+        let generate = |string_type| {
+            RustGenerator::new(
+                RustOptions {
+                    string_type,
+                    ..Default::default()
+                },
+                SharedOptions::default(),
+            )
+            .generate(&schema)
+            .unwrap()
+        };
+
+        let output = generate(RustStringType::Cow);
+        assert!(output.contains(
+            "pub struct Root<'a> {
+    pub id: i64,
+    #[serde(borrow)]
+    pub name: Cow<'a, str>,
+    #[serde(borrow)]
+    pub tags: Vec<Cow<'a, str>>,
+    pub meta: Meta,
+    #[serde(borrow)]
+    pub items: Vec<Items<'a>>,
+}"
+        ));
+        assert_eq!(output.matches("#[serde(borrow)]").count(), 4);
+
+        assert!(!generate(RustStringType::String).contains("#[serde(borrow)]"));
+    }
+
+    #[test]
+    fn test_enum_catchall_adds_raw_value_variant() {
+        let options = GeneratorOptions {
+            shared: SharedOptions {
+                enum_catchall: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let schema = crate::parser::parse_json(r#"{"mixed": [1, "a"]}"#, &options).unwrap();
+        let output = RustGenerator::new(options.rust, options.shared)
+            .generate(&schema)
+            .unwrap();
+
+        assert!(output.contains(
+            "pub enum IntegerOrString {\n    Integer(i64),\n    String(String),\n    Other(serde_json::Value),\n}"
+        ));
+    }
+
+    #[test]
+    fn test_string_enums_become_unit_enums() {
+        let json = r#"[{"status": "in-progress", "kind": "A"}, {"status": "done", "kind": "A"}, {"status": "done", "kind": "b"}]"#;
+        let options = GeneratorOptions::default();
+        let schema = crate::parser::parse_json(json, &options).unwrap();
+        let output = RustGenerator::new(options.rust, options.shared)
+            .generate(&schema)
+            .unwrap();
+
+        assert!(output.contains(
+            "pub enum Status {\n    #[serde(rename = \"in-progress\")]\n    InProgress,\n    #[serde(rename = \"done\")]\n    Done,\n}"
+        ));
+        assert!(output.contains("pub enum Kind {\n    A,\n    #[serde(rename = \"b\")]\n    B,\n}"));
+        assert!(output.contains("    pub status: Status,\n"));
+
+        let options = GeneratorOptions {
+            shared: SharedOptions {
+                enum_catchall: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let output = RustGenerator::new(options.rust, options.shared)
+            .generate(&schema)
+            .unwrap();
+        assert!(output.contains("    B,\n    #[serde(other)]\n    Unknown,\n}"));
+    }
+
+    #[test]
+    fn test_flatten_turns_root_wrapper_into_alias() {
+        let options = GeneratorOptions {
+            shared: SharedOptions {
+                flatten: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut schema = crate::parser::parse_json(r#"["a", "b"]"#, &options).unwrap();
+        schema.types[0].doc = Some("Tag names".to_string());
+        let output = RustGenerator::new(options.rust.clone(), options.shared.clone())
+            .generate(&schema)
+            .unwrap();
+
+        assert!(output.ends_with("/// Tag names\npub type Root = Vec<String>;\n"));
+        assert!(!output.contains("struct Root"));
+
+        // A root object with one field keeps it, since the key is in the data
+        let schema = crate::parser::parse_json(r#"{"items": ["a"]}"#, &options).unwrap();
+        let output = RustGenerator::new(options.rust, options.shared)
+            .generate(&schema)
+            .unwrap();
+        assert!(output.contains("pub struct Root {\n    pub items: Vec<String>,\n}"));
+    }
+
+    #[test]
+    fn test_flatten_with_item_as_root_adds_list_alias() {
+        let options = GeneratorOptions {
+            shared: SharedOptions {
+                flatten: true,
+                ..Default::default()
+            },
+            root_name: "User".to_string(),
+            item_as_root: true,
+            ..Default::default()
+        };
+        let schema = crate::parser::parse_json(r#"[{"id": 1}, {"id": 2}]"#, &options).unwrap();
+        let output = RustGenerator::new(options.rust.clone(), options.shared.clone())
+            .generate(&schema)
+            .unwrap();
+
+        assert!(output.contains("pub struct User {\n    pub id: i64,\n}"));
+        assert!(output.contains("pub type UserList = Vec<User>;\n"));
+
+        // Primitive elements have no type to be the root, so the root is the alias
+        let schema = crate::parser::parse_json("[1, 2]", &options).unwrap();
+        let output = RustGenerator::new(options.rust, options.shared)
+            .generate(&schema)
+            .unwrap();
+        assert!(output.ends_with("pub type User = Vec<i64>;\n"));
+    }
+
+    #[test]
+    fn test_tagged_union_becomes_internally_tagged_enum() {
+        let options = GeneratorOptions {
+            tagged_union: Some("kind".to_string()),
+            ..Default::default()
+        };
+        let schema = crate::parser::parse_json(
+            r#"[{"kind": "circle", "radius": 1.5}, {"kind": "square", "side": 2.0}]"#,
+            &options,
+        )
+        .unwrap();
+        let output = RustGenerator::new(options.rust, options.shared)
+            .generate(&schema)
+            .unwrap();
+
+        assert!(output.contains(
+            "#[serde(tag = \"kind\")]\npub enum CircleOrSquare {\n    #[serde(rename = \"circle\")]\n    Circle(Circle),\n    #[serde(rename = \"square\")]\n    Square(Square),\n}"
+        ));
+        // serde reads the tag, so the variant structs leave it out
+        assert!(output.contains("pub struct Circle {\n    pub radius: f64,\n}"));
+        assert!(!output.contains("pub enum Kind"));
+    }
 }
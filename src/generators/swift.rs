@@ -0,0 +1,341 @@
+//! Swift Codable struct generator
+
+use crate::ast::{FieldType, Schema, TypeDef};
+use crate::error::{AlchemistError, Result};
+use crate::generators::{
+    recursive_types, split_nullable_union, CodeGenerator, Indent, SharedOptions,
+};
+use crate::utils::{to_pascal_case, to_snake_case};
+use std::collections::HashSet;
+
+/// Swift keywords, which need backticks as property names
+const SWIFT_KEYWORDS: &[&str] = &[
+    "as",
+    "associatedtype",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "continue",
+    "default",
+    "defer",
+    "deinit",
+    "do",
+    "else",
+    "enum",
+    "extension",
+    "fallthrough",
+    "false",
+    "fileprivate",
+    "for",
+    "func",
+    "guard",
+    "if",
+    "import",
+    "in",
+    "init",
+    "inout",
+    "internal",
+    "is",
+    "let",
+    "nil",
+    "operator",
+    "private",
+    "protocol",
+    "public",
+    "repeat",
+    "rethrows",
+    "return",
+    "self",
+    "static",
+    "struct",
+    "subscript",
+    "super",
+    "switch",
+    "throw",
+    "throws",
+    "true",
+    "try",
+    "typealias",
+    "var",
+    "where",
+    "while",
+];
+
+/// Swift struct generator for `Codable`
+///
+/// Properties are camelCase, with a `CodingKeys` enum mapping them back to
+/// the original keys wherever the two differ. Types that contain themselves
+/// become `final class`es, since a struct cannot. Values of no single type
+/// use `AnyCodable`, from the package of the same name.
+pub struct SwiftGenerator {
+    shared: SharedOptions,
+    /// Whitespace of one indentation level
+    indent: String,
+}
+
+impl SwiftGenerator {
+    /// Create a new Swift generator with the shared options
+    pub fn new(shared: SharedOptions) -> Self {
+        let indent = shared.indent_unit(Indent::Spaces(4));
+        Self { shared, indent }
+    }
+
+    /// Generate a Swift struct, or class if it is recursive, from a type
+    /// definition
+    fn generate_struct(&self, type_def: &TypeDef, recursive: &HashSet<&str>) -> String {
+        let indent = &self.indent;
+        let mut output = String::new();
+
+        if let Some(doc) = &type_def.doc {
+            output.push_str(&format!("/// {}\n", doc));
+        }
+        let kind = if recursive.contains(type_def.name.as_str()) {
+            "final class"
+        } else {
+            "struct"
+        };
+        output.push_str(&format!("{} {}: Codable {{\n", kind, type_def.name));
+
+        let mut used = HashSet::new();
+        let mut keys = Vec::new();
+        for field in &type_def.fields {
+            if let Some(doc) = &field.doc {
+                output.push_str(&format!("{}/// {}\n", indent, doc));
+            }
+
+            let name = unique_name(property_name(&field.name), &mut used);
+            let mut swift_type = self.field_type_to_swift(&field.field_type);
+            if (field.optional || self.shared.optional_fields) && !swift_type.ends_with('?') {
+                swift_type.push('?');
+            }
+            output.push_str(&format!("{}let {}: {}\n", indent, name, swift_type));
+            keys.push((name, &field.name));
+        }
+
+        // Coding keys must list every property once any of them is renamed
+        if keys
+            .iter()
+            .any(|(name, key)| name.trim_matches('`') != *key)
+        {
+            output.push_str(&format!(
+                "\n{}enum CodingKeys: String, CodingKey {{\n",
+                indent
+            ));
+            let case_indent = indent.repeat(2);
+            for (name, key) in &keys {
+                if name.trim_matches('`') == *key {
+                    output.push_str(&format!("{}case {}\n", case_indent, name));
+                } else {
+                    output.push_str(&format!(
+                        "{}case {} = {}\n",
+                        case_indent,
+                        name,
+                        swift_string(key)
+                    ));
+                }
+            }
+            output.push_str(&format!("{}}}\n", indent));
+        }
+
+        output.push_str("}\n");
+        output
+    }
+
+    /// Convert AST FieldType to Swift type string
+    fn field_type_to_swift(&self, field_type: &FieldType) -> String {
+        match field_type {
+            FieldType::String | FieldType::Enum(_) => "String".to_string(),
+            // Date decoding depends on the decoder's strategy, so ISO dates stay strings
+            FieldType::DateTime(format) if !format.is_epoch() => "String".to_string(),
+            FieldType::Integer | FieldType::DateTime(_) => "Int".to_string(),
+            FieldType::Float => "Double".to_string(),
+            FieldType::Decimal => "Decimal".to_string(),
+            FieldType::Uuid => "UUID".to_string(),
+            FieldType::Boolean => "Bool".to_string(),
+            FieldType::Array(inner) => format!("[{}]", self.field_type_to_swift(inner)),
+            FieldType::Set(inner) => format!("Set<{}>", self.field_type_to_swift(inner)),
+            FieldType::Optional(inner) => optional(self.field_type_to_swift(inner)),
+            FieldType::Reference(name) => name.clone(),
+            FieldType::Union(types) => match split_nullable_union(types) {
+                (members, nullable) if members.len() == 1 => {
+                    let member = self.field_type_to_swift(members[0]);
+                    if nullable {
+                        optional(member)
+                    } else {
+                        member
+                    }
+                }
+                (_, true) => "AnyCodable?".to_string(),
+                _ => "AnyCodable".to_string(),
+            },
+            // JSONDecoder only reads objects into dictionaries with String keys
+            FieldType::Map(_, value) => format!("[String: {}]", self.field_type_to_swift(value)),
+            FieldType::Null | FieldType::Any => "AnyCodable".to_string(),
+        }
+    }
+}
+
+/// Make a Swift type optional, once
+fn optional(swift_type: String) -> String {
+    if swift_type.ends_with('?') {
+        swift_type
+    } else {
+        swift_type + "?"
+    }
+}
+
+/// Get the camelCase property name of a key, in backticks when it is a
+/// keyword
+fn property_name(key: &str) -> String {
+    let words: String = key
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let pascal = to_pascal_case(&to_snake_case(&words));
+    let mut chars = pascal.chars();
+    let mut name: String = match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => "field".to_string(),
+    };
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+
+    if SWIFT_KEYWORDS.contains(&name.as_str()) {
+        format!("`{}`", name)
+    } else {
+        name
+    }
+}
+
+/// Suffix a name with a number until no earlier property uses it
+fn unique_name(name: String, used: &mut HashSet<String>) -> String {
+    let mut candidate = name.clone();
+    let mut counter = 1;
+    while !used.insert(candidate.clone()) {
+        counter += 1;
+        candidate = format!("{}{}", name, counter);
+    }
+    candidate
+}
+
+/// Quote text as a Swift string, escaping quotes, backslashes and control
+/// characters
+fn swift_string(text: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{{{:X}}}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+impl CodeGenerator for SwiftGenerator {
+    fn generate(&self, schema: &Schema) -> Result<String> {
+        if schema.types.is_empty() {
+            return Err(AlchemistError::GenerationError(
+                "Schema has no types to generate".to_string(),
+            ));
+        }
+
+        let recursive = recursive_types(schema);
+        let structs: Vec<String> = schema
+            .types
+            .iter()
+            .map(|type_def| self.generate_struct(type_def, &recursive))
+            .collect();
+
+        let mut output = String::new();
+        output.push_str("// Generated by Alchemist\n");
+        output.push_str("// Do not edit manually\n\n");
+        output.push_str("import Foundation\n");
+        if structs.iter().any(|s| s.contains("AnyCodable")) {
+            output.push_str("import AnyCodable\n");
+        }
+        output.push('\n');
+        output.push_str(&structs.join("\n"));
+
+        Ok(output)
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "swift"
+    }
+
+    fn name(&self) -> &'static str {
+        "Swift"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Field;
+    use crate::generators::GeneratorOptions;
+    use crate::parser::parse_json;
+
+    fn generate_from_json(json: &str) -> String {
+        let options = GeneratorOptions::default();
+        let schema = parse_json(json, &options).unwrap();
+        SwiftGenerator::new(options.shared)
+            .generate(&schema)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_generate_codable_structs() {
+        let output = generate_from_json(
+            r#"{"id": 1, "ratio": 0.5, "tags": ["a"], "scores": [1, null], "owner": {"first-name": "x", "class": 1, "type": 2}, "items": [{"a": 1}, {"b": "x"}], "extra": null}"#,
+        );
+
+        assert!(output.starts_with(
+            "// Generated by Alchemist\n// Do not edit manually\n\nimport Foundation\nimport AnyCodable\n\n"
+        ));
+        assert!(output.contains(
+            "struct Root: Codable {\n    let id: Int\n    let ratio: Double\n    let tags: [String]\n    let scores: [Int?]\n    let owner: Owner\n"
+        ));
+        assert!(output.contains("    let extra: AnyCodable?\n}"));
+        assert!(output.contains(
+            "struct Owner: Codable {\n    let firstName: String\n    let `class`: Int\n    let type: Int\n\n    enum CodingKeys: String, CodingKey {\n        case firstName = \"first-name\"\n        case `class`\n        case type\n    }\n}"
+        ));
+        assert!(output.contains("    let a: Int?\n    let b: String?\n"));
+    }
+
+    #[test]
+    fn test_coding_keys_are_escaped() {
+        let output = generate_from_json(r#"{"a\"b": 1, "c\\d": 2}"#);
+
+        assert!(output.contains("        case aB = \"a\\\"b\"\n"));
+        assert!(output.contains("        case cD = \"c\\\\d\"\n"));
+        assert_eq!(swift_string("\u{1}"), "\"\\u{1}\"");
+    }
+
+    #[test]
+    fn test_maps_and_recursive_types() {
+        let mut schema = Schema::new("Node");
+        let mut node = TypeDef::new("Node");
+        node.add_field(Field::new("next", FieldType::Reference("Node".to_string())).optional());
+        node.add_field(Field::new(
+            "attrs",
+            FieldType::Map(Box::new(FieldType::Integer), Box::new(FieldType::Decimal)),
+        ));
+        schema.add_type(node);
+        let output = SwiftGenerator::new(SharedOptions::default())
+            .generate(&schema)
+            .unwrap();
+
+        assert!(!output.contains("import AnyCodable"));
+        assert!(output.contains(
+            "final class Node: Codable {\n    let next: Node?\n    let attrs: [String: Decimal]\n}"
+        ));
+    }
+}
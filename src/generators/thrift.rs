@@ -0,0 +1,226 @@
+//! Apache Thrift IDL generator
+
+use crate::ast::{Field, FieldType, Schema, TypeDef};
+use crate::error::{AlchemistError, Result};
+use crate::generators::{split_nullable_union, CodeGenerator, Indent, SharedOptions};
+
+/// Thrift keywords that cannot be used as field names
+const THRIFT_KEYWORDS: &[&str] = &[
+    "binary",
+    "bool",
+    "byte",
+    "const",
+    "double",
+    "enum",
+    "exception",
+    "extends",
+    "false",
+    "i16",
+    "i32",
+    "i64",
+    "i8",
+    "include",
+    "list",
+    "map",
+    "namespace",
+    "oneway",
+    "optional",
+    "required",
+    "service",
+    "set",
+    "string",
+    "struct",
+    "throws",
+    "true",
+    "typedef",
+    "union",
+    "void",
+];
+
+/// Thrift IDL generator
+///
+/// Fields are numbered from 1 in the order they appear in the type. Values
+/// Thrift cannot type, such as `Any` or unions of several types, become
+/// `string` fields holding their JSON text.
+pub struct ThriftGenerator {
+    shared: SharedOptions,
+    /// Whitespace of one indentation level
+    indent: String,
+}
+
+impl ThriftGenerator {
+    /// Create a new Thrift generator with the shared options
+    pub fn new(shared: SharedOptions) -> Self {
+        let indent = shared.indent_unit(Indent::Spaces(2));
+        Self { shared, indent }
+    }
+
+    /// Generate a Thrift struct from a type definition
+    fn generate_struct(&self, type_def: &TypeDef) -> String {
+        let mut output = String::new();
+
+        if let Some(doc) = &type_def.doc {
+            output.push_str(&format!("/** {} */\n", doc));
+        }
+        output.push_str(&format!("struct {} {{\n", type_def.name));
+
+        for (index, field) in type_def.fields.iter().enumerate() {
+            if let Some(doc) = &field.doc {
+                output.push_str(&format!("{}/** {} */\n", self.indent, doc));
+            }
+            let (thrift_type, nullable) = self.field_type_to_thrift(&field.field_type);
+            let requiredness = if nullable || self.is_optional(field) {
+                "optional"
+            } else {
+                "required"
+            };
+            output.push_str(&format!(
+                "{}{}: {} {} {},\n",
+                self.indent,
+                index + 1,
+                requiredness,
+                thrift_type,
+                field_name(field)
+            ));
+        }
+
+        output.push_str("}\n");
+        output
+    }
+
+    /// Whether a field may be missing from the data
+    fn is_optional(&self, field: &Field) -> bool {
+        field.optional || self.shared.optional_fields
+    }
+
+    /// Convert AST FieldType to a Thrift type and whether it may be `null`
+    ///
+    /// Thrift has no null values, so nullable fields are made `optional`.
+    fn field_type_to_thrift(&self, field_type: &FieldType) -> (String, bool) {
+        let thrift_type = match field_type {
+            FieldType::String | FieldType::Enum(_) | FieldType::Uuid | FieldType::Any => {
+                "string".to_string()
+            }
+            FieldType::DateTime(format) if !format.is_epoch() => "string".to_string(),
+            FieldType::Integer | FieldType::DateTime(_) => "i64".to_string(),
+            FieldType::Float | FieldType::Decimal => "double".to_string(),
+            FieldType::Boolean => "bool".to_string(),
+            FieldType::Null => return ("string".to_string(), true),
+            FieldType::Array(inner) => format!("list<{}>", self.element_type(inner)),
+            FieldType::Set(inner) => format!("set<{}>", self.element_type(inner)),
+            FieldType::Optional(inner) => return (self.field_type_to_thrift(inner).0, true),
+            FieldType::Reference(name) => name.clone(),
+            FieldType::Union(types) => match split_nullable_union(types) {
+                (members, nullable) if members.len() == 1 => {
+                    return (self.field_type_to_thrift(members[0]).0, nullable)
+                }
+                (_, nullable) => return ("string".to_string(), nullable),
+            },
+            FieldType::Map(key, value) => format!(
+                "map<{}, {}>",
+                self.element_type(key),
+                self.element_type(value)
+            ),
+        };
+        (thrift_type, false)
+    }
+
+    /// Thrift type of a collection element, which cannot be marked nullable
+    fn element_type(&self, field_type: &FieldType) -> String {
+        self.field_type_to_thrift(field_type).0
+    }
+}
+
+/// Get the field name from the JSON key, made a valid Thrift identifier
+///
+/// Characters other than letters, digits and `_` become `_`, a leading digit
+/// gets a `_` prefix and Thrift keywords get a trailing `_`.
+fn field_name(field: &Field) -> String {
+    let mut name: String = field
+        .name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    if THRIFT_KEYWORDS.contains(&name.as_str()) {
+        name.push('_');
+    }
+    name
+}
+
+impl CodeGenerator for ThriftGenerator {
+    fn generate(&self, schema: &Schema) -> Result<String> {
+        if schema.types.is_empty() {
+            return Err(AlchemistError::GenerationError(
+                "Schema has no types to generate".to_string(),
+            ));
+        }
+
+        let mut output = String::new();
+
+        output.push_str("// Generated by Alchemist\n");
+        output.push_str("// Do not edit manually\n\n");
+
+        // Structs must be defined before the structs that use them
+        let structs: Vec<String> = schema
+            .iter_topological(false)
+            .map(|type_def| self.generate_struct(type_def))
+            .collect();
+        output.push_str(&structs.join("\n"));
+
+        Ok(output)
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "thrift"
+    }
+
+    fn name(&self) -> &'static str {
+        "Thrift"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::GeneratorOptions;
+    use crate::parser::parse_json;
+
+    fn generate(json: &str) -> String {
+        let options = GeneratorOptions::default();
+        let schema = parse_json(json, &options).unwrap();
+        ThriftGenerator::new(options.shared)
+            .generate(&schema)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_generate_structs() {
+        let output = generate(
+            r#"{"id": 1, "ratio": 0.5, "tags": ["a"], "scores": [1, null], "owner": {"list": true}, "counts": {"a": 1}, "note": null, "users": [{"a": 1}, {"b": "x"}]}"#,
+        );
+
+        assert!(output.contains(
+            "struct Root {\n  1: required i64 id,\n  2: required double ratio,\n  3: required list<string> tags,\n  4: required list<i64> scores,\n  5: required Owner owner,\n"
+        ));
+        assert!(output.contains("  7: optional string note,\n"));
+        assert!(output.contains("  8: required list<Users> users,\n"));
+        assert!(output.contains("struct Owner {\n  1: required bool list_,\n}\n"));
+        assert!(
+            output.contains("struct Users {\n  1: optional i64 a,\n  2: optional string b,\n}\n")
+        );
+        // Referenced structs are defined first
+        assert!(output.find("struct Users").unwrap() < output.find("struct Root").unwrap());
+    }
+
+    #[test]
+    fn test_field_names_are_thrift_identifiers() {
+        let output = generate(r#"{"type": 1, "fn": 2, "first-name": "a", "2x": 3, "map": 4}"#);
+
+        assert!(output.contains(
+            "struct Root {\n  1: required i64 type,\n  2: required i64 fn,\n  3: required string first_name,\n  4: required i64 _2x,\n  5: required i64 map_,\n}\n"
+        ));
+    }
+}
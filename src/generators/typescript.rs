@@ -1,22 +1,77 @@
 //! TypeScript interface generator
 
-use crate::ast::{FieldType, Schema, TypeDef};
+use crate::ast::{Field, FieldType, Schema, TypeDef};
 use crate::error::{AlchemistError, Result};
-use crate::generators::{CodeGenerator, GeneratorOptions};
+use crate::generators::{root_alias, CodeGenerator, Indent, RootAlias, SharedOptions};
+use clap::ValueEnum;
+use serde::Deserialize;
+
+/// Declaration style emitted by the TypeScript generator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TsStyle {
+    /// `export interface` declarations
+    #[default]
+    Interface,
+    /// JSDoc `@typedef` comment blocks for type-checked JavaScript
+    Jsdoc,
+    /// Ambient `declare` declarations for a types-only `.d.ts` file
+    Dts,
+}
+
+/// TypeScript representation of decimal fields (also used by Zod)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TsDecimal {
+    /// Plain `number`, matching how the values appear in the data
+    #[default]
+    Number,
+    /// `string`, for APIs that serialize decimals as strings to keep precision
+    String,
+}
+
+/// Options of the TypeScript generator, partly shared with Zod
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TsOptions {
+    /// Declaration style
+    #[serde(rename = "ts_style")]
+    pub style: TsStyle,
+    /// Namespace wrapping ambient declarations in the `dts` style
+    pub namespace: Option<String>,
+    /// Representation of decimal fields (also used by Zod)
+    #[serde(rename = "ts_decimal")]
+    pub decimal: TsDecimal,
+    /// Whether nullable `unknown` collapses to `unknown`, with a `?` marker on fields
+    pub collapse_optional_any: bool,
+}
 
 /// TypeScript interface generator
+///
+/// Properties are named by their JSON keys, so the types match the data
+/// as it is parsed. Keys that aren't identifiers, like `my-key`, are
+/// written as quoted strings rather than renamed.
 pub struct TypeScriptGenerator {
-    options: GeneratorOptions,
+    options: TsOptions,
+    shared: SharedOptions,
+    /// Whitespace of one indentation level
+    indent: String,
 }
 
 impl TypeScriptGenerator {
-    /// Create a new TypeScript generator with the given options
-    pub fn new(options: GeneratorOptions) -> Self {
-        Self { options }
+    /// Create a new TypeScript generator with its own and the shared options
+    pub fn new(options: TsOptions, shared: SharedOptions) -> Self {
+        let indent = shared.indent_unit(Indent::Spaces(2));
+        Self {
+            options,
+            shared,
+            indent,
+        }
     }
 
-    /// Generate a TypeScript interface from a type definition
-    fn generate_interface(&self, type_def: &TypeDef) -> String {
+    /// Generate a TypeScript interface from a type definition, declared with
+    /// `keyword`, e.g. `export interface`
+    fn generate_interface(&self, type_def: &TypeDef, keyword: &str) -> String {
         let mut output = String::new();
 
         // Add documentation comment if present
@@ -25,31 +80,29 @@ impl TypeScriptGenerator {
         }
 
         // Interface declaration
-        output.push_str(&format!("export interface {} {{\n", type_def.name));
+        output.push_str(&format!("{} {} {{\n", keyword, type_def.name));
 
         // Generate fields
         for field in &type_def.fields {
+            let key = self.shared.renamed_key(&field.name);
+
             // Add field documentation if present
-            if let Some(doc) = &field.doc {
-                output.push_str(&format!("  /** {} */\n", doc));
+            if let Some(doc) = field_doc(field, &key) {
+                output.push_str(&format!("{}/** {} */\n", self.indent, doc));
             }
 
-            let readonly = if self.options.readonly {
+            let readonly = if self.shared.readonly {
                 "readonly "
             } else {
                 ""
             };
-            let optional = if field.optional || self.options.optional_fields {
-                "?"
-            } else {
-                ""
-            };
-            let field_name = field.code_name();
-            let ts_type = self.field_type_to_typescript(&field.field_type);
+            let optional = if self.is_optional(field) { "?" } else { "" };
+            let field_name = property_name(&key);
+            let ts_type = self.property_type(type_def, field);
 
             output.push_str(&format!(
-                "  {}{}{}: {};\n",
-                readonly, field_name, optional, ts_type
+                "{}{}{}{}: {};\n",
+                self.indent, readonly, field_name, optional, ts_type
             ));
         }
 
@@ -57,25 +110,131 @@ impl TypeScriptGenerator {
         output
     }
 
+    /// Generate a JSDoc `@typedef` block from a type definition
+    fn generate_typedef(&self, type_def: &TypeDef) -> String {
+        let mut output = String::from("/**\n");
+
+        if let Some(doc) = &type_def.doc {
+            output.push_str(&format!(" * {}\n", doc));
+        }
+
+        output.push_str(&format!(" * @typedef {{Object}} {}\n", type_def.name));
+
+        for field in &type_def.fields {
+            let ts_type = self.property_type(type_def, field);
+            let key = self.shared.renamed_key(&field.name);
+            // Quoted like interface properties when not an identifier
+            let name = if self.is_optional(field) {
+                format!("[{}]", property_name(&key))
+            } else {
+                property_name(&key)
+            };
+
+            match field_doc(field, &key) {
+                Some(doc) => output.push_str(&format!(
+                    " * @property {{{}}} {} - {}\n",
+                    ts_type, name, doc
+                )),
+                None => output.push_str(&format!(" * @property {{{}}} {}\n", ts_type, name)),
+            }
+        }
+
+        output.push_str(" */\n");
+        output
+    }
+
+    /// Generate a type alias for a flattened root, see [`root_alias`]
+    fn generate_alias(&self, alias: &RootAlias, keyword: &str) -> String {
+        let mut output = String::new();
+        if let Some(doc) = alias.doc {
+            output.push_str(&format!("/**\n * {}\n */\n", doc));
+        }
+        output.push_str(&format!(
+            "{} {} = {};\n",
+            keyword,
+            alias.name,
+            self.field_type_to_typescript(alias.field_type)
+        ));
+        output
+    }
+
+    /// Generate a JSDoc `@typedef` for a flattened root, see [`root_alias`]
+    fn generate_alias_typedef(&self, alias: &RootAlias) -> String {
+        let mut output = String::from("/**\n");
+        if let Some(doc) = alias.doc {
+            output.push_str(&format!(" * {}\n", doc));
+        }
+        output.push_str(&format!(
+            " * @typedef {{{}}} {}\n */\n",
+            self.field_type_to_typescript(alias.field_type),
+            alias.name
+        ));
+        output
+    }
+
+    /// TypeScript type of a property
+    ///
+    /// The tag of a tagged union variant is its bare literal, even with
+    /// `--enum-catchall`, so the union stays discriminated.
+    fn property_type(&self, type_def: &TypeDef, field: &Field) -> String {
+        match &field.field_type {
+            FieldType::Enum(values) if type_def.tag.as_deref() == Some(field.name.as_str()) => {
+                values
+                    .iter()
+                    .map(|value| format!("\"{}\"", value))
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            }
+            field_type => self.field_type_to_typescript(field_type),
+        }
+    }
+
     /// Convert AST FieldType to TypeScript type string
     fn field_type_to_typescript(&self, field_type: &FieldType) -> String {
         match field_type {
-            FieldType::String => "string".to_string(),
-            FieldType::Integer | FieldType::Float => "number".to_string(),
+            FieldType::String | FieldType::Uuid => "string".to_string(),
+            FieldType::Enum(values) => {
+                let mut members: Vec<String> = values
+                    .iter()
+                    .map(|value| format!("\"{}\"", value))
+                    .collect();
+                // Still suggests the known values, unlike a plain `string`
+                if self.shared.enum_catchall {
+                    members.push("(string & {})".to_string());
+                }
+                members.join(" | ")
+            }
+            FieldType::DateTime(format) if !format.is_epoch() => "Date".to_string(),
+            FieldType::Integer | FieldType::Float | FieldType::DateTime(_) => "number".to_string(),
+            FieldType::Decimal => match self.options.decimal {
+                TsDecimal::Number => "number".to_string(),
+                TsDecimal::String => "string".to_string(),
+            },
             FieldType::Boolean => "boolean".to_string(),
             FieldType::Null => "null".to_string(),
             FieldType::Array(inner) => {
-                format!("{}[]", self.field_type_to_typescript(inner))
+                let element = self.field_type_to_typescript(inner);
+                // `A | B[]` would mean "A or array of B", so unions need parentheses
+                let is_union = match inner.as_ref() {
+                    _ if self.collapses_to_unknown(inner) => false,
+                    FieldType::Optional(_) => true,
+                    FieldType::Union(types) => self.union_members(types).len() > 1,
+                    FieldType::Enum(values) => values.len() > 1 || self.shared.enum_catchall,
+                    _ => false,
+                };
+                if is_union {
+                    format!("({})[]", element)
+                } else {
+                    format!("{}[]", element)
+                }
             }
+            FieldType::Set(inner) => format!("Set<{}>", self.field_type_to_typescript(inner)),
+            _ if self.collapses_to_unknown(field_type) => "unknown".to_string(),
             FieldType::Optional(inner) => {
-                format!("{} | undefined", self.field_type_to_typescript(inner))
+                format!("{} | null", self.field_type_to_typescript(inner))
             }
             FieldType::Reference(name) => name.clone(),
-            FieldType::Union(types) => types
-                .iter()
-                .map(|t| self.field_type_to_typescript(t))
-                .collect::<Vec<_>>()
-                .join(" | "),
+            FieldType::Union(types) => self.union_members(types).join(" | "),
             FieldType::Any => "unknown".to_string(),
             FieldType::Map(key, value) => {
                 format!(
@@ -88,6 +247,46 @@ impl TypeScriptGenerator {
     }
 }
 
+impl TypeScriptGenerator {
+    /// Whether a field gets the `?` marker
+    ///
+    /// With `collapse_optional_any`, nullable `unknown` fields get it too, as
+    /// their type no longer spells out `| null`.
+    fn is_optional(&self, field: &Field) -> bool {
+        let nullable_unknown = self.collapses_to_unknown(&field.field_type)
+            && match &field.field_type {
+                FieldType::Union(types) => types.contains(&FieldType::Null),
+                _ => true,
+            };
+        field.optional || self.shared.optional_fields || nullable_unknown
+    }
+
+    /// Whether a type is rendered as plain `unknown` under
+    /// `collapse_optional_any`: `unknown | null`, or any union with an
+    /// `unknown` member, which absorbs the others
+    fn collapses_to_unknown(&self, field_type: &FieldType) -> bool {
+        self.options.collapse_optional_any
+            && match field_type {
+                FieldType::Optional(inner) => matches!(**inner, FieldType::Any),
+                FieldType::Union(types) => types.contains(&FieldType::Any),
+                _ => false,
+            }
+    }
+
+    /// Render the members of a union, dropping duplicates such as an integer
+    /// and a float that both become `number`
+    fn union_members(&self, types: &[FieldType]) -> Vec<String> {
+        let mut members: Vec<String> = Vec::new();
+        for member in types {
+            let rendered = self.field_type_to_typescript(member);
+            if !members.contains(&rendered) {
+                members.push(rendered);
+            }
+        }
+        members
+    }
+}
+
 impl CodeGenerator for TypeScriptGenerator {
     fn generate(&self, schema: &Schema) -> Result<String> {
         if schema.types.is_empty() {
@@ -102,28 +301,129 @@ impl CodeGenerator for TypeScriptGenerator {
         output.push_str("// Generated by Alchemist\n");
         output.push_str("// Do not edit manually\n\n");
 
+        // Members of an ambient namespace are declared by the namespace itself
+        let namespace = match self.options.style {
+            TsStyle::Dts => self.options.namespace.as_deref(),
+            _ => None,
+        };
+        if let Some(namespace) = namespace {
+            output.push_str(&format!("declare namespace {} {{\n", namespace));
+        }
+
         // Generate all type definitions (in reverse order so nested types come first)
+        let alias = root_alias(schema, &self.shared);
+        let mut declarations = Vec::new();
         for type_def in schema.types.iter().rev() {
-            output.push_str(&self.generate_interface(type_def));
-            output.push('\n');
+            if let Some(alias) = alias.as_ref().filter(|a| a.name == type_def.name) {
+                declarations.push(match (self.options.style, namespace) {
+                    (TsStyle::Interface, _) | (TsStyle::Dts, Some(_)) => {
+                        self.generate_alias(alias, "export type")
+                    }
+                    (TsStyle::Dts, None) => self.generate_alias(alias, "declare type"),
+                    (TsStyle::Jsdoc, _) => self.generate_alias_typedef(alias),
+                });
+                continue;
+            }
+            declarations.push(match (self.options.style, namespace) {
+                (TsStyle::Interface, _) | (TsStyle::Dts, Some(_)) => {
+                    self.generate_interface(type_def, "export interface")
+                }
+                (TsStyle::Dts, None) => self.generate_interface(type_def, "declare interface"),
+                (TsStyle::Jsdoc, _) => self.generate_typedef(type_def),
+            });
+        }
+
+        match namespace {
+            Some(_) => {
+                let body = declarations.join("\n");
+                for line in body.lines() {
+                    if line.is_empty() {
+                        output.push('\n');
+                    } else {
+                        output.push_str(&format!("{}{}\n", self.indent, line));
+                    }
+                }
+                output.push_str("}\n");
+            }
+            None => output.push_str(&declarations.join("\n")),
         }
 
         Ok(output.trim_end().to_string() + "\n")
     }
 
     fn file_extension(&self) -> &'static str {
-        "ts"
+        match self.options.style {
+            TsStyle::Interface => "ts",
+            TsStyle::Jsdoc => "js",
+            TsStyle::Dts => "d.ts",
+        }
     }
 
     fn name(&self) -> &'static str {
-        "TypeScript"
+        match self.options.style {
+            TsStyle::Interface => "TypeScript",
+            TsStyle::Jsdoc => "JSDoc",
+            TsStyle::Dts => "TypeScript declarations",
+        }
+    }
+}
+
+/// Documentation of a field emitted under `key`, noting the original JSON
+/// key when `--rename-all` changed it
+fn field_doc(field: &Field, key: &str) -> Option<String> {
+    if key == field.name {
+        return field.doc.clone();
+    }
+    let note = format!("JSON key \"{}\"", field.name);
+    Some(match &field.doc {
+        Some(doc) => format!("{} ({})", doc, note),
+        None => note,
+    })
+}
+
+/// Get the property key for a field in an interface or `@property` tag,
+/// quoting it when it isn't a valid identifier
+fn property_name(name: &str) -> String {
+    let is_identifier = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+
+    if is_identifier {
+        name.to_string()
+    } else {
+        js_string(name)
+    }
+}
+
+/// Quote text as a JavaScript string, escaping quotes, backslashes and
+/// control characters
+fn js_string(text: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+            c => quoted.push(c),
+        }
     }
+    quoted.push('"');
+    quoted
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::ast::Field;
+    use crate::generators::GeneratorOptions;
+    use crate::generators::RenameAll;
 
     #[test]
     fn test_generate_simple_interface() {
@@ -134,7 +434,7 @@ mod tests {
         type_def.add_field(Field::new("active", FieldType::Boolean));
         schema.add_type(type_def);
 
-        let generator = TypeScriptGenerator::new(GeneratorOptions::default());
+        let generator = TypeScriptGenerator::new(TsOptions::default(), SharedOptions::default());
         let output = generator.generate(&schema).unwrap();
 
         assert!(output.contains("export interface User {"));
@@ -151,10 +451,13 @@ mod tests {
         schema.add_type(type_def);
 
         let options = GeneratorOptions {
-            readonly: true,
+            shared: SharedOptions {
+                readonly: true,
+                ..Default::default()
+            },
             ..Default::default()
         };
-        let generator = TypeScriptGenerator::new(options);
+        let generator = TypeScriptGenerator::new(options.typescript, options.shared);
         let output = generator.generate(&schema).unwrap();
 
         assert!(output.contains("readonly version: string;"));
@@ -167,12 +470,52 @@ mod tests {
         type_def.add_field(Field::new("name", FieldType::String).optional());
         schema.add_type(type_def);
 
-        let generator = TypeScriptGenerator::new(GeneratorOptions::default());
+        let generator = TypeScriptGenerator::new(TsOptions::default(), SharedOptions::default());
         let output = generator.generate(&schema).unwrap();
 
         assert!(output.contains("name?: string;"));
     }
 
+    #[test]
+    fn test_optional_fields_and_readonly_apply_to_every_property() {
+        let mut schema = Schema::new("Config");
+        let mut type_def = TypeDef::new("Config");
+        type_def.add_field(Field::new("id", FieldType::Integer));
+        type_def.add_field(Field::new("name", FieldType::String).optional());
+        type_def.add_field(Field::new(
+            "note",
+            FieldType::Optional(Box::new(FieldType::String)),
+        ));
+        schema.add_type(type_def);
+
+        let render = |optional_fields, readonly| {
+            let options = GeneratorOptions {
+                shared: SharedOptions {
+                    optional_fields,
+                    readonly,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            TypeScriptGenerator::new(options.typescript, options.shared)
+                .generate(&schema)
+                .unwrap()
+        };
+
+        assert!(render(false, false).contains(
+            "export interface Config {\n  id: number;\n  name?: string;\n  note: string | null;\n}\n"
+        ));
+        assert!(render(true, false).contains(
+            "export interface Config {\n  id?: number;\n  name?: string;\n  note?: string | null;\n}\n"
+        ));
+        assert!(render(false, true).contains(
+            "export interface Config {\n  readonly id: number;\n  readonly name?: string;\n  readonly note: string | null;\n}\n"
+        ));
+        assert!(render(true, true).contains(
+            "export interface Config {\n  readonly id?: number;\n  readonly name?: string;\n  readonly note?: string | null;\n}\n"
+        ));
+    }
+
     #[test]
     fn test_generate_array_type() {
         let mut schema = Schema::new("Data");
@@ -183,7 +526,7 @@ mod tests {
         ));
         schema.add_type(type_def);
 
-        let generator = TypeScriptGenerator::new(GeneratorOptions::default());
+        let generator = TypeScriptGenerator::new(TsOptions::default(), SharedOptions::default());
         let output = generator.generate(&schema).unwrap();
 
         assert!(output.contains("items: string[];"));
@@ -206,11 +549,285 @@ mod tests {
         ));
         schema.add_type(root);
 
-        let generator = TypeScriptGenerator::new(GeneratorOptions::default());
+        let generator = TypeScriptGenerator::new(TsOptions::default(), SharedOptions::default());
         let output = generator.generate(&schema).unwrap();
 
         assert!(output.contains("export interface Address {"));
         assert!(output.contains("export interface Root {"));
         assert!(output.contains("address: Address;"));
     }
+
+    #[test]
+    fn test_generate_ambient_declarations() {
+        let mut schema = Schema::new("User");
+        let mut type_def = TypeDef::new("User");
+        type_def.add_field(Field::new("name", FieldType::String));
+        schema.add_type(type_def);
+        let render = |namespace: Option<&str>| {
+            TypeScriptGenerator::new(
+                TsOptions {
+                    style: TsStyle::Dts,
+                    namespace: namespace.map(str::to_string),
+                    ..Default::default()
+                },
+                SharedOptions::default(),
+            )
+            .generate(&schema)
+            .unwrap()
+        };
+
+        assert!(render(None).contains("declare interface User {\n  name: string;\n}"));
+        assert!(render(Some("Api")).contains(
+            "declare namespace Api {\n  export interface User {\n    name: string;\n  }\n}"
+        ));
+    }
+
+    #[test]
+    fn test_generate_jsdoc_typedef() {
+        let mut schema = Schema::new("User");
+        let mut type_def = TypeDef::new("User");
+        type_def.add_field(Field::new("name", FieldType::String));
+        type_def.add_field(Field::new("age", FieldType::Integer).optional());
+        type_def.add_field(Field::new(
+            "tags",
+            FieldType::Array(Box::new(FieldType::String)),
+        ));
+        schema.add_type(type_def);
+
+        let options = GeneratorOptions {
+            typescript: TsOptions {
+                style: TsStyle::Jsdoc,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let generator = TypeScriptGenerator::new(options.typescript, options.shared);
+        let output = generator.generate(&schema).unwrap();
+
+        assert!(output.contains(" * @typedef {Object} User"));
+        assert!(output.contains(" * @property {string} name"));
+        assert!(output.contains(" * @property {number} [age]"));
+        assert!(output.contains(" * @property {string[]} tags"));
+        assert!(!output.contains("interface"));
+        assert_eq!(generator.file_extension(), "js");
+    }
+
+    #[test]
+    fn test_property_names_are_quoted_when_needed() {
+        let mut schema = Schema::new("Config");
+        let mut type_def = TypeDef::new("Config");
+        type_def.add_field(Field::new("type", FieldType::String).with_safe_name("r#type"));
+        type_def
+            .add_field(Field::new("first-name", FieldType::String).with_safe_name("first_name"));
+        schema.add_type(type_def);
+
+        let generator = TypeScriptGenerator::new(TsOptions::default(), SharedOptions::default());
+        let output = generator.generate(&schema).unwrap();
+
+        assert!(output.contains("  type: string;"));
+        assert!(output.contains("  \"first-name\": string;"));
+
+        let generator = TypeScriptGenerator::new(
+            TsOptions {
+                style: TsStyle::Jsdoc,
+                ..Default::default()
+            },
+            SharedOptions::default(),
+        );
+        let output = generator.generate(&schema).unwrap();
+
+        assert!(output.contains(" * @property {string} type\n"));
+        assert!(output.contains(" * @property {string} \"first-name\"\n"));
+    }
+
+    #[test]
+    fn test_quoted_property_names_are_escaped() {
+        let mut schema = Schema::new("Config");
+        let mut type_def = TypeDef::new("Config");
+        type_def.add_field(Field::new("quote\"key", FieldType::Integer));
+        type_def.add_field(Field::new("back\\slash", FieldType::Integer).optional());
+        schema.add_type(type_def);
+
+        let output = TypeScriptGenerator::new(TsOptions::default(), SharedOptions::default())
+            .generate(&schema)
+            .unwrap();
+        assert!(output.contains("  \"quote\\\"key\": number;\n"));
+        assert!(output.contains("  \"back\\\\slash\"?: number;\n"));
+
+        let output = TypeScriptGenerator::new(
+            TsOptions {
+                style: TsStyle::Jsdoc,
+                ..Default::default()
+            },
+            SharedOptions::default(),
+        )
+        .generate(&schema)
+        .unwrap();
+        assert!(output.contains(" * @property {number} \"quote\\\"key\"\n"));
+        assert!(output.contains(" * @property {number} [\"back\\\\slash\"]\n"));
+    }
+
+    #[test]
+    fn test_rename_all_renames_properties_and_keeps_keys() {
+        let mut schema = Schema::new("User");
+        let mut type_def = TypeDef::new("User");
+        type_def.add_field(Field::new("first-name", FieldType::String).with_doc("Given name"));
+        type_def.add_field(Field::new("userId", FieldType::Integer).optional());
+        schema.add_type(type_def);
+        let shared = SharedOptions {
+            rename_all: Some(RenameAll::SnakeCase),
+            ..Default::default()
+        };
+
+        let output = TypeScriptGenerator::new(TsOptions::default(), shared.clone())
+            .generate(&schema)
+            .unwrap();
+        assert!(output.contains(
+            "  /** Given name (JSON key \"first-name\") */\n  first_name: string;\n  /** JSON key \"userId\" */\n  user_id?: number;\n"
+        ));
+
+        let output = TypeScriptGenerator::new(
+            TsOptions {
+                style: TsStyle::Jsdoc,
+                ..Default::default()
+            },
+            shared,
+        )
+        .generate(&schema)
+        .unwrap();
+        assert!(output.contains(" * @property {number} [user_id] - JSON key \"userId\"\n"));
+    }
+
+    #[test]
+    fn test_mixed_primitive_array_renders_union() {
+        let options = GeneratorOptions::default();
+        let schema =
+            crate::parser::parse_json(r#"{"values": [1, "a", 2.5], "ids": [1, 2.5]}"#, &options)
+                .unwrap();
+        let output = TypeScriptGenerator::new(options.typescript, options.shared)
+            .generate(&schema)
+            .unwrap();
+
+        assert!(output.contains("  values: (number | string)[];\n"));
+        assert!(output.contains("  ids: number[];\n"));
+    }
+
+    #[test]
+    fn test_collapse_optional_any() {
+        let mut schema = Schema::new("Event");
+        let mut type_def = TypeDef::new("Event");
+        let optional_any = FieldType::Optional(Box::new(FieldType::Any));
+        type_def.add_field(Field::new("payload", optional_any.clone()).optional());
+        type_def.add_field(Field::new("meta", optional_any.clone()));
+        type_def.add_field(Field::new(
+            "value",
+            FieldType::Union(vec![FieldType::String, FieldType::Any]),
+        ));
+        type_def.add_field(Field::new(
+            "items",
+            FieldType::Array(Box::new(optional_any)),
+        ));
+        schema.add_type(type_def);
+
+        let output = TypeScriptGenerator::new(TsOptions::default(), SharedOptions::default())
+            .generate(&schema)
+            .unwrap();
+        assert!(output.contains("  payload?: unknown | null;\n"));
+
+        let generator = TypeScriptGenerator::new(
+            TsOptions {
+                collapse_optional_any: true,
+                ..Default::default()
+            },
+            SharedOptions::default(),
+        );
+        let output = generator.generate(&schema).unwrap();
+        assert!(output.contains("  payload?: unknown;\n"));
+        assert!(output.contains("  meta?: unknown;\n"));
+        assert!(output.contains("  value: unknown;\n"));
+        assert!(output.contains("  items: unknown[];\n"));
+    }
+
+    #[test]
+    fn test_string_enums_become_literal_unions() {
+        let mut schema = Schema::new("Task");
+        let mut type_def = TypeDef::new("Task");
+        let status = FieldType::Enum(vec!["open".to_string(), "done".to_string()]);
+        type_def.add_field(Field::new("status", status.clone()));
+        type_def.add_field(Field::new("history", FieldType::Array(Box::new(status))));
+        schema.add_type(type_def);
+
+        let output = TypeScriptGenerator::new(TsOptions::default(), SharedOptions::default())
+            .generate(&schema)
+            .unwrap();
+        assert!(output.contains("  status: \"open\" | \"done\";\n"));
+        assert!(output.contains("  history: (\"open\" | \"done\")[];\n"));
+
+        let options = GeneratorOptions {
+            shared: SharedOptions {
+                enum_catchall: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let output = TypeScriptGenerator::new(options.typescript, options.shared)
+            .generate(&schema)
+            .unwrap();
+        assert!(output.contains("  status: \"open\" | \"done\" | (string & {});\n"));
+    }
+
+    #[test]
+    fn test_flatten_root_array_to_type_alias() {
+        let options = GeneratorOptions {
+            shared: SharedOptions {
+                flatten: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let schema = crate::parser::parse_json(r#"[{"id": 1}, {"id": 2}]"#, &options).unwrap();
+
+        let output = TypeScriptGenerator::new(options.typescript.clone(), options.shared.clone())
+            .generate(&schema)
+            .unwrap();
+        assert!(output.contains("export interface RootItem {\n  id: number;\n}"));
+        assert!(output.ends_with("export type Root = RootItem[];\n"));
+
+        let options = GeneratorOptions {
+            typescript: TsOptions {
+                style: TsStyle::Jsdoc,
+                ..Default::default()
+            },
+            ..options
+        };
+        let output = TypeScriptGenerator::new(options.typescript, options.shared)
+            .generate(&schema)
+            .unwrap();
+        assert!(output.contains("/**\n * @typedef {RootItem[]} Root\n */\n"));
+    }
+
+    #[test]
+    fn test_tagged_union_keeps_literal_tags() {
+        let options = GeneratorOptions {
+            shared: SharedOptions {
+                enum_catchall: true,
+                ..Default::default()
+            },
+            tagged_union: Some("kind".to_string()),
+            ..Default::default()
+        };
+        let schema = crate::parser::parse_json(
+            r#"{"shapes": [{"kind": "circle", "radius": 1.5}, {"kind": "square", "side": 2}]}"#,
+            &options,
+        )
+        .unwrap();
+        let output = TypeScriptGenerator::new(options.typescript, options.shared)
+            .generate(&schema)
+            .unwrap();
+
+        assert!(output.contains("  shapes: (Circle | Square)[];\n"));
+        assert!(
+            output.contains("export interface Circle {\n  kind: \"circle\";\n  radius: number;\n}")
+        );
+    }
 }
@@ -1,18 +1,28 @@
 //! Zod schema generator
 
-use crate::ast::{FieldType, Schema, TypeDef};
+use crate::ast::{DateTimeFormat, FieldType, Schema, TypeDef};
 use crate::error::{AlchemistError, Result};
-use crate::generators::{CodeGenerator, GeneratorOptions};
+use crate::generators::typescript::TsDecimal;
+use crate::generators::typescript::TsOptions;
+use crate::generators::{root_alias, CodeGenerator, Indent, RootAlias, SharedOptions};
 
 /// Zod schema generator
 pub struct ZodGenerator {
-    options: GeneratorOptions,
+    options: TsOptions,
+    shared: SharedOptions,
+    /// Whitespace of one indentation level
+    indent: String,
 }
 
 impl ZodGenerator {
-    /// Create a new Zod generator with the given options
-    pub fn new(options: GeneratorOptions) -> Self {
-        Self { options }
+    /// Create a new Zod generator with the TypeScript and shared options
+    pub fn new(options: TsOptions, shared: SharedOptions) -> Self {
+        let indent = shared.indent_unit(Indent::Spaces(2));
+        Self {
+            options,
+            shared,
+            indent,
+        }
     }
 
     /// Generate a Zod schema from a type definition
@@ -35,13 +45,16 @@ impl ZodGenerator {
             let field_name = field.code_name();
             let zod_type = self.field_type_to_zod(&field.field_type);
 
-            let final_type = if field.optional || self.options.optional_fields {
+            let mut final_type = if field.optional || self.shared.optional_fields {
                 format!("{}.optional()", zod_type)
             } else {
                 zod_type
             };
+            if let Some(doc) = &field.doc {
+                final_type = format!("{}.describe({:?})", final_type, doc);
+            }
 
-            output.push_str(&format!("  {}: {},\n", field_name, final_type));
+            output.push_str(&format!("{}{}: {},\n", self.indent, field_name, final_type));
         }
 
         output.push_str("});\n\n");
@@ -55,36 +68,83 @@ impl ZodGenerator {
         output
     }
 
+    /// Generate the schema of a flattened root, see [`root_alias`]
+    fn generate_alias(&self, alias: &RootAlias) -> String {
+        let mut output = String::new();
+        if let Some(doc) = alias.doc {
+            output.push_str(&format!("/**\n * {}\n */\n", doc));
+        }
+        output.push_str(&format!(
+            "export const {}Schema = {};\n\n",
+            alias.name,
+            self.field_type_to_zod(alias.field_type)
+        ));
+        output.push_str(&format!(
+            "export type {} = z.infer<typeof {}Schema>;\n",
+            alias.name, alias.name
+        ));
+        output
+    }
+
     /// Convert AST FieldType to Zod type string
     fn field_type_to_zod(&self, field_type: &FieldType) -> String {
         match field_type {
             FieldType::String => "z.string()".to_string(),
-            FieldType::Integer => "z.number().int()".to_string(),
+            FieldType::Enum(values) => {
+                let values: Vec<String> = values.iter().map(|v| format!("{:?}", v)).collect();
+                let zod_enum = format!("z.enum([{}])", values.join(", "));
+                if self.shared.enum_catchall {
+                    // Tried last, so unseen values still parse
+                    format!("z.union([{}, z.string()])", zod_enum)
+                } else {
+                    zod_enum
+                }
+            }
+            FieldType::DateTime(DateTimeFormat::Rfc3339) => {
+                "z.string().datetime({ offset: true })".to_string()
+            }
+            FieldType::DateTime(DateTimeFormat::LocalDateTime) => {
+                "z.string().datetime({ local: true })".to_string()
+            }
+            FieldType::DateTime(DateTimeFormat::Date) => "z.string().date()".to_string(),
+            FieldType::Integer | FieldType::DateTime(_) => "z.number().int()".to_string(),
             FieldType::Float => "z.number()".to_string(),
+            FieldType::Uuid => "z.string().uuid()".to_string(),
+            FieldType::Decimal => match self.options.decimal {
+                TsDecimal::Number => "z.number()".to_string(),
+                TsDecimal::String => "z.string()".to_string(),
+            },
             FieldType::Boolean => "z.boolean()".to_string(),
             FieldType::Null => "z.null()".to_string(),
             FieldType::Array(inner) => {
                 format!("z.array({})", self.field_type_to_zod(inner))
             }
+            // JSON has no sets, so parse an array and collect it
+            FieldType::Set(inner) => format!(
+                "z.array({}).transform((items) => new Set(items))",
+                self.field_type_to_zod(inner)
+            ),
             FieldType::Optional(inner) => {
-                format!("{}.optional()", self.field_type_to_zod(inner))
+                format!("{}.nullable()", self.field_type_to_zod(inner))
             }
             FieldType::Reference(name) => format!("{}Schema", name),
             FieldType::Union(types) => {
-                let types_str = types
-                    .iter()
-                    .map(|t| self.field_type_to_zod(t))
-                    .collect::<Vec<_>>()
-                    .join(", ");
-                format!("z.union([{}])", types_str)
+                let mut members: Vec<String> =
+                    types.iter().map(|t| self.field_type_to_zod(t)).collect();
+                if self.shared.enum_catchall {
+                    // Tried last, so values of other types still parse
+                    members.push("z.unknown()".to_string());
+                }
+                format!("z.union([{}])", members.join(", "))
             }
             FieldType::Any => "z.unknown()".to_string(),
             FieldType::Map(key, value) => {
-                format!(
-                    "z.record({}, {})",
-                    self.field_type_to_zod(key),
-                    self.field_type_to_zod(value)
-                )
+                // Object keys are always strings at runtime
+                let key = match key.as_ref() {
+                    FieldType::Integer => r"z.string().regex(/^-?\d+$/)".to_string(),
+                    other => self.field_type_to_zod(other),
+                };
+                format!("z.record({}, {})", key, self.field_type_to_zod(value))
             }
         }
     }
@@ -107,9 +167,13 @@ impl CodeGenerator for ZodGenerator {
         // Add zod import
         output.push_str("import { z } from 'zod';\n\n");
 
-        // Generate all type definitions (in reverse order so nested types come first)
-        for type_def in schema.types.iter().rev() {
-            output.push_str(&self.generate_schema(type_def));
+        // Schemas are `const` bindings, so referenced schemas must be declared first
+        let alias = root_alias(schema, &self.shared);
+        for type_def in schema.iter_topological(false) {
+            match alias.as_ref().filter(|a| a.name == type_def.name) {
+                Some(alias) => output.push_str(&self.generate_alias(alias)),
+                None => output.push_str(&self.generate_schema(type_def)),
+            }
             output.push('\n');
         }
 
@@ -129,6 +193,7 @@ impl CodeGenerator for ZodGenerator {
 mod tests {
     use super::*;
     use crate::ast::Field;
+    use crate::generators::GeneratorOptions;
 
     #[test]
     fn test_generate_simple_schema() {
@@ -138,7 +203,7 @@ mod tests {
         type_def.add_field(Field::new("age", FieldType::Integer));
         schema.add_type(type_def);
 
-        let generator = ZodGenerator::new(GeneratorOptions::default());
+        let generator = ZodGenerator::new(TsOptions::default(), SharedOptions::default());
         let output = generator.generate(&schema).unwrap();
 
         assert!(output.contains("export const UserSchema = z.object({"));
@@ -146,4 +211,92 @@ mod tests {
         assert!(output.contains("age: z.number().int(),"));
         assert!(output.contains("export type User = z.infer<typeof UserSchema>;"));
     }
+
+    #[test]
+    fn test_referenced_schemas_are_declared_first() {
+        // Parser order: root, then the innermost type before its parent
+        let mut schema = Schema::new("Root");
+        let mut root = TypeDef::new("Root");
+        root.add_field(Field::new("a", FieldType::Reference("A".to_string())));
+        schema.add_type(root);
+        let mut b = TypeDef::new("B");
+        b.add_field(Field::new("value", FieldType::Integer));
+        schema.add_type(b);
+        let mut a = TypeDef::new("A");
+        a.add_field(Field::new("b", FieldType::Reference("B".to_string())));
+        schema.add_type(a);
+
+        let generator = ZodGenerator::new(TsOptions::default(), SharedOptions::default());
+        let output = generator.generate(&schema).unwrap();
+
+        let b_pos = output.find("export const BSchema").unwrap();
+        let a_pos = output.find("export const ASchema").unwrap();
+        let root_pos = output.find("export const RootSchema").unwrap();
+        assert!(b_pos < a_pos && a_pos < root_pos);
+    }
+
+    #[test]
+    fn test_integer_map_keys_validate_as_numeric_strings() {
+        let mut schema = Schema::new("Root");
+        let mut root = TypeDef::new("Root");
+        root.add_field(Field::new(
+            "scores",
+            FieldType::Map(Box::new(FieldType::Integer), Box::new(FieldType::Float)),
+        ));
+        schema.add_type(root);
+
+        let output = ZodGenerator::new(TsOptions::default(), SharedOptions::default())
+            .generate(&schema)
+            .unwrap();
+
+        assert!(output.contains(r"scores: z.record(z.string().regex(/^-?\d+$/), z.number()),"));
+    }
+
+    #[test]
+    fn test_field_docs_become_descriptions() {
+        let mut schema = Schema::new("Root");
+        let mut root = TypeDef::new("Root");
+        root.add_field(
+            Field::new("id", FieldType::Integer)
+                .optional()
+                .with_doc("Account \"number\""),
+        );
+        schema.add_type(root);
+
+        let output = ZodGenerator::new(TsOptions::default(), SharedOptions::default())
+            .generate(&schema)
+            .unwrap();
+
+        assert!(
+            output.contains(r#"id: z.number().int().optional().describe("Account \"number\""),"#)
+        );
+    }
+
+    #[test]
+    fn test_string_enums_use_z_enum() {
+        let mut schema = Schema::new("Task");
+        let mut root = TypeDef::new("Task");
+        root.add_field(Field::new(
+            "status",
+            FieldType::Enum(vec!["open".to_string(), "done".to_string()]),
+        ));
+        schema.add_type(root);
+
+        let output = ZodGenerator::new(TsOptions::default(), SharedOptions::default())
+            .generate(&schema)
+            .unwrap();
+        assert!(output.contains(r#"status: z.enum(["open", "done"]),"#));
+
+        let options = GeneratorOptions {
+            shared: SharedOptions {
+                enum_catchall: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let output = ZodGenerator::new(options.typescript, options.shared)
+            .generate(&schema)
+            .unwrap();
+        assert!(output.contains(r#"status: z.union([z.enum(["open", "done"]), z.string()]),"#));
+    }
 }
@@ -0,0 +1,35 @@
+//! Alchemist - Transform JSON/YAML/TOML into type-safe code
+//!
+//! The library holds the inference engine and code generators. It has no
+//! filesystem or terminal dependencies, so it also builds for
+//! `wasm32-unknown-unknown`.
+//!
+//! Infer a [`Schema`] with the [`parser`], then hand it to any
+//! [`CodeGenerator`]:
+//!
+//! ```
+//! use alchemist::generators::rust::RustGenerator;
+//! use alchemist::{parser, CodeGenerator, GeneratorOptions};
+//!
+//! let options = GeneratorOptions::default();
+//! let schema = parser::parse_json(r#"{"id": 1, "tags": ["a"]}"#, &options)?;
+//! let code = RustGenerator::new(options.rust, options.shared).generate(&schema)?;
+//! assert!(code.contains("pub tags: Vec<String>,"));
+//! # Ok::<(), alchemist::AlchemistError>(())
+//! ```
+//!
+//! [`transform()`] does both steps in one call.
+
+pub mod ast;
+pub mod error;
+pub mod formats;
+pub mod generators;
+pub mod parser;
+pub mod transform;
+mod utils;
+
+pub use ast::Schema;
+pub use error::{AlchemistError, Result};
+pub use formats::{InputFormat, OutputFormat};
+pub use generators::{CodeGenerator, GeneratorOptions};
+pub use transform::transform;
@@ -0,0 +1,53 @@
+//! Diagnostic logging to stderr (`-v`, `-vv`)
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Writes `[LEVEL target] message` lines to stderr, keeping stdout for the
+/// generated code
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{} {}] {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Log level for a `-v` count: silent by default, debug for `-v` and trace
+/// for `-vv` or more
+pub fn level_for(verbosity: u8) -> LevelFilter {
+    match verbosity {
+        0 => LevelFilter::Off,
+        1 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
+
+/// Install the stderr logger at the level for `verbosity`
+pub fn init(verbosity: u8) {
+    static LOGGER: StderrLogger = StderrLogger;
+    // Only fails if a logger is already set, which then stays in charge
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(level_for(verbosity));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_for_verbosity() {
+        assert_eq!(level_for(0), LevelFilter::Off);
+        assert_eq!(level_for(1), LevelFilter::Debug);
+        assert_eq!(level_for(2), LevelFilter::Trace);
+        assert_eq!(level_for(5), LevelFilter::Trace);
+    }
+}
@@ -1,26 +1,61 @@
 //! Alchemist - Transform JSON/YAML/TOML into type-safe code
 
-mod ast;
+mod cache;
 mod cli;
-mod error;
-mod formats;
-mod generators;
-mod parser;
+mod explore;
+mod logger;
 mod reporter;
-mod utils;
+mod verify;
 
-use anyhow::Result;
+use alchemist::ast::Schema;
+use alchemist::error::{AlchemistError, Result};
+use alchemist::formats::{InputFormat, OutputFormat};
+use alchemist::generators::rust::{RenameCoverage, RustGenerator};
+use alchemist::generators::RenameAll;
+use alchemist::{generators, parser};
+use cache::SchemaCache;
 use clap::Parser;
 use cli::Cli;
-use formats::OutputFormat;
-use generators::CodeGenerator;
+use log::debug;
+use notify_debouncer_mini::notify::RecursiveMode;
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult};
 use owo_colors::set_override;
-use reporter::{ConversionStats, Reporter};
+use reporter::{ConversionStats, FileSummary, Reporter};
+use std::collections::BTreeSet;
 use std::fs;
-use std::time::Instant;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
 
-fn main() -> Result<()> {
+/// Exit with the error's exit code, see [`AlchemistError::exit_code`]
+///
+/// The reporter has already printed the error.
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) | Err(AlchemistError::Cancelled) => ExitCode::SUCCESS,
+        Err(e) => ExitCode::from(e.exit_code()),
+    }
+}
+
+/// Code generated from one input, with what the report shows about it
+struct Conversion {
+    schema: Schema,
+    output: String,
+    stats: ConversionStats,
+    /// Generator name and file extension, e.g. `Rust (.rs)`
+    format_label: String,
+    extension: &'static str,
+    rename_coverage: Option<(RenameAll, RenameCoverage)>,
+    warnings: Vec<String>,
+}
+
+fn run() -> Result<()> {
     let cli = Cli::parse();
+    if let Err(e) = cli.check() {
+        e.exit();
+    }
 
     // Handle shell completions request
     if let Some(shell) = cli.completions {
@@ -28,85 +63,380 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    // Handle format listing request
+    if cli.list_formats {
+        print!("{}", Cli::format_list());
+        return Ok(());
+    }
+
+    logger::init(cli.verbose);
+
     // Handle no-color mode for CI/CD
     if cli.no_color {
         set_override(false);
     }
 
-    // Start timing
-    let start = Instant::now();
-
-    // Read input from file or stdin
-    let input_content = cli.read_input()?;
-    let input_size = input_content.len();
+    let reporter = Reporter::for_terminal(cli.report_width, cli.report_theme);
 
-    // Create generator options
-    let options = cli.generator_options();
-
-    // Auto-detect input format from extension, or use specified
-    let input_format = cli.detect_input_format();
-
-    // Parse input to AST based on input format
-    let schema = match input_format {
-        formats::InputFormat::Json => parser::parse_json(&input_content, &options),
-        formats::InputFormat::Yaml => parser::parse_yaml(&input_content, &options),
-        formats::InputFormat::Toml => parser::parse_toml(&input_content, &options),
-    };
+    // Batch mode reports each failed file as it goes
+    if !cli.batch.is_empty() {
+        return run_batch(&cli, &reporter);
+    }
 
-    let schema = match schema {
-        Ok(s) => s,
-        Err(e) => {
-            Reporter::print_error(&e.to_string());
-            return Err(e.into());
+    run_single(&cli, &reporter).inspect_err(|e| {
+        if !matches!(e, AlchemistError::Cancelled) {
+            reporter.print_error(&e.to_string())
         }
-    };
+    })
+}
 
-    // Select generator based on output format
-    let generator: Box<dyn CodeGenerator> = match cli.output_format {
-        OutputFormat::Rust => Box::new(generators::rust::RustGenerator::new(
-            cli.generator_options(),
-        )),
-        OutputFormat::Typescript => Box::new(generators::typescript::TypeScriptGenerator::new(
-            cli.generator_options(),
-        )),
-        OutputFormat::Zod => Box::new(generators::zod::ZodGenerator::new(cli.generator_options())),
-        OutputFormat::Python => Box::new(generators::python::PythonGenerator::new(
-            cli.generator_options(),
-        )),
-    };
+/// Diff, watch or convert the input once, leaving its error to the caller to
+/// report
+fn run_single(cli: &Cli, reporter: &Reporter) -> Result<()> {
+    if let [old_path, new_path] = cli.diff_schema.as_slice() {
+        return run_diff(cli, old_path, new_path);
+    }
 
-    let output = generator.generate(&schema)?;
+    if cli.watch {
+        return run_watch(cli, reporter);
+    }
 
-    let output_size = output.len();
-    let duration = start.elapsed();
+    // Start timing
+    let start = Instant::now();
 
-    // Calculate statistics
-    let stats = ConversionStats::from_schema(&schema, duration, input_size, output_size);
+    // Read input from files or stdin, auto-detecting each file's format
+    let inputs = cli.read_inputs()?;
+
+    let conversion = convert(cli, &inputs, start)?;
 
     // Write output to file if specified
     if let Some(ref output_path) = cli.output {
-        fs::write(output_path, &output)?;
+        fs::write(output_path, cli.output_encoding.encode(&conversion.output))?;
+    }
+
+    if let Some(report_path) = &cli.report_json {
+        if report_path.as_os_str() == "-" {
+            reporter.write_json_report(&conversion.stats, &conversion.schema, io::stdout())?;
+        } else {
+            let file = fs::File::create(report_path)?;
+            reporter.write_json_report(&conversion.stats, &conversion.schema, file)?;
+        }
     }
 
     // Print report and output
     if !cli.quiet {
-        Reporter::print_stats(
-            &stats,
-            &format!("{} (.{})", generator.name(), generator.file_extension()),
-        );
-        Reporter::print_types_summary(&schema);
-        Reporter::print_success(cli.output.as_ref().map(|p| p.to_str().unwrap_or("output")));
+        reporter.print_stats(&conversion.stats, &conversion.format_label);
+        reporter.print_types_summary(&conversion.schema);
+        if let Some((rename_all, coverage)) = conversion.rename_coverage {
+            reporter.print_rename_coverage(rename_all.serde_name(), coverage);
+        }
+        reporter.print_warnings(&conversion.warnings);
+        reporter.print_success(cli.output.as_ref().map(|p| p.to_str().unwrap_or("output")));
 
         // Print generated code to stdout only if no output file specified
         if cli.output.is_none() {
-            println!("{}", "─".repeat(60));
-            println!();
-            println!("{}", output);
+            reporter.print_separator();
+            println!("{}", conversion.output);
         }
     } else if cli.output.is_none() {
         // Quiet mode but no output file - just print the code
-        print!("{}", output);
+        print!("{}", conversion.output);
+    }
+
+    Ok(())
+}
+
+/// Convert every `--batch` file, recording a failed file and moving on to
+/// the next unless `--fail-fast` is set
+///
+/// The run fails with the first file's error once all files are done, after
+/// the `--summary-json` report is written.
+fn run_batch(cli: &Cli, reporter: &Reporter) -> Result<()> {
+    let mut summaries = Vec::new();
+    let mut first_error = None;
+
+    for path in &cli.batch {
+        let start = Instant::now();
+        let result = fs::read_to_string(path)
+            .map_err(AlchemistError::from)
+            .and_then(|content| convert(cli, &[(content, cli.detect_format_of(path))], start))
+            .and_then(|conversion| {
+                let output_path = cli.batch_output_path(path, conversion.extension);
+                if let Some(dir) = output_path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                fs::write(&output_path, cli.output_encoding.encode(&conversion.output))?;
+                Ok((conversion, output_path))
+            });
+
+        match result {
+            Ok((conversion, output_path)) => {
+                if !cli.quiet {
+                    reporter.print_warnings(&conversion.warnings);
+                    reporter.print_success(output_path.to_str());
+                }
+                summaries.push(FileSummary::success(path, &conversion.stats));
+            }
+            Err(e) => {
+                reporter.print_error(&format!("{}: {}", path.display(), e));
+                summaries.push(FileSummary::failure(path, start.elapsed(), &e));
+                first_error.get_or_insert(e);
+                if cli.fail_fast {
+                    break;
+                }
+            }
+        }
+    }
+
+    if let Some(summary_path) = &cli.summary_json {
+        write_summary(summary_path, &summaries)
+            .inspect_err(|e| reporter.print_error(&e.to_string()))?;
+    }
+
+    first_error.map_or(Ok(()), Err)
+}
+
+/// Write the `--summary-json` file of a batch run
+fn write_summary(path: &Path, summaries: &[FileSummary]) -> Result<()> {
+    fs::write(path, serde_json::to_string_pretty(summaries)?)?;
+    Ok(())
+}
+
+/// How long `--watch` waits for edits to settle before regenerating
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// What wakes the `--watch` loop
+enum WatchEvent {
+    /// The watched directories changed, possibly not in an input file
+    Changed(DebounceEventResult),
+    /// Ctrl-C was pressed
+    Stop,
+}
+
+/// Regenerate the `-o` file each time an input file changes, until Ctrl-C
+///
+/// Editors often save by replacing the file, so the directories holding the
+/// inputs are watched and their events filtered down to the inputs. A failed
+/// regeneration is reported and the watch goes on.
+fn run_watch(cli: &Cli, reporter: &Reporter) -> Result<()> {
+    let Some(output_path) = cli.output.as_deref().filter(|_| !cli.reads_stdin()) else {
+        return Err(AlchemistError::IoError(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "--watch needs an input file (-i) and an output file (-o), stdin cannot be watched",
+        )));
+    };
+    let inputs = cli
+        .input
+        .iter()
+        .map(fs::canonicalize)
+        .collect::<io::Result<Vec<PathBuf>>>()?;
+
+    let (sender, events) = mpsc::channel();
+    let stop = sender.clone();
+    ctrlc::set_handler(move || {
+        let _ = stop.send(WatchEvent::Stop);
+    })
+    .map_err(io::Error::other)?;
+    let mut debouncer = new_debouncer(WATCH_DEBOUNCE, move |result| {
+        let _ = sender.send(WatchEvent::Changed(result));
+    })
+    .map_err(io::Error::other)?;
+    let dirs: BTreeSet<&Path> = inputs.iter().filter_map(|path| path.parent()).collect();
+    for dir in dirs {
+        debouncer
+            .watcher()
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(io::Error::other)?;
     }
 
+    let modified = || {
+        inputs
+            .iter()
+            .map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+            .collect::<Vec<_>>()
+    };
+    let mut last_modified = modified();
+    regenerate(cli, reporter, output_path);
+    if !cli.quiet {
+        println!("Watching {} input(s), press Ctrl-C to stop", inputs.len());
+    }
+    for event in events {
+        match event {
+            WatchEvent::Changed(Ok(changes)) => {
+                // Reading an input is an event too, so only new content counts
+                if changes.iter().any(|change| inputs.contains(&change.path))
+                    && modified() != last_modified
+                {
+                    last_modified = modified();
+                    regenerate(cli, reporter, output_path);
+                }
+            }
+            WatchEvent::Changed(Err(e)) => reporter.print_error(&e.to_string()),
+            WatchEvent::Stop => break,
+        }
+    }
+    debug!("watch stopped");
     Ok(())
 }
+
+/// Convert the inputs again and write the `-o` file for `--watch`
+fn regenerate(cli: &Cli, reporter: &Reporter, output_path: &Path) {
+    let start = Instant::now();
+    let result = cli
+        .read_inputs()
+        .map_err(AlchemistError::from)
+        .and_then(|inputs| convert(cli, &inputs, start))
+        .and_then(|conversion| {
+            fs::write(output_path, cli.output_encoding.encode(&conversion.output))?;
+            Ok(conversion)
+        });
+
+    match result {
+        Ok(conversion) if !cli.quiet => {
+            reporter.print_warnings(&conversion.warnings);
+            reporter.print_regenerated(&conversion.stats, &output_path.to_string_lossy());
+        }
+        Ok(_) => {}
+        Err(e) => reporter.print_error(&e.to_string()),
+    }
+}
+
+/// Print the changes between the schemas inferred from two inputs, one per
+/// line, see [`Schema::diff`]
+fn run_diff(cli: &Cli, old_path: &Path, new_path: &Path) -> Result<()> {
+    let options = cli.generator_options();
+    let infer = |path: &Path| -> Result<Schema> {
+        let content = fs::read_to_string(path)?;
+        parser::parse(&content, cli.detect_format_of(path), &options)
+    };
+    let changes = infer(old_path)?.diff(&infer(new_path)?);
+
+    if changes.is_empty() {
+        println!("No schema changes");
+    }
+    for change in changes {
+        println!("{}", change);
+    }
+    Ok(())
+}
+
+/// Infer the schema of the inputs, merged if there are several, and
+/// generate code from it
+fn convert(cli: &Cli, inputs: &[(String, InputFormat)], start: Instant) -> Result<Conversion> {
+    let inputs: Vec<(&str, InputFormat)> = inputs
+        .iter()
+        .map(|(content, format)| (content.as_str(), *format))
+        .collect();
+    let input_size = inputs.iter().map(|(content, _)| content.len()).sum();
+    let output_format = cli.output_format();
+
+    // Create generator options
+    let mut options = cli.generator_options();
+    options.descriptions = cli.read_descriptions()?;
+
+    // Reuse the schema inferred by an earlier run on the same input, if cached
+    let cache = cli.cache_dir.as_ref().map(|dir| {
+        let key = SchemaCache::key(&inputs, &options);
+        (SchemaCache::new(dir), key)
+    });
+    let cached = cache.as_ref().and_then(|(cache, key)| cache.load(key));
+    let cache_hit = cached.is_some();
+    if cache.is_some() {
+        debug!("schema cache hit={}", cache_hit);
+    }
+
+    // Parse input to AST based on input format
+    let mut schema = match cached {
+        Some(schema) => schema,
+        None => parser::parse_samples(&inputs, &options)?,
+    };
+
+    // The cache keeps the inferred schema, before any overrides. It only
+    // saves time, so failing to write it does not fail the conversion
+    let mut cache_warning = None;
+    if let Some((cache, key)) = cache.as_ref().filter(|_| !cache_hit) {
+        if let Err(e) = cache.store(key, &schema) {
+            cache_warning = Some(format!("Could not write the schema cache: {}", e));
+        }
+    }
+    schema.apply_overrides(&cli.read_schema_overrides()?)?;
+    if let Some(name) = &cli.only_type {
+        schema.retain_type(name)?;
+    }
+    if cli.topological_order {
+        schema.topological_sort();
+    }
+    // Commands are read from stdin, so the input must come from a file
+    if cli.interactive && !explore::explore(&mut schema, io::stdin().lock(), io::stderr())? {
+        return Err(AlchemistError::Cancelled);
+    }
+    schema.validate()?;
+
+    // Fields that fell back to Any, reported by path
+    let any_paths = schema.any_field_paths();
+    if cli.fail_on_any && !any_paths.is_empty() {
+        return Err(AlchemistError::AnyFields { paths: any_paths });
+    }
+
+    // How many fields a uniform Rust rename covers, shown in the report
+    let rename_coverage = match (output_format, options.shared.rename_all) {
+        (OutputFormat::Rust, Some(rename_all)) => Some((
+            rename_all,
+            RustGenerator::new(options.rust.clone(), options.shared.clone())
+                .rename_coverage(&schema),
+        )),
+        _ => None,
+    };
+
+    let generator = generators::for_format(output_format, options);
+    let output = generator.generate(&schema)?;
+    debug!(
+        "code generated generator={} bytes={}",
+        generator.name(),
+        output.len()
+    );
+
+    // Type-check Rust output when asked, noting when that was not possible
+    let mut verify_warning = None;
+    if cli.verify_compile {
+        if output_format != OutputFormat::Rust {
+            verify_warning = Some("--verify-compile only checks Rust output".to_string());
+        } else if !verify::verify_rust(&output)? {
+            verify_warning =
+                Some("rustc was not found; generated code was not verified".to_string());
+        }
+    }
+
+    // Calculate statistics
+    let stats = ConversionStats::from_schema(&schema, start.elapsed(), input_size, output.len());
+
+    let mut warnings = schema.warnings.clone();
+    warnings.extend(
+        any_paths
+            .iter()
+            .map(|path| format!("Field '{}' is typed as Any", path)),
+    );
+    warnings.extend(verify_warning);
+    warnings.extend(cache_warning);
+    let flattens = matches!(
+        output_format,
+        OutputFormat::Rust
+            | OutputFormat::Typescript
+            | OutputFormat::Zod
+            | OutputFormat::Python
+            | OutputFormat::Go
+    );
+    if cli.flatten && !flattens {
+        warnings.push("--flatten only applies to Rust, TypeScript, Zod, Python and Go".to_string());
+    }
+
+    Ok(Conversion {
+        format_label: format!("{} (.{})", generator.name(), generator.file_extension()),
+        extension: generator.file_extension(),
+        schema,
+        output,
+        stats,
+        rename_coverage,
+        warnings,
+    })
+}
@@ -6,20 +6,206 @@
 //! - Nested object handling with automatic type generation
 //! - Array type unification with optional field detection
 //! - Handles heterogeneous arrays by merging object schemas
-
-use crate::ast::{Field, FieldType, Schema, TypeDef};
+//!
+//! CSV rows are typed column by column and NDJSON records are collected,
+//! then both are inferred like a JSON array of objects. GraphQL SDL input
+//! declares its types instead, see the `graphql` submodule.
+
+use crate::ast::{
+    walk_field_type_mut, Constraints, DateTimeFormat, Field, FieldType, FieldTypeVisitor, Schema,
+    TypeDef, TypeOrigin,
+};
 use crate::error::{AlchemistError, Result};
+use crate::formats::InputFormat;
 use crate::generators::GeneratorOptions;
-use crate::utils::{to_pascal_case, to_safe_identifier};
+use crate::utils::{to_pascal_case, to_safe_identifier, to_singular};
+use clap::ValueEnum;
+use log::{debug, trace};
+use serde::Deserialize;
 use serde_json::Value as JsonValue;
 use serde_yaml::Value as YamlValue;
 use std::collections::{HashMap, HashSet};
 use toml::Value as TomlValue;
 
+mod graphql;
+
+/// How to type a field whose only observed value is `null`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NullOnlyType {
+    /// Type the field as `Any`
+    Any,
+    /// Type the field as `Any` and mark it optional
+    #[default]
+    OptionalAny,
+    /// Drop the field entirely
+    Skip,
+}
+
+/// How a field that is `null` in some samples and set in others is typed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NullPolicy {
+    /// Make the type nullable, leaving the field optional only if it is also
+    /// missing from some samples
+    #[default]
+    Auto,
+    /// Mark the field optional, e.g. `field?: T`
+    Optional,
+    /// Keep the field required with a nullable type, e.g. `field: T | null`
+    Nullable,
+    /// Mark the field optional with a nullable type
+    Both,
+}
+
+impl NullPolicy {
+    /// Apply the policy to a field that was `null` in some samples
+    fn apply(self, field: Field) -> Field {
+        let field = match self {
+            NullPolicy::Optional => field,
+            _ if field.field_type.is_nullable() => field,
+            NullPolicy::Auto | NullPolicy::Nullable | NullPolicy::Both => Field {
+                field_type: FieldType::Optional(Box::new(field.field_type)),
+                ..field
+            },
+        };
+        match self {
+            NullPolicy::Optional | NullPolicy::Both => field.optional(),
+            NullPolicy::Auto | NullPolicy::Nullable => field,
+        }
+    }
+}
+
+/// Type of arrays mixing primitives with objects or arrays
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MixedArrayRepr {
+    /// A union of the element kinds, e.g. `Vec<IntegerOrItem>`
+    #[default]
+    Union,
+    /// `Any` elements, e.g. `Vec<serde_json::Value>`
+    Any,
+}
+
+/// Order of the fields within each generated type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FieldOrdering {
+    /// Keep the order in which fields first appear in the input
+    #[default]
+    Source,
+    /// Sort fields alphabetically by their original name
+    Alpha,
+    /// Move required fields before optional ones, keeping source order otherwise
+    RequiredFirst,
+}
+
+impl FieldOrdering {
+    /// Reorder the fields of a type definition
+    fn apply(self, type_def: &mut TypeDef) {
+        match self {
+            FieldOrdering::Source => {}
+            FieldOrdering::Alpha => type_def.fields.sort_by(|a, b| a.name.cmp(&b.name)),
+            // Stable sort, so each group keeps its source order
+            FieldOrdering::RequiredFirst => type_def.fields.sort_by_key(|f| f.optional),
+        }
+    }
+}
+
+/// Parse input in the given format into Schema AST
+pub fn parse(input: &str, format: InputFormat, options: &GeneratorOptions) -> Result<Schema> {
+    debug!("parse format={} bytes={}", format, input.len());
+    match format {
+        InputFormat::Json => parse_json(input, options),
+        InputFormat::Yaml => parse_yaml(input, options),
+        InputFormat::Toml => parse_toml(input, options),
+        InputFormat::Graphql => parse_graphql(input, options),
+        InputFormat::Csv => parse_csv(input, options),
+        InputFormat::Ndjson => parse_ndjson(input, options),
+    }
+}
+
+/// Parse several samples of the same data, each in its own format, into
+/// one Schema AST
+///
+/// Root objects are merged like the objects of an array, so a field missing
+/// from some samples is optional, and so are the objects nested in them;
+/// root arrays are concatenated. Samples must all have the same root shape.
+/// GraphQL SDL declares rather than samples its types, so it can only be
+/// parsed on its own.
+pub fn parse_samples(inputs: &[(&str, InputFormat)], options: &GeneratorOptions) -> Result<Schema> {
+    if let [(input, format)] = inputs {
+        return parse(input, *format, options);
+    }
+    debug!("parse samples count={}", inputs.len());
+
+    let values = inputs
+        .iter()
+        .map(|(input, format)| match format {
+            InputFormat::Json => Ok(serde_json::from_str(input)?),
+            InputFormat::Yaml => yaml_to_json_value(serde_yaml::from_str(input)?),
+            InputFormat::Toml => toml_to_json_value(
+                toml::from_str(input)
+                    .map_err(|e| AlchemistError::InvalidStructure(e.to_string()))?,
+            ),
+            InputFormat::Csv => csv_to_json_value(input),
+            InputFormat::Ndjson => ndjson_to_json_value(input),
+            InputFormat::Graphql => Err(AlchemistError::InvalidStructure(
+                "GraphQL schemas cannot be merged with other inputs".to_string(),
+            )),
+        })
+        .collect::<Result<Vec<JsonValue>>>()?;
+
+    let shape = |value: &JsonValue| match value {
+        JsonValue::Object(_) => "an object",
+        JsonValue::Array(_) => "an array",
+        _ => "neither an object nor an array",
+    };
+    if let Some(index) = values.iter().position(|v| shape(v) != shape(&values[0])) {
+        return Err(AlchemistError::InvalidStructure(format!(
+            "Cannot merge inputs: the root of input 1 is {} but the root of input {} is {}",
+            shape(&values[0]),
+            index + 1,
+            shape(&values[index])
+        )));
+    }
+
+    let mut context = InferenceContext::new(options);
+    for value in &values {
+        context.check_nesting(value)?;
+    }
+    match values.first() {
+        Some(JsonValue::Object(_)) => {
+            let objects: Vec<&serde_json::Map<String, JsonValue>> =
+                values.iter().filter_map(JsonValue::as_object).collect();
+            let root_name = context.root_name.clone();
+            context.used_names.insert(root_name.clone());
+            let type_def = build_sample_type_def(&root_name, &objects, &mut context)?;
+            context.types.insert(0, type_def);
+        }
+        Some(JsonValue::Array(_)) => {
+            let items = values
+                .into_iter()
+                .flat_map(|value| match value {
+                    JsonValue::Array(items) => items,
+                    _ => Vec::new(),
+                })
+                .collect();
+            infer_schema(&JsonValue::Array(items), &mut context)?;
+        }
+        _ => {
+            return Err(AlchemistError::InvalidStructure(
+                "Root must be an object or array".to_string(),
+            ));
+        }
+    }
+    Ok(context.into_schema())
+}
+
 /// Parse JSON string into Schema AST
 pub fn parse_json(input: &str, options: &GeneratorOptions) -> Result<Schema> {
     let value: JsonValue = serde_json::from_str(input)?;
-    let mut context = InferenceContext::new(&options.root_name);
+    let mut context = InferenceContext::new(options);
     infer_schema(&value, &mut context)?;
     Ok(context.into_schema())
 }
@@ -28,21 +214,193 @@ pub fn parse_json(input: &str, options: &GeneratorOptions) -> Result<Schema> {
 pub fn parse_yaml(input: &str, options: &GeneratorOptions) -> Result<Schema> {
     let value: YamlValue = serde_yaml::from_str(input)?;
     let json_value = yaml_to_json_value(value)?;
-    let mut context = InferenceContext::new(&options.root_name);
+    let mut context = InferenceContext::new(options);
     infer_schema(&json_value, &mut context)?;
     Ok(context.into_schema())
 }
 
+/// Parse GraphQL SDL type definitions into Schema AST
+///
+/// Types come from the definitions rather than inferred from data, see
+/// `graphql::parse_sdl`.
+pub fn parse_graphql(input: &str, options: &GeneratorOptions) -> Result<Schema> {
+    graphql::parse_sdl(input, options)
+}
+
 /// Parse TOML string into Schema AST
 pub fn parse_toml(input: &str, options: &GeneratorOptions) -> Result<Schema> {
     let value: TomlValue =
         toml::from_str(input).map_err(|e| AlchemistError::InvalidStructure(e.to_string()))?;
     let json_value = toml_to_json_value(value)?;
-    let mut context = InferenceContext::new(&options.root_name);
+    let mut context = InferenceContext::new(options);
     infer_schema(&json_value, &mut context)?;
     Ok(context.into_schema())
 }
 
+/// Parse CSV with a header row into Schema AST
+///
+/// Rows become objects keyed by the header and are typed like a JSON array
+/// of objects. Each column gets a single type from all of its cells, see
+/// `CsvColumn`. Empty cells are left out of their row, so a column with any
+/// is an optional field.
+pub fn parse_csv(input: &str, options: &GeneratorOptions) -> Result<Schema> {
+    let mut context = InferenceContext::new(options);
+    infer_schema(&csv_to_json_value(input)?, &mut context)?;
+    Ok(context.into_schema())
+}
+
+/// Convert CSV rows to a JSON array of objects, see `parse_csv`
+fn csv_to_json_value(input: &str) -> Result<JsonValue> {
+    let csv_error = |e: csv::Error| AlchemistError::InvalidStructure(format!("CSV: {}", e));
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(input.as_bytes());
+    let headers = reader.headers().map_err(csv_error)?.clone();
+    let rows: Vec<csv::StringRecord> = reader
+        .records()
+        .collect::<std::result::Result<_, _>>()
+        .map_err(csv_error)?;
+
+    if let Some(row) = rows.iter().find(|row| row.len() > headers.len()) {
+        return Err(AlchemistError::InvalidStructure(format!(
+            "CSV row on line {} has {} cells but the header has {}",
+            row.position().map_or(0, |p| p.line()),
+            row.len(),
+            headers.len()
+        )));
+    }
+
+    let columns: Vec<Option<CsvColumn>> = (0..headers.len())
+        .map(|i| {
+            rows.iter()
+                .filter_map(|row| row.get(i).filter(|cell| !cell.is_empty()))
+                .map(CsvColumn::of_cell)
+                .reduce(CsvColumn::widen)
+        })
+        .collect();
+
+    // A header without rows still declares its columns, with no values
+    let rows = if rows.is_empty() {
+        vec![csv::StringRecord::new()]
+    } else {
+        rows
+    };
+    let objects: Vec<JsonValue> = rows
+        .iter()
+        .map(|row| {
+            let object = headers
+                .iter()
+                .zip(&columns)
+                .enumerate()
+                .filter_map(|(i, (header, column))| {
+                    let value = match (column, row.get(i).filter(|cell| !cell.is_empty())) {
+                        (Some(column), Some(cell)) => column.value(cell),
+                        (Some(_), None) => return None,
+                        // No cell of the column has a value
+                        (None, _) => JsonValue::Null,
+                    };
+                    Some((header.to_string(), value))
+                })
+                .collect();
+            JsonValue::Object(object)
+        })
+        .collect();
+
+    Ok(JsonValue::Array(objects))
+}
+
+/// Parse newline-delimited JSON (JSON Lines) into Schema AST
+///
+/// Each non-empty line is one object. The records are typed like a JSON
+/// array of objects, so a field missing from some lines is optional.
+pub fn parse_ndjson(input: &str, options: &GeneratorOptions) -> Result<Schema> {
+    let mut context = InferenceContext::new(options);
+    infer_schema(&ndjson_to_json_value(input)?, &mut context)?;
+    Ok(context.into_schema())
+}
+
+/// Collect NDJSON records into a JSON array, see `parse_ndjson`
+fn ndjson_to_json_value(input: &str) -> Result<JsonValue> {
+    let mut records = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: JsonValue = serde_json::from_str(line).map_err(|e| {
+            // The error counts lines within the record, which is always line 1
+            let message = e.to_string();
+            let position = format!(" at line {} column {}", e.line(), e.column());
+            AlchemistError::InvalidStructure(format!(
+                "NDJSON line {}, column {}: {}",
+                line_number,
+                e.column(),
+                message.strip_suffix(&position).unwrap_or(&message)
+            ))
+        })?;
+        if !record.is_object() {
+            return Err(AlchemistError::InvalidStructure(format!(
+                "NDJSON line {} is not an object",
+                line_number
+            )));
+        }
+        records.push(record);
+    }
+    Ok(JsonValue::Array(records))
+}
+
+/// Type of a CSV column, the narrowest that fits all of its non-empty cells
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CsvColumn {
+    /// `true` or `false`, in any case
+    Boolean,
+    /// Whole numbers, without leading zeros that would be lost, e.g. zip codes
+    Integer,
+    /// Numbers, some of them fractional
+    Float,
+    /// Anything else
+    String,
+}
+
+impl CsvColumn {
+    fn of_cell(cell: &str) -> Self {
+        let digits = cell.strip_prefix(['-', '+']).unwrap_or(cell);
+        if cell.eq_ignore_ascii_case("true") || cell.eq_ignore_ascii_case("false") {
+            CsvColumn::Boolean
+        } else if digits.starts_with('0') && digits[1..].starts_with(|c: char| c.is_ascii_digit()) {
+            CsvColumn::String
+        } else if cell.parse::<i64>().is_ok() {
+            CsvColumn::Integer
+        } else if cell.parse::<f64>().is_ok_and(f64::is_finite) {
+            CsvColumn::Float
+        } else {
+            CsvColumn::String
+        }
+    }
+
+    /// The narrowest type holding the cells of both
+    fn widen(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (CsvColumn::Integer | CsvColumn::Float, CsvColumn::Integer | CsvColumn::Float) => {
+                CsvColumn::Float
+            }
+            _ => CsvColumn::String,
+        }
+    }
+
+    /// A cell as a JSON value of the column's type
+    fn value(self, cell: &str) -> JsonValue {
+        match self {
+            CsvColumn::Boolean => JsonValue::Bool(cell.eq_ignore_ascii_case("true")),
+            CsvColumn::Integer => cell.parse::<i64>().map_or(JsonValue::Null, JsonValue::from),
+            CsvColumn::Float => cell.parse::<f64>().map_or(JsonValue::Null, JsonValue::from),
+            CsvColumn::String => JsonValue::String(cell.to_string()),
+        }
+    }
+}
+
 /// Convert YAML value to JSON value for unified processing
 fn yaml_to_json_value(yaml: YamlValue) -> Result<JsonValue> {
     let json_str = serde_json::to_string(&yaml)
@@ -51,10 +409,32 @@ fn yaml_to_json_value(yaml: YamlValue) -> Result<JsonValue> {
 }
 
 /// Convert TOML value to JSON value
+///
+/// Converted directly rather than through serde, which would turn datetimes
+/// into `{"$__toml_private_datetime": ...}` objects.
 fn toml_to_json_value(toml: TomlValue) -> Result<JsonValue> {
-    let json_str = serde_json::to_string(&toml)
-        .map_err(|e| AlchemistError::InvalidStructure(e.to_string()))?;
-    serde_json::from_str(&json_str).map_err(AlchemistError::from)
+    Ok(match toml {
+        TomlValue::String(s) => JsonValue::String(s),
+        TomlValue::Integer(i) => JsonValue::from(i),
+        // NaN and infinity have no JSON representation
+        TomlValue::Float(f) => serde_json::Number::from_f64(f)
+            .map(JsonValue::Number)
+            .unwrap_or(JsonValue::Null),
+        TomlValue::Boolean(b) => JsonValue::Bool(b),
+        TomlValue::Datetime(datetime) => JsonValue::String(datetime.to_string()),
+        TomlValue::Array(array) => JsonValue::Array(
+            array
+                .into_iter()
+                .map(toml_to_json_value)
+                .collect::<Result<_>>()?,
+        ),
+        TomlValue::Table(table) => JsonValue::Object(
+            table
+                .into_iter()
+                .map(|(key, value)| Ok((key, toml_to_json_value(value)?)))
+                .collect::<Result<_>>()?,
+        ),
+    })
 }
 
 /// Context for type inference, tracks generated types and naming
@@ -62,21 +442,123 @@ fn toml_to_json_value(toml: TomlValue) -> Result<JsonValue> {
 struct InferenceContext {
     /// Root type name
     root_name: String,
+    /// Name of the synthetic type wrapping a root array, if set explicitly
+    wrapper_name: Option<String>,
+    /// Whether the element type of a root array of objects becomes the root
+    item_as_root: bool,
+    /// Whether a root array is emitted as a type alias, which with
+    /// `item_as_root` is an extra `{Root}List` type
+    flatten: bool,
+    /// Whether types below the root get short numbered names
+    minify_identifiers: bool,
+    /// Whether every type gets an all-optional `NamePartial` copy
+    emit_partial: bool,
+    /// Maximum number of fields allowed on a single type
+    max_fields: Option<usize>,
+    /// Treatment of fields that are only ever `null`
+    null_only_type: NullOnlyType,
+    /// Treatment of fields that are `null` in some samples
+    null_policy: NullPolicy,
+    /// Type of arrays mixing primitives with objects or arrays
+    mixed_array_repr: MixedArrayRepr,
+    /// Order of the fields within each type
+    field_ordering: FieldOrdering,
+    /// Whether generators will make every field optional
+    optional_fields: bool,
+    /// Whether fields missing from some samples become optional
+    optional_inference: bool,
+    /// Field docs keyed by dotted field path
+    descriptions: HashMap<String, String>,
+    /// Patterns of numeric fields that become `Decimal`
+    money_fields: Vec<String>,
+    /// Whether integers in timestamp-named fields become `DateTime`
+    epoch_dates: bool,
+    /// Whether ISO 8601 date and date-time strings become `DateTime`
+    iso_dates: bool,
+    /// Whether canonical UUID strings become `Uuid`
+    uuids: bool,
+    /// Most distinct values a merged string field can have to become an `Enum`
+    enum_threshold: usize,
+    /// Whether maps whose keys are all integers get `Integer` keys
+    typed_map_keys: bool,
+    /// Whether plural fields of distinct strings or integers become sets
+    detect_sets: bool,
+    /// Whether array elements and map values are named in the singular
+    singular_names: bool,
+    /// Whether fields record the number ranges and string lengths seen
+    infer_constraints: bool,
+    /// Maximum nesting depth of generated types below the root
+    max_depth: Option<usize>,
+    /// Deepest nesting of arrays and objects accepted in the input
+    recursion_limit: usize,
+    /// Largest union kept before it collapses to `Any`
+    max_union_members: usize,
+    /// Field whose value splits arrays of objects into tagged union variants
+    tagged_union: Option<String>,
+    /// Whether types with identical fields are generated once and shared
+    share_types: bool,
+    /// Number of types currently being built, i.e. the current nesting depth
+    depth: usize,
+    /// Number of nested values replaced by `Any` because of `max_depth`
+    truncated_subtrees: usize,
     /// All generated type definitions
     types: Vec<TypeDef>,
     /// Tracks used type names to avoid collisions
     used_names: HashSet<String>,
     /// Counter for generating unique names
     name_counter: HashMap<String, usize>,
+    /// Names of the types generated so far, keyed by their field structure
+    structures: HashMap<String, String>,
+    /// Notes about lossy or uncertain inference decisions
+    warnings: Vec<String>,
 }
 
 impl InferenceContext {
-    fn new(root_name: &str) -> Self {
+    fn new(options: &GeneratorOptions) -> Self {
         Self {
-            root_name: root_name.to_string(),
+            root_name: options.root_name.clone(),
+            wrapper_name: options.wrapper_name.clone(),
+            item_as_root: options.item_as_root,
+            flatten: options.shared.flatten,
+            minify_identifiers: options.minify_identifiers,
+            emit_partial: options.emit_partial,
+            max_fields: options.max_fields,
+            // Without optional inference, null only ever makes types nullable
+            null_only_type: match options.null_only_type {
+                NullOnlyType::OptionalAny if !options.optional_inference => NullOnlyType::Any,
+                null_only_type => null_only_type,
+            },
+            null_policy: if options.optional_inference {
+                options.null_policy
+            } else {
+                NullPolicy::Nullable
+            },
+            mixed_array_repr: options.mixed_array_repr,
+            field_ordering: options.field_ordering,
+            optional_fields: options.shared.optional_fields,
+            optional_inference: options.optional_inference,
+            descriptions: options.descriptions.clone(),
+            money_fields: options.money_fields.clone(),
+            epoch_dates: options.epoch_dates,
+            iso_dates: options.iso_dates,
+            uuids: options.uuids,
+            enum_threshold: options.enum_threshold,
+            typed_map_keys: options.typed_map_keys,
+            detect_sets: options.detect_sets,
+            singular_names: options.singular_names,
+            infer_constraints: options.infer_constraints,
+            max_depth: options.max_depth,
+            recursion_limit: options.recursion_limit,
+            max_union_members: options.max_union_members,
+            tagged_union: options.tagged_union.clone(),
+            share_types: options.share_types,
+            depth: 0,
+            truncated_subtrees: 0,
             types: Vec::new(),
             used_names: HashSet::new(),
             name_counter: HashMap::new(),
+            structures: HashMap::new(),
+            warnings: Vec::new(),
         }
     }
 
@@ -96,38 +578,459 @@ impl InferenceContext {
         unique_name
     }
 
+    /// Give back a name from `generate_type_name` that ended up unused
+    fn release_type_name(&mut self, name: &str) {
+        self.used_names.remove(name);
+        // Hand out the number again if it was the last one taken
+        if let Some(counter) = self
+            .name_counter
+            .iter_mut()
+            .find(|(base, counter)| format!("{}{}", base, counter) == name)
+            .map(|(_, counter)| counter)
+        {
+            *counter -= 1;
+        }
+    }
+
+    /// Fail if a type would exceed the configured field limit
+    fn check_field_count(&self, type_name: &str, field_count: usize) -> Result<()> {
+        match self.max_fields {
+            Some(limit) if field_count > limit => Err(AlchemistError::TooManyFields {
+                type_name: type_name.to_string(),
+                field_count,
+                limit,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Date/time format of a string, when ISO dates are detected
+    fn iso_format(&self, text: &str) -> Option<DateTimeFormat> {
+        self.iso_dates
+            .then(|| DateTimeFormat::of_iso_string(text))
+            .flatten()
+    }
+
+    /// Date/time format shared by all string `values`, ignoring nulls
+    fn shared_iso_format<'a>(
+        &self,
+        mut values: impl Iterator<Item = &'a JsonValue>,
+    ) -> Option<DateTimeFormat> {
+        let mut shared = None;
+        values.try_for_each(|value| match value {
+            JsonValue::Null => Some(()),
+            JsonValue::String(text) => {
+                let format = self.iso_format(text)?;
+                (*shared.get_or_insert(format) == format).then_some(())
+            }
+            _ => None,
+        })?;
+        shared
+    }
+
+    /// Type of a single string: a date/time or UUID when detected, otherwise
+    /// a plain string
+    fn string_type(&self, text: &str) -> FieldType {
+        if let Some(format) = self.iso_format(text) {
+            FieldType::DateTime(format)
+        } else if self.uuids && is_uuid(text) {
+            FieldType::Uuid
+        } else {
+            FieldType::String
+        }
+    }
+
+    /// Type shared by all string `values`, ignoring nulls: a date/time or
+    /// UUID only if every value is one, otherwise a plain string
+    fn shared_string_type<'a>(
+        &self,
+        values: impl Iterator<Item = &'a JsonValue> + Clone,
+    ) -> FieldType {
+        if let Some(format) = self.shared_iso_format(values.clone()) {
+            return FieldType::DateTime(format);
+        }
+        let mut texts = values.filter(|value| !value.is_null()).peekable();
+        let all_uuids =
+            texts.peek().is_some() && texts.all(|value| value.as_str().is_some_and(is_uuid));
+        if self.uuids && all_uuids {
+            FieldType::Uuid
+        } else {
+            FieldType::String
+        }
+    }
+
+    /// Distinct values of a merged string field, if it looks like an enum
+    ///
+    /// That is when every non-null value is a short label, at least one value
+    /// repeats and there are no more than `enum_threshold` distinct values.
+    /// Values are kept in the order first seen.
+    fn string_enum<'a>(&self, values: impl Iterator<Item = &'a JsonValue>) -> Option<Vec<String>> {
+        fn is_label(text: &str) -> bool {
+            !text.is_empty()
+                && text.len() <= 64
+                && text
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | ' '))
+        }
+
+        let mut distinct: Vec<String> = Vec::new();
+        let mut count = 0;
+        for value in values {
+            match value {
+                JsonValue::Null => continue,
+                JsonValue::String(text) if is_label(text) => {
+                    count += 1;
+                    if !distinct.contains(text) {
+                        if distinct.len() == self.enum_threshold {
+                            return None;
+                        }
+                        distinct.push(text.clone());
+                    }
+                }
+                _ => return None,
+            }
+        }
+        (distinct.len() < count).then_some(distinct)
+    }
+
+    /// Fail if arrays and objects nest deeper than `recursion_limit`
+    ///
+    /// Inference recurses once per level, so this is checked up front, with
+    /// an explicit stack, rather than letting deep input overflow the call
+    /// stack. The JSON, YAML and TOML parsers already refuse input nested
+    /// about 128 levels deep, so for them only lower limits make a difference.
+    fn check_nesting(&self, value: &JsonValue) -> Result<()> {
+        let mut pending = vec![(value, 1)];
+        while let Some((value, depth)) = pending.pop() {
+            let children: Vec<&JsonValue> = match value {
+                JsonValue::Array(items) => items.iter().collect(),
+                JsonValue::Object(obj) => obj.values().collect(),
+                _ => continue,
+            };
+            if depth > self.recursion_limit {
+                return Err(AlchemistError::InvalidStructure(format!(
+                    "Input nests arrays and objects more than {} levels deep (--recursion-limit)",
+                    self.recursion_limit
+                )));
+            }
+            pending.extend(children.into_iter().map(|child| (child, depth + 1)));
+        }
+        Ok(())
+    }
+
+    /// Base name of the element type of an array field, in the singular
+    /// with `singular_names`, e.g. `servers: Vec<Server>`
+    fn element_name(&self, field_name: &str) -> String {
+        if self.singular_names {
+            to_singular(field_name)
+        } else {
+            field_name.to_string()
+        }
+    }
+
+    /// Whether a nested type at the current depth would exceed `max_depth`
+    fn exceeds_max_depth(&self) -> bool {
+        self.max_depth.is_some_and(|limit| self.depth > limit)
+    }
+
+    /// Record a warning about an uncertain inference decision
+    fn warn(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        debug!("warning message={:?}", message);
+        self.warnings.push(message);
+    }
+
     /// Add a type definition to the context
     fn add_type(&mut self, type_def: TypeDef) {
+        debug!(
+            "type created name={} fields={} depth={}",
+            type_def.name,
+            type_def.fields.len(),
+            self.depth
+        );
         self.types.push(type_def);
     }
 
+    /// Add a type definition unless `share_types` is on and one with the
+    /// same fields and origin exists, and return the name to reference it by
+    ///
+    /// A duplicate's nested types are duplicates too, so dropping it leaves
+    /// nothing unreachable behind; only its reserved name is given back.
+    fn intern_type(&mut self, type_def: TypeDef) -> String {
+        if !self.share_types {
+            let name = type_def.name.clone();
+            self.add_type(type_def);
+            return name;
+        }
+
+        let key = structure_key(&type_def);
+        if let Some(existing) = self.structures.get(&key).cloned() {
+            debug!("type shared name={} as={}", type_def.name, existing);
+            self.release_type_name(&type_def.name);
+            return existing;
+        }
+
+        let name = type_def.name.clone();
+        self.structures.insert(key, name.clone());
+        self.add_type(type_def);
+        name
+    }
+
     /// Build the final schema from the context
+    ///
+    /// Types left behind by speculative inference are dropped here.
     fn into_schema(self) -> Schema {
         // Use Schema::new and add_type to resolve "unused method" warnings
         let mut schema = Schema::new(self.root_name);
-        for type_def in self.types {
+        for mut type_def in self.types {
+            self.field_ordering.apply(&mut type_def);
             schema.add_type(type_def);
         }
+        let union_warnings = cap_union_members(&mut schema, self.max_union_members);
+        schema.prune_unreachable();
+        if self.minify_identifiers {
+            minify_type_names(&mut schema);
+        }
+        apply_money_fields(&mut schema, &self.money_fields);
+        schema.describe_fields(&self.descriptions);
+        if self.emit_partial {
+            schema.add_partial_types();
+        }
+        schema.warnings = self.warnings;
+        schema.warnings.extend(union_warnings);
+        debug!(
+            "schema built root={} types={}",
+            schema.root_name,
+            schema.types.len()
+        );
+        if self.truncated_subtrees > 0 {
+            schema.warnings.push(format!(
+                "Truncated {} subtree(s) nested deeper than --max-depth {}; typed as Any",
+                self.truncated_subtrees,
+                self.max_depth.unwrap_or_default()
+            ));
+        }
+        if self.optional_fields {
+            let required = schema
+                .types
+                .iter()
+                .flat_map(|t| &t.fields)
+                .filter(|f| !f.optional)
+                .count();
+            if required > 0 {
+                schema.warnings.push(format!(
+                    "--optional-fields made {} field(s) optional that were present in every sample",
+                    required
+                ));
+            }
+        }
         schema
     }
 }
 
+/// Field structure of a type, equal for types that differ only in name,
+/// doc or field order
+///
+/// The origin is part of it, since generators treat types of different
+/// origins differently, e.g. `--deny-unknown` skips merged array elements.
+fn structure_key(type_def: &TypeDef) -> String {
+    let mut fields: Vec<String> = type_def
+        .fields
+        .iter()
+        .map(|field| format!("{:?}", field))
+        .collect();
+    fields.sort();
+    fields.insert(0, format!("origin {:?}", type_def.origin));
+    // A tagged union variant only stands in for another variant
+    if let Some(tag) = &type_def.tag {
+        fields.insert(0, format!("tag {:?}", tag));
+    }
+    fields.join("\n")
+}
+
+/// Replace the names of all types but the root with `T1`, `T2`, ... in
+/// declaration order
+///
+/// Field names are untouched, so the JSON keys stay the same.
+fn minify_type_names(schema: &mut Schema) {
+    let mut counter = 0;
+    let mut renames = HashMap::new();
+    for type_def in &schema.types {
+        if type_def.name == schema.root_name {
+            continue;
+        }
+        let name = loop {
+            counter += 1;
+            let name = format!("T{}", counter);
+            if name != schema.root_name {
+                break name;
+            }
+        };
+        renames.insert(type_def.name.clone(), name);
+    }
+    schema.rename_types(&renames);
+}
+
+/// Collapse unions of more than `limit` members to `Any`, returning a
+/// warning for every field affected
+pub(crate) fn cap_union_members(schema: &mut Schema, limit: usize) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for type_def in &mut schema.types {
+        for field in &mut type_def.fields {
+            let mut capper = UnionCapper { limit, largest: 0 };
+            capper.visit_field_type(&mut field.field_type);
+            if capper.largest > 0 {
+                debug!(
+                    "union collapsed field={}.{} members={} limit={}",
+                    type_def.name, field.name, capper.largest, limit
+                );
+                warnings.push(format!(
+                    "Field '{}.{}' had a union of {} members, over --max-union-members {}; typed as Any",
+                    type_def.name, field.name, capper.largest, limit
+                ));
+            }
+        }
+    }
+    warnings
+}
+
+/// Replaces unions over a size limit with `Any`
+struct UnionCapper {
+    limit: usize,
+    /// Size of the largest union replaced
+    largest: usize,
+}
+
+impl FieldTypeVisitor for UnionCapper {
+    fn visit_field_type(&mut self, field_type: &mut FieldType) {
+        match field_type {
+            FieldType::Union(members) if members.len() > self.limit => {
+                self.largest = self.largest.max(members.len());
+                *field_type = FieldType::Any;
+            }
+            _ => walk_field_type_mut(self, field_type),
+        }
+    }
+}
+
+/// Type the numeric fields matching any of `patterns` as `Decimal`
+fn apply_money_fields(schema: &mut Schema, patterns: &[String]) {
+    if patterns.is_empty() {
+        return;
+    }
+    let matched: Vec<(String, usize)> = schema
+        .field_paths()
+        .into_iter()
+        .filter(|(path, _, _)| {
+            patterns
+                .iter()
+                .any(|pattern| matches_field_pattern(pattern, path))
+        })
+        .map(|(_, name, index)| (name.to_string(), index))
+        .collect();
+
+    for (name, index) in matched {
+        if let Some(type_def) = schema.types.iter_mut().find(|t| t.name == name) {
+            let field = &mut type_def.fields[index];
+            if let Some(decimal) = field.field_type.to_decimal() {
+                field.field_type = decimal;
+            }
+        }
+    }
+}
+
+/// Whether a dotted field path matches a `--money-fields` pattern
+///
+/// `*` matches any run of characters, including dots. A pattern without a
+/// dot is matched against the field name alone, so `amount` matches the
+/// field at any depth.
+fn matches_field_pattern(pattern: &str, path: &str) -> bool {
+    let target = if pattern.contains('.') {
+        path
+    } else {
+        path.rsplit('.').next().unwrap_or(path)
+    };
+    glob_match(pattern.as_bytes(), target.as_bytes())
+}
+
+/// Match `text` against a pattern where `*` matches any run of characters
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((b'*', rest)) => (0..=text.len()).any(|skip| glob_match(rest, &text[skip..])),
+        Some((c, rest)) => text.first() == Some(c) && glob_match(rest, &text[1..]),
+    }
+}
+
 /// Main entry point for type inference
 ///
 /// Analyzes a JSON value recursively and produces a Schema AST.
 /// Handles all JSON types and generates appropriate type definitions.
 fn infer_schema(value: &JsonValue, context: &mut InferenceContext) -> Result<()> {
+    context.check_nesting(value)?;
     let root_name = context.root_name.clone();
 
     match value {
         JsonValue::Object(obj) => {
+            context.used_names.insert(root_name.clone());
             let type_def = infer_object_type(obj, &root_name, context)?;
             context.types.insert(0, type_def);
         }
+        JsonValue::Array(arr)
+            if context.item_as_root && !arr.is_empty() && arr.iter().all(JsonValue::is_object) =>
+        {
+            // The merged element type is the root, with no wrapper around it
+            match infer_array_element_type(arr, &root_name, context)? {
+                FieldType::Reference(item_name) => {
+                    if let Some(index) = context.types.iter().position(|t| t.name == item_name) {
+                        let item = context.types.remove(index);
+                        context.types.insert(0, item);
+                    }
+                    // The array itself only gets a name as an alias, e.g.
+                    // `type RootList = Vec<Root>`
+                    if context.flatten {
+                        let list_name = context.generate_type_name(&format!("{}List", item_name));
+                        let mut list = TypeDef::new(&list_name);
+                        list.origin = TypeOrigin::ArrayWrapper;
+                        list.add_field(Field::new(
+                            "items",
+                            FieldType::Array(Box::new(FieldType::Reference(item_name.clone()))),
+                        ));
+                        context.types.insert(1, list);
+                    }
+                    context.root_name = item_name;
+                }
+                // Elements typed without a type of their own, e.g. beyond
+                // --max-depth, keep the wrapper
+                item_type => {
+                    context.used_names.insert(root_name.clone());
+                    let mut wrapper = TypeDef::new(&root_name);
+                    wrapper.origin = TypeOrigin::ArrayWrapper;
+                    wrapper.add_field(Field::new("items", FieldType::Array(Box::new(item_type))));
+                    context.types.insert(0, wrapper);
+                }
+            }
+        }
         JsonValue::Array(arr) => {
+            if context.item_as_root {
+                context.warn(
+                    "--item-as-root needs a non-empty root array of objects; generated a wrapper type",
+                );
+            }
+
+            // With an explicit wrapper name the root name goes to the element type,
+            // otherwise the wrapper takes the root name and the element is "{Root}Item"
+            let (wrapper_name, item_name) = match context.wrapper_name.clone() {
+                Some(wrapper_name) => (wrapper_name, root_name),
+                None => (root_name.clone(), format!("{}Item", root_name)),
+            };
+            context.used_names.insert(wrapper_name.clone());
+            context.root_name = wrapper_name.clone();
+
             if arr.is_empty() {
                 // Empty array, create a simple wrapper
-                let mut wrapper = TypeDef::new(&root_name);
+                let mut wrapper = TypeDef::new(&wrapper_name);
+                wrapper.origin = TypeOrigin::ArrayWrapper;
                 wrapper.add_field(Field::new(
                     "items",
                     FieldType::Array(Box::new(FieldType::Any)),
@@ -135,12 +1038,12 @@ fn infer_schema(value: &JsonValue, context: &mut InferenceContext) -> Result<()>
                 context.types.insert(0, wrapper);
             } else {
                 // Infer the array element type
-                let item_type =
-                    infer_array_element_type(arr, &format!("{}Item", root_name), context)?;
+                let item_type = infer_array_element_type(arr, &item_name, context)?;
 
                 // If it's a reference type, we already have the type definition
                 // Create a wrapper or just use the array type depending on context
-                let mut wrapper = TypeDef::new(&root_name);
+                let mut wrapper = TypeDef::new(&wrapper_name);
+                wrapper.origin = TypeOrigin::ArrayWrapper;
                 wrapper.add_field(Field::new("items", FieldType::Array(Box::new(item_type))));
                 context.types.insert(0, wrapper);
             }
@@ -164,23 +1067,47 @@ fn infer_value_type(
     field_name: &str,
     context: &mut InferenceContext,
 ) -> Result<FieldType> {
+    // Stop descending instead of generating another level of nested types
+    if context.exceeds_max_depth() && contains_object(value) {
+        trace!(
+            "subtree truncated field={} depth={}",
+            field_name,
+            context.depth
+        );
+        context.truncated_subtrees += 1;
+        return Ok(FieldType::Any);
+    }
+
     match value {
         JsonValue::Null => Ok(FieldType::Null),
         JsonValue::Bool(_) => Ok(FieldType::Boolean),
         JsonValue::Number(n) => {
             // Distinguish between integers and floats
             if n.is_i64() || n.is_u64() {
+                if context.epoch_dates && is_timestamp_name(field_name) {
+                    if let Some(format) = n.as_i64().and_then(epoch_format) {
+                        return Ok(FieldType::DateTime(format));
+                    }
+                }
                 Ok(FieldType::Integer)
             } else {
                 Ok(FieldType::Float)
             }
         }
-        JsonValue::String(_) => Ok(FieldType::String),
+        JsonValue::String(text) => Ok(context.string_type(text)),
         JsonValue::Array(arr) => {
             if arr.is_empty() {
+                context.warn(format!(
+                    "Field '{}' is an empty array; its element type is unknown (Any)",
+                    field_name
+                ));
                 Ok(FieldType::Array(Box::new(FieldType::Any)))
             } else {
-                let inner_type = infer_array_element_type(arr, field_name, context)?;
+                let inner_type =
+                    infer_array_element_type(arr, &context.element_name(field_name), context)?;
+                if context.detect_sets && is_set_like(field_name, arr, &inner_type) {
+                    return Ok(FieldType::Set(Box::new(inner_type)));
+                }
                 Ok(FieldType::Array(Box::new(inner_type)))
             }
         }
@@ -191,20 +1118,22 @@ fn infer_value_type(
             if obj.len() >= 20 {
                 let values: Vec<JsonValue> = obj.values().cloned().collect();
 
-                // Try to infer a single unified type for all values
-                // We use a temporary context or speculative generation?
-                // Actually, if we decide it's a Map, the "Item" type is valid and needed.
-                // If we reject Map, we might have generated an unused "Item" type in context.
-                // This generates a "dead type" in output, but correctness is preserved.
-                // Given the high threshold, wasted types are rare.
+                // Try to infer a single unified type for all values.
+                // If we decide it's a Map, the "Item" type is valid and needed.
+                // If we reject the Map, any type generated while trying is unused
+                // and gets pruned when the schema is built.
 
-                let val_base_name = if field_name.ends_with('s') {
-                    &field_name[0..field_name.len() - 1]
+                let val_base_name = if context.singular_names {
+                    to_singular(field_name)
                 } else {
                     field_name
+                        .strip_suffix('s')
+                        .unwrap_or(field_name)
+                        .to_string()
                 };
 
-                let item_type_res = infer_array_element_type(&values, val_base_name, context);
+                let types_before = context.types.len();
+                let item_type_res = infer_array_element_type(&values, &val_base_name, context);
 
                 if let Ok(item_type) = item_type_res {
                     // Heuristic: If it's a Union, it's likely a mixed struct (name, age, etc).
@@ -212,22 +1141,100 @@ fn infer_value_type(
                     let is_union = matches!(item_type, FieldType::Union(_));
 
                     if !is_union {
-                        return Ok(FieldType::Map(
-                            Box::new(FieldType::String),
-                            Box::new(item_type),
-                        ));
+                        let key_type = if context.typed_map_keys
+                            && obj.keys().all(|key| key.parse::<i64>().is_ok())
+                        {
+                            FieldType::Integer
+                        } else {
+                            FieldType::String
+                        };
+                        debug!(
+                            "map detected field={} keys={} value={:?}",
+                            field_name,
+                            obj.len(),
+                            item_type
+                        );
+                        if let FieldType::Reference(name) = &item_type {
+                            let value_type = context.types[types_before..]
+                                .iter_mut()
+                                .find(|t| t.name == *name);
+                            if let Some(value_type) = value_type {
+                                value_type.origin = TypeOrigin::Map;
+                            }
+                        }
+                        return Ok(FieldType::Map(Box::new(key_type), Box::new(item_type)));
                     }
                 }
+
+                let unused: Vec<String> = context.types[types_before..]
+                    .iter()
+                    .map(|t| t.name.clone())
+                    .collect();
+                debug!(
+                    "map rejected field={} keys={} discarded={:?}",
+                    field_name,
+                    obj.len(),
+                    unused
+                );
+                // Later objects must not be interned as the discarded types
+                context.structures.retain(|_, name| !unused.contains(name));
+                if !unused.is_empty() {
+                    context.warn(format!(
+                        "Map detection for '{}' was rejected; discarded speculative type(s) {}",
+                        field_name,
+                        unused.join(", ")
+                    ));
+                }
             }
 
+            // Identical objects share one type instead of each getting a copy
             let type_name = context.generate_type_name(field_name);
             let type_def = infer_object_type(obj, &type_name, context)?;
-            context.add_type(type_def);
-            Ok(FieldType::Reference(type_name))
+            Ok(FieldType::Reference(context.intern_type(type_def)))
         }
     }
 }
 
+/// Whether a field name suggests it holds a point in time, e.g. `created_at`,
+/// `updatedAt` or `timestamp`
+fn is_timestamp_name(name: &str) -> bool {
+    name.ends_with("_at") || name.ends_with("At") || name.to_lowercase().ends_with("timestamp")
+}
+
+/// Whether a string is a UUID in the canonical 8-4-4-4-12 hex form, e.g.
+/// `550e8400-e29b-41d4-a716-446655440000`, in either case
+fn is_uuid(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    bytes.len() == 36
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            8 | 13 | 18 | 23 => *b == b'-',
+            _ => b.is_ascii_hexdigit(),
+        })
+}
+
+/// Classify an integer as an epoch timestamp if it falls between the years
+/// 2000 and 2100 in seconds or milliseconds
+fn epoch_format(value: i64) -> Option<DateTimeFormat> {
+    const SECONDS: std::ops::Range<i64> = 946_684_800..4_102_444_800;
+
+    if SECONDS.contains(&value) {
+        Some(DateTimeFormat::EpochSeconds)
+    } else if (SECONDS.start * 1000..SECONDS.end * 1000).contains(&value) {
+        Some(DateTimeFormat::EpochMillis)
+    } else {
+        None
+    }
+}
+
+/// Whether a value is or contains an object, i.e. would produce a nested type
+fn contains_object(value: &JsonValue) -> bool {
+    match value {
+        JsonValue::Object(_) => true,
+        JsonValue::Array(arr) => arr.iter().any(contains_object),
+        _ => false,
+    }
+}
+
 /// Infer the element type for an array
 ///
 /// This function handles the complex case of arrays with potentially
@@ -244,9 +1251,11 @@ fn infer_array_element_type(
         return Ok(FieldType::Any);
     }
 
+    trace!("array elements name={} count={}", base_name, arr.len());
+
     // Collect all element types for analysis
     let mut primitive_types: HashSet<&'static str> = HashSet::new();
-    let mut object_schemas: Vec<ObjectSchema> = Vec::new();
+    let mut objects: Vec<&serde_json::Map<String, JsonValue>> = Vec::new();
     let mut has_null = false;
     let mut has_array = false;
 
@@ -268,16 +1277,23 @@ fn infer_array_element_type(
             }
             JsonValue::Array(_) => has_array = true,
             JsonValue::Object(obj) => {
-                object_schemas.push(analyze_object_schema(obj));
+                objects.push(obj);
             }
         }
     }
 
+    // Strings are date/times or UUIDs only if every one of them is
+    let string_type = if primitive_types.contains("string") && primitive_types.len() == 1 {
+        context.shared_string_type(arr.iter())
+    } else {
+        FieldType::String
+    };
+
     // Case 1: All elements are the same primitive type
-    if object_schemas.is_empty() && !has_array && primitive_types.len() == 1 && !has_null {
+    if objects.is_empty() && !has_array && primitive_types.len() == 1 && !has_null {
         let ptype = primitive_types.into_iter().next().unwrap();
         return Ok(match ptype {
-            "string" => FieldType::String,
+            "string" => string_type,
             "boolean" => FieldType::Boolean,
             "integer" => FieldType::Integer,
             "float" => FieldType::Float,
@@ -286,10 +1302,10 @@ fn infer_array_element_type(
     }
 
     // Case 2: Primitives with null - make it optional
-    if object_schemas.is_empty() && !has_array && primitive_types.len() == 1 && has_null {
+    if objects.is_empty() && !has_array && primitive_types.len() == 1 && has_null {
         let ptype = primitive_types.into_iter().next().unwrap();
         let inner = match ptype {
-            "string" => FieldType::String,
+            "string" => string_type,
             "boolean" => FieldType::Boolean,
             "integer" => FieldType::Integer,
             "float" => FieldType::Float,
@@ -298,12 +1314,55 @@ fn infer_array_element_type(
         return Ok(FieldType::Optional(Box::new(inner)));
     }
 
+    // Case 3a: Objects told apart by the tag field - one type per tag value
+    let tagged_groups = context
+        .tagged_union
+        .clone()
+        .filter(|_| primitive_types.is_empty() && !has_array)
+        .and_then(|tag| group_by_tag(&objects, &tag).map(|groups| (tag, groups)));
+    if let Some((tag, groups)) = tagged_groups {
+        debug!(
+            "tagged union name={} tag={} variants={}",
+            base_name,
+            tag,
+            groups.len()
+        );
+        let mut variants = Vec::new();
+        for (value, group) in groups {
+            let name = to_pascal_case(value);
+            let type_name = if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                context.generate_type_name(&name)
+            } else {
+                context.generate_type_name(&format!("{} {}", base_name, value))
+            };
+            let mut type_def =
+                build_merged_type_def(&type_name, &merge_object_schemas(&group), context)?;
+            if let Some(field) = type_def.fields.iter_mut().find(|f| f.name == tag) {
+                field.field_type = FieldType::Enum(vec![value.to_string()]);
+                field.constraints = Constraints::default();
+            }
+            type_def.tag = Some(tag.clone());
+            variants.push(FieldType::Reference(context.intern_type(type_def)));
+        }
+
+        let union_type = FieldType::Union(variants);
+        if has_null {
+            return Ok(FieldType::Optional(Box::new(union_type)));
+        }
+        return Ok(union_type);
+    }
+
     // Case 3: All elements are objects - merge schemas
-    if !object_schemas.is_empty() && primitive_types.is_empty() && !has_array {
-        let merged = merge_object_schemas(&object_schemas);
+    if !objects.is_empty() && primitive_types.is_empty() && !has_array {
+        debug!(
+            "objects merged name={} objects={}",
+            base_name,
+            objects.len()
+        );
+        let merged = merge_object_schemas(&objects);
         let type_name = context.generate_type_name(base_name);
-        let type_def = build_merged_type_def(&type_name, &merged, arr, context)?;
-        context.add_type(type_def);
+        let type_def = build_merged_type_def(&type_name, &merged, context)?;
+        let type_name = context.intern_type(type_def);
 
         if has_null {
             return Ok(FieldType::Optional(Box::new(FieldType::Reference(
@@ -315,10 +1374,18 @@ fn infer_array_element_type(
 
     // Case 4: Mixed types - create a union
     if primitive_types.len() > 1
-        || (has_array && !object_schemas.is_empty())
-        || (!primitive_types.is_empty() && !object_schemas.is_empty())
+        || (has_array && !objects.is_empty())
+        || (!primitive_types.is_empty() && !objects.is_empty())
         || (has_array && !primitive_types.is_empty())
     {
+        let has_structured = has_array || !objects.is_empty();
+        if has_structured
+            && !primitive_types.is_empty()
+            && context.mixed_array_repr == MixedArrayRepr::Any
+        {
+            return Ok(FieldType::Any);
+        }
+
         let mut union_types = Vec::new();
 
         // Add primitive types sorted to ensure deterministic order (optional but good)
@@ -337,11 +1404,11 @@ fn infer_array_element_type(
         }
 
         // Add object type (merged)
-        if !object_schemas.is_empty() {
-            let merged = merge_object_schemas(&object_schemas);
+        if !objects.is_empty() {
+            let merged = merge_object_schemas(&objects);
             let type_name = context.generate_type_name(base_name);
-            let type_def = build_merged_type_def(&type_name, &merged, arr, context)?;
-            context.add_type(type_def);
+            let type_def = build_merged_type_def(&type_name, &merged, context)?;
+            let type_name = context.intern_type(type_def);
             union_types.push(FieldType::Reference(type_name));
         }
 
@@ -350,6 +1417,7 @@ fn infer_array_element_type(
             union_types.push(FieldType::Array(Box::new(FieldType::Any)));
         }
 
+        debug!("union created name={} members={:?}", base_name, union_types);
         let union_type = if union_types.len() == 1 {
             union_types.pop().unwrap()
         } else {
@@ -363,7 +1431,7 @@ fn infer_array_element_type(
     }
 
     // Case 5: Array of arrays (nested arrays)
-    if has_array && object_schemas.is_empty() && primitive_types.is_empty() {
+    if has_array && objects.is_empty() && primitive_types.is_empty() {
         // Recursively infer nested array type from first element
         if let Some(JsonValue::Array(inner_arr)) = arr.first() {
             let inner_type = infer_array_element_type(inner_arr, base_name, context)?;
@@ -371,82 +1439,110 @@ fn infer_array_element_type(
         }
     }
 
+    context.warn(format!(
+        "Could not unify the elements of '{}'; falling back to Any",
+        base_name
+    ));
     Ok(FieldType::Any)
 }
 
-/// Represents the schema of a single object for merging purposes
-#[derive(Debug, Clone)]
-struct ObjectSchema {
-    /// Field names present in this object
-    fields: HashSet<String>,
-}
-
-// ... JsonValueType removed/unused ... (Wait, merge_object_schemas needs it? No, it uses counts)
-// Actually ObjectSchema needs field_types? No, analyze_object_schema populated it but merge_object_schemas uses logic on field NAMES?
-// Let's check merge_object_schemas. It only uses schema.fields!
-// So field_types in ObjectSchema IS unused.
+/// A JSON object
+type JsonObject = serde_json::Map<String, JsonValue>;
 
-/// Analyze a single object and extract its schema
-fn analyze_object_schema(obj: &serde_json::Map<String, JsonValue>) -> ObjectSchema {
-    let mut fields = HashSet::new();
+/// Objects sharing one value of a tagged union's tag field
+type TagGroup<'a> = (&'a str, Vec<&'a JsonObject>);
 
-    for key in obj.keys() {
-        fields.insert(key.clone());
+/// Group objects by the string value of their tag field, in order of first
+/// appearance
+///
+/// `None` unless every object has a string tag and there are at least two
+/// distinct values, since a single variant is better off as a plain type.
+fn group_by_tag<'a>(objects: &[&'a JsonObject], tag: &str) -> Option<Vec<TagGroup<'a>>> {
+    let mut groups: Vec<TagGroup> = Vec::new();
+    for object in objects {
+        let value = object.get(tag)?.as_str()?;
+        match groups.iter_mut().find(|(seen, _)| *seen == value) {
+            Some((_, group)) => group.push(object),
+            None => groups.push((value, vec![object])),
+        }
     }
-
-    ObjectSchema { fields }
+    (groups.len() > 1).then_some(groups)
 }
 
-/// Merged schema representing the union of multiple object schemas
+/// Fields of the objects of an array, merged in a single pass
 #[derive(Debug)]
-struct MergedObjectSchema {
-    /// All field names across all objects
-    all_fields: HashSet<String>,
-    /// Fields that appear in ALL objects (required)
-    required_fields: HashSet<String>,
-    /// Fields that appear in SOME but not all objects (optional)
-    optional_fields: HashSet<String>,
+struct MergedObjectSchema<'a> {
+    /// All field names across all objects, in order of first appearance
+    all_fields: Vec<&'a str>,
+    /// Every value of each field, in array order
+    values: HashMap<&'a str, Vec<&'a JsonValue>>,
     /// Total number of objects merged
     total_objects: usize,
 }
 
-/// Merge multiple object schemas into a unified schema
-///
-/// This is the key function for handling heterogeneous arrays.
-/// It tracks which fields appear in all objects vs some objects.
-fn merge_object_schemas(schemas: &[ObjectSchema]) -> MergedObjectSchema {
-    let total_objects = schemas.len();
-    let mut all_fields: HashSet<String> = HashSet::new();
-    let mut field_counts: HashMap<String, usize> = HashMap::new();
+impl<'a> MergedObjectSchema<'a> {
+    /// The values of a field, one per object that has it
+    fn values_of(&self, field: &str) -> &[&'a JsonValue] {
+        self.values.get(field).map_or(&[], Vec::as_slice)
+    }
 
-    // Collect all fields and count occurrences
-    for schema in schemas {
-        for field in &schema.fields {
-            all_fields.insert(field.clone());
-            *field_counts.entry(field.clone()).or_insert(0) += 1;
-        }
+    /// Whether the field is missing from some of the objects
+    fn is_optional(&self, field: &str) -> bool {
+        self.values_of(field).len() < self.total_objects
     }
 
-    // Determine required vs optional fields
-    let mut required_fields = HashSet::new();
-    let mut optional_fields = HashSet::new();
+    /// Whether the field is `null` in at least one object
+    fn is_nullable(&self, field: &str) -> bool {
+        self.values_of(field).iter().any(|v| v.is_null())
+    }
 
-    // ... logic same ...
+    /// The first non-null value of the field, if any
+    fn sample(&self, field: &str) -> Option<&'a JsonValue> {
+        self.values_of(field).iter().copied().find(|v| !v.is_null())
+    }
+}
 
-    for field in &all_fields {
-        let count = field_counts.get(field).unwrap_or(&0);
-        if *count == total_objects {
-            required_fields.insert(field.clone());
-        } else {
-            optional_fields.insert(field.clone());
+/// Whether an array looks like a set: a plural field name and distinct
+/// string or integer elements, e.g. `"tags": ["a", "b"]`
+fn is_set_like(field_name: &str, arr: &[JsonValue], element: &FieldType) -> bool {
+    if to_singular(field_name) == field_name
+        || !matches!(
+            element,
+            FieldType::String | FieldType::Uuid | FieldType::Integer
+        )
+    {
+        return false;
+    }
+    let mut seen = HashSet::new();
+    arr.iter().all(|value| seen.insert(value.to_string()))
+}
+
+/// Merge the fields of several objects into a unified schema
+///
+/// This is the key function for handling heterogeneous arrays. Fields are
+/// collected in one pass over the objects, so wide objects merge in time
+/// linear in their total size; which fields appear in all objects and which
+/// only in some follows from how many values each one collected.
+fn merge_object_schemas<'a>(
+    objects: &[&'a serde_json::Map<String, JsonValue>],
+) -> MergedObjectSchema<'a> {
+    let mut all_fields = Vec::new();
+    let mut values: HashMap<&'a str, Vec<&'a JsonValue>> = HashMap::new();
+
+    for object in objects {
+        for (field, value) in object.iter() {
+            let field_values = values.entry(field.as_str()).or_default();
+            if field_values.is_empty() {
+                all_fields.push(field.as_str());
+            }
+            field_values.push(value);
         }
     }
 
     MergedObjectSchema {
         all_fields,
-        required_fields,
-        optional_fields,
-        total_objects,
+        values,
+        total_objects: objects.len(),
     }
 }
 
@@ -457,42 +1553,156 @@ fn merge_object_schemas(schemas: &[ObjectSchema]) -> MergedObjectSchema {
 fn build_merged_type_def(
     name: &str,
     merged: &MergedObjectSchema,
-    arr: &[JsonValue],
     context: &mut InferenceContext,
 ) -> Result<TypeDef> {
-    let mut type_def = TypeDef::new(name);
+    context.check_field_count(name, merged.all_fields.len())?;
 
-    // Validation using total_objects (silences unused warning)
-    if merged.total_objects == 0 {
-        return Ok(TypeDef::new(name));
-    }
-
-    // Process each field
-    for field_name in &merged.all_fields {
-        // Find the first object that has this field to infer type
-        let sample_value = arr
-            .iter()
-            .filter_map(|v| v.as_object())
-            .find_map(|obj| obj.get(field_name));
+    context.depth += 1;
+    let type_def = build_merged_fields(name, merged, false, context);
+    context.depth -= 1;
+    type_def
+}
 
-        let field_type = if let Some(value) = sample_value {
-            infer_value_type(value, field_name, context)?
-        } else {
-            FieldType::Any
-        };
+/// Build a TypeDef from the root objects of several samples
+///
+/// Unlike array elements, each sample stands for the whole object, so
+/// nested objects are merged across the samples too and arrays are typed
+/// from all of their elements. The result is typed like a single object.
+fn build_sample_type_def(
+    name: &str,
+    objects: &[&serde_json::Map<String, JsonValue>],
+    context: &mut InferenceContext,
+) -> Result<TypeDef> {
+    let merged = merge_object_schemas(objects);
+    context.check_field_count(name, merged.all_fields.len())?;
 
-        let is_optional = merged.optional_fields.contains(field_name);
-        let _is_required = merged.required_fields.contains(field_name);
+    context.depth += 1;
+    let type_def = build_merged_fields(name, &merged, true, context);
+    context.depth -= 1;
 
-        // Consistency check (activates unused field)
-        debug_assert!(
-            !(_is_required && is_optional),
-            "Field cannot be both required and optional"
-        );
+    let mut type_def = type_def?.with_doc(format!("Auto-generated {} type", name));
+    type_def.origin = TypeOrigin::Object;
+    Ok(type_def)
+}
 
-        let mut field = Field::new(field_name.clone(), field_type);
-        if is_optional {
-            field = field.optional();
+/// Type of a field from all of its values across samples, see
+/// `build_sample_type_def`
+///
+/// Returns `None` when the first value alone gives the type, i.e. unless
+/// there are several values and all of them are objects or all arrays.
+fn infer_sample_values_type(
+    values: &[&JsonValue],
+    field_name: &str,
+    context: &mut InferenceContext,
+) -> Result<Option<FieldType>> {
+    if values.len() < 2 || context.exceeds_max_depth() {
+        return Ok(None);
+    }
+
+    if let Some(objects) = values
+        .iter()
+        .map(|value| value.as_object())
+        .collect::<Option<Vec<_>>>()
+    {
+        let type_name = context.generate_type_name(field_name);
+        let type_def = build_sample_type_def(&type_name, &objects, context)?;
+        return Ok(Some(FieldType::Reference(context.intern_type(type_def))));
+    }
+
+    if values.iter().all(|value| value.is_array()) {
+        let items = values
+            .iter()
+            .filter_map(|value| value.as_array())
+            .flatten()
+            .cloned()
+            .collect();
+        return infer_value_type(&JsonValue::Array(items), field_name, context).map(Some);
+    }
+
+    Ok(None)
+}
+
+/// Infer the fields of a merged type, see `build_merged_type_def`
+///
+/// With `merge_samples`, the objects are samples rather than array elements,
+/// see `build_sample_type_def`.
+fn build_merged_fields(
+    name: &str,
+    merged: &MergedObjectSchema,
+    merge_samples: bool,
+    context: &mut InferenceContext,
+) -> Result<TypeDef> {
+    let mut type_def = TypeDef::new(name);
+    type_def.origin = TypeOrigin::MergedArray;
+
+    // Process each field
+    for &field_name in &merged.all_fields {
+        // Infer the type from the first non-null value of this field
+        let has_null = merged.is_nullable(field_name);
+        let sample_value = merged.sample(field_name).or_else(|| {
+            // Only nulls: the field is typed as null below
+            has_null.then_some(&JsonValue::Null)
+        });
+        let merged_type = if merge_samples {
+            let values: Vec<&JsonValue> = merged
+                .values_of(field_name)
+                .iter()
+                .copied()
+                .filter(|value| !value.is_null())
+                .collect();
+            infer_sample_values_type(&values, field_name, context)?
+        } else {
+            None
+        };
+
+        let mut field_type = match (merged_type, sample_value) {
+            (Some(field_type), _) => field_type,
+            (None, Some(value)) => infer_value_type(value, field_name, context)?,
+            (None, None) => FieldType::Any,
+        };
+        // A date/time or UUID sample only speaks for the field if every
+        // value agrees
+        if matches!(field_type, FieldType::DateTime(format) if !format.is_epoch())
+            || field_type == FieldType::Uuid
+        {
+            field_type = context.shared_string_type(merged.values_of(field_name).iter().copied());
+        }
+        if field_type == FieldType::String {
+            if let Some(values) = context.string_enum(merged.values_of(field_name).iter().copied())
+            {
+                debug!(
+                    "enum detected type={} field={} values={}",
+                    name,
+                    field_name,
+                    values.len()
+                );
+                field_type = FieldType::Enum(values);
+            }
+        }
+
+        let is_optional = context.optional_inference && merged.is_optional(field_name);
+
+        let mut field = Field::new(field_name, field_type);
+        if let FieldType::DateTime(format) = field.field_type {
+            field = field.with_doc(format.description());
+        }
+        if context.infer_constraints {
+            let samples = merged.values_of(field_name).iter().copied();
+            field.constraints = infer_constraints(&field.field_type, samples);
+        }
+        if is_optional {
+            field = field.optional();
+        }
+        if has_null && !matches!(field.field_type, FieldType::Null) {
+            field = context.null_policy.apply(field);
+        }
+
+        if matches!(field.field_type, FieldType::Null) {
+            context.warn(null_only_warning(name, field_name, context.null_only_type));
+            match resolve_null_only_field(field, context.null_only_type) {
+                Some(resolved) => field = resolved,
+                None => continue,
+            }
         }
 
         // Generate safe field name if needed
@@ -512,16 +1722,46 @@ fn infer_object_type(
     obj: &serde_json::Map<String, JsonValue>,
     name: &str,
     context: &mut InferenceContext,
+) -> Result<TypeDef> {
+    context.check_field_count(name, obj.len())?;
+
+    context.depth += 1;
+    let type_def = infer_object_fields(obj, name, context);
+    context.depth -= 1;
+    type_def
+}
+
+/// Infer the fields of a single object, see `infer_object_type`
+fn infer_object_fields(
+    obj: &serde_json::Map<String, JsonValue>,
+    name: &str,
+    context: &mut InferenceContext,
 ) -> Result<TypeDef> {
     let mut type_def = TypeDef::new(name).with_doc(format!("Auto-generated {} type", name));
 
     for (key, value) in obj {
         let field_type = infer_value_type(value, key, context)?;
+        trace!(
+            "field inferred type={} field={} as={:?}",
+            name,
+            key,
+            field_type
+        );
         let mut field = Field::new(key.clone(), field_type);
+        if let FieldType::DateTime(format) = field.field_type {
+            field = field.with_doc(format.description());
+        }
+        if context.infer_constraints {
+            field.constraints = infer_constraints(&field.field_type, [value]);
+        }
 
-        // Handle null values as optional
+        // A null value carries no type information
         if value.is_null() {
-            field = field.optional();
+            context.warn(null_only_warning(name, key, context.null_only_type));
+            match resolve_null_only_field(field, context.null_only_type) {
+                Some(resolved) => field = resolved,
+                None => continue,
+            }
         }
 
         // Generate safe field name if needed
@@ -536,9 +1776,76 @@ fn infer_object_type(
     Ok(type_def)
 }
 
+/// Bounds of a number or string field's samples: the smallest and largest
+/// number, or the shortest and longest string
+fn infer_constraints<'a>(
+    field_type: &FieldType,
+    samples: impl IntoIterator<Item = &'a JsonValue>,
+) -> Constraints {
+    fn widen<T: PartialOrd + Copy>(bounds: (&mut Option<T>, &mut Option<T>), value: T) {
+        let (min, max) = bounds;
+        if min.is_none_or(|min| value < min) {
+            *min = Some(value);
+        }
+        if max.is_none_or(|max| value > max) {
+            *max = Some(value);
+        }
+    }
+
+    let mut constraints = Constraints::default();
+    for sample in samples {
+        match (field_type, sample) {
+            (FieldType::Integer | FieldType::Float, JsonValue::Number(n)) => {
+                if let Some(n) = n.as_f64() {
+                    widen((&mut constraints.min, &mut constraints.max), n);
+                }
+            }
+            (FieldType::String, JsonValue::String(s)) => {
+                let length = s.chars().count();
+                widen(
+                    (&mut constraints.min_length, &mut constraints.max_length),
+                    length,
+                );
+            }
+            _ => {}
+        }
+    }
+    constraints
+}
+
+/// Resolve a field whose only observed value is `null`
+///
+/// Returns `None` when the field should be dropped from its type.
+fn resolve_null_only_field(field: Field, mode: NullOnlyType) -> Option<Field> {
+    let field = Field {
+        field_type: FieldType::Any,
+        ..field
+    };
+
+    match mode {
+        NullOnlyType::Any => Some(field),
+        NullOnlyType::OptionalAny => Some(field.optional()),
+        NullOnlyType::Skip => None,
+    }
+}
+
+/// Describe how a null-only field was handled
+fn null_only_warning(type_name: &str, field_name: &str, mode: NullOnlyType) -> String {
+    let outcome = match mode {
+        NullOnlyType::Any => "typed as Any",
+        NullOnlyType::OptionalAny => "typed as optional Any",
+        NullOnlyType::Skip => "skipped",
+    };
+    format!(
+        "Field '{}.{}' is always null; {}",
+        type_name, field_name, outcome
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generators::SharedOptions;
 
     /// Helper to create default options for tests
     fn default_options() -> GeneratorOptions {
@@ -611,6 +1918,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_merge_wide_objects() {
+        // Every object has the shared fields plus one of its own; `late` is
+        // null until the last object
+        let objects: Vec<String> = (0..300)
+            .map(|i| {
+                let shared: Vec<String> = (0..300).map(|f| format!(r#""f{}": {}"#, f, i)).collect();
+                let late = if i == 299 { r#""x""# } else { "null" };
+                format!(
+                    r#"{{{}, "own{}": true, "late": {}}}"#,
+                    shared.join(", "),
+                    i,
+                    late
+                )
+            })
+            .collect();
+        let json = format!("[{}]", objects.join(", "));
+        let schema = parse_json(&json, &default_options()).unwrap();
+
+        let item = schema.types.iter().find(|t| t.name == "RootItem").unwrap();
+        assert_eq!(item.fields.len(), 300 + 300 + 1);
+        assert!(item.fields[..300]
+            .iter()
+            .all(|f| !f.optional && f.field_type == FieldType::Integer));
+        assert_eq!(item.fields[300].name, "own0");
+        assert!(item.fields[300].optional);
+        let late = item.fields.iter().find(|f| f.name == "late").unwrap();
+        assert!(!late.optional);
+        assert_eq!(
+            late.field_type,
+            FieldType::Optional(Box::new(FieldType::String))
+        );
+    }
+
     #[test]
     fn test_array_with_null_values() {
         let json = r#"["hello", null, "world"]"#;
@@ -669,4 +2010,1050 @@ mod tests {
         assert!(matches!(int_field.field_type, FieldType::Integer));
         assert!(matches!(float_field.field_type, FieldType::Float));
     }
+
+    fn parse_with_null_only(json: &str, mode: NullOnlyType) -> Schema {
+        let options = GeneratorOptions {
+            null_only_type: mode,
+            ..Default::default()
+        };
+        parse_json(json, &options).unwrap()
+    }
+
+    #[test]
+    fn test_null_only_type_any() {
+        let schema = parse_with_null_only(r#"{"name": "x", "extra": null}"#, NullOnlyType::Any);
+        let extra = schema.types[0]
+            .fields
+            .iter()
+            .find(|f| f.name == "extra")
+            .unwrap();
+
+        assert_eq!(extra.field_type, FieldType::Any);
+        assert!(!extra.optional);
+    }
+
+    #[test]
+    fn test_null_only_type_optional_any() {
+        let schema =
+            parse_with_null_only(r#"{"name": "x", "extra": null}"#, NullOnlyType::OptionalAny);
+        let extra = schema.types[0]
+            .fields
+            .iter()
+            .find(|f| f.name == "extra")
+            .unwrap();
+
+        assert_eq!(extra.field_type, FieldType::Any);
+        assert!(extra.optional);
+    }
+
+    #[test]
+    fn test_null_only_type_skip() {
+        let schema = parse_with_null_only(
+            r#"[{"name": "x", "extra": null}, {"name": "y", "extra": null}]"#,
+            NullOnlyType::Skip,
+        );
+        let item = schema.types.iter().find(|t| t.name == "RootItem").unwrap();
+
+        assert_eq!(item.fields.len(), 1);
+        assert_eq!(item.fields[0].name, "name");
+    }
+
+    #[test]
+    fn test_lossy_decisions_produce_warnings() {
+        let json = r#"{"tags": [], "extra": null}"#;
+        let schema = parse_json(json, &default_options()).unwrap();
+
+        assert_eq!(schema.warnings.len(), 2);
+        assert!(schema.warnings.iter().any(|w| w.contains("'tags'")));
+        assert!(schema.warnings.iter().any(|w| w.contains("'Root.extra'")));
+    }
+
+    #[test]
+    fn test_rejected_map_detection_warns() {
+        // 20+ keys with mixed values: a map is considered, then rejected
+        let mut entries: Vec<String> = (0..10).map(|i| format!(r#""k{}": "v""#, i)).collect();
+        entries.extend((10..20).map(|i| format!(r#""k{}": {{"x": 1}}"#, i)));
+        let json = format!(r#"{{"data": {{{}}}}}"#, entries.join(", "));
+
+        let schema = parse_json(&json, &default_options()).unwrap();
+
+        assert!(schema
+            .warnings
+            .iter()
+            .any(|w| w.contains("Map detection for 'data' was rejected")));
+    }
+
+    #[test]
+    fn test_rejected_map_detection_leaves_no_orphan_types() {
+        let mut entries: Vec<String> = (0..10).map(|i| format!(r#""k{}": "v""#, i)).collect();
+        entries.extend((10..20).map(|i| format!(r#""k{}": {{"x": 1}}"#, i)));
+        let json = format!(r#"{{"data": {{{}}}}}"#, entries.join(", "));
+
+        let schema = parse_json(&json, &default_options()).unwrap();
+
+        let referenced: HashSet<&str> = schema
+            .types
+            .iter()
+            .flat_map(|t| t.fields.iter())
+            .flat_map(|f| f.field_type.referenced_types())
+            .collect();
+        for type_def in &schema.types {
+            assert!(
+                type_def.name == schema.root_name || referenced.contains(type_def.name.as_str()),
+                "orphan type {} left in schema",
+                type_def.name
+            );
+        }
+        assert!(schema.types.iter().all(|t| t.name != "Data"));
+    }
+
+    #[test]
+    fn test_wrapper_name_is_independent_of_root_name() {
+        let json = r#"[{"id": 1}, {"id": 2}]"#;
+
+        let default_schema = parse_json(json, &default_options()).unwrap();
+        assert_eq!(default_schema.root_name, "Root");
+        assert!(default_schema.types.iter().any(|t| t.name == "RootItem"));
+
+        let options = GeneratorOptions {
+            root_name: "User".to_string(),
+            wrapper_name: Some("UserList".to_string()),
+            ..Default::default()
+        };
+        let schema = parse_json(json, &options).unwrap();
+
+        assert_eq!(schema.root_name, "UserList");
+        let wrapper = schema.root_type().unwrap();
+        assert_eq!(
+            wrapper.fields[0].field_type,
+            FieldType::Array(Box::new(FieldType::Reference("User".to_string())))
+        );
+        assert!(schema.types.iter().any(|t| t.name == "User"));
+    }
+
+    #[test]
+    fn test_parse_csv_types_columns() {
+        let csv = "id,name,score,active,zip,note\n1,Ann,1.5,true,01234,\n2,\"Bob, Jr\",2,FALSE,90210,hi\n";
+        let schema = parse_csv(csv, &default_options()).unwrap();
+
+        let root = schema.root_type().unwrap();
+        assert_eq!(
+            root.fields[0].field_type,
+            FieldType::Array(Box::new(FieldType::Reference("RootItem".to_string())))
+        );
+        let item = schema.types.iter().find(|t| t.name == "RootItem").unwrap();
+        let types: Vec<(&str, &FieldType, bool)> = item
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), &f.field_type, f.optional))
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                ("id", &FieldType::Integer, false),
+                ("name", &FieldType::String, false),
+                ("score", &FieldType::Float, false),
+                ("active", &FieldType::Boolean, false),
+                ("zip", &FieldType::String, false),
+                ("note", &FieldType::String, true),
+            ]
+        );
+
+        let err = parse_csv("a,b\n1,2,3\n", &default_options()).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("line 2 has 3 cells but the header has 2"));
+    }
+
+    #[test]
+    fn test_parse_ndjson_merges_records() {
+        let ndjson = "{\"id\": 1, \"level\": \"info\"}\n\n{\"id\": 2, \"level\": \"warn\", \"user\": \"ann\"}\n";
+        let schema = parse_ndjson(ndjson, &default_options()).unwrap();
+
+        let item = schema.types.iter().find(|t| t.name == "RootItem").unwrap();
+        let fields: Vec<(&str, bool)> = item
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.optional))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![("id", false), ("level", false), ("user", true)]
+        );
+
+        let err = parse_ndjson("{\"id\": 1}\n\n{\"id\": }\n", &default_options()).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("NDJSON line 3, column 8: expected value"));
+        let err = parse_ndjson("{\"id\": 1}\n[1]\n", &default_options()).unwrap_err();
+        assert!(err.to_string().contains("NDJSON line 2 is not an object"));
+    }
+
+    #[test]
+    fn test_toml_array_of_tables() {
+        let toml = r#"
+released = 1979-05-27T07:32:00Z
+
+[[servers]]
+host = "alpha"
+port = 8080
+
+[[servers]]
+host = "beta"
+tls = true
+"#;
+        let options = GeneratorOptions {
+            singular_names: true,
+            ..Default::default()
+        };
+        let schema = parse_toml(toml, &options).unwrap();
+
+        let root = schema.root_type().unwrap();
+        let servers = root.fields.iter().find(|f| f.name == "servers").unwrap();
+        assert_eq!(
+            servers.field_type,
+            FieldType::Array(Box::new(FieldType::Reference("Server".to_string())))
+        );
+        let released = root.fields.iter().find(|f| f.name == "released").unwrap();
+        assert_eq!(
+            released.field_type,
+            FieldType::DateTime(DateTimeFormat::Rfc3339)
+        );
+
+        let server = schema.types.iter().find(|t| t.name == "Server").unwrap();
+        let optional: Vec<(&str, bool)> = server
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.optional))
+            .collect();
+        assert_eq!(
+            optional,
+            vec![("host", false), ("port", true), ("tls", true)]
+        );
+    }
+
+    #[test]
+    fn test_minify_identifiers() {
+        let options = GeneratorOptions {
+            minify_identifiers: true,
+            ..Default::default()
+        };
+        let schema = parse_json(
+            r#"{"owner": {"id": 1, "address": {"city": "x"}}, "tags": [{"name": "a"}]}"#,
+            &options,
+        )
+        .unwrap();
+
+        let mut names: Vec<&str> = schema.types.iter().map(|t| t.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["Root", "T1", "T2", "T3"]);
+        let root = schema.root_type().unwrap();
+        assert_eq!(root.fields[0].name, "owner");
+        assert!(
+            matches!(&root.fields[0].field_type, FieldType::Reference(name) if name.starts_with('T'))
+        );
+        // Every reference points at a renamed type
+        assert!(schema.types.iter().flat_map(|t| &t.fields).all(|f| f
+            .field_type
+            .referenced_types()
+            .iter()
+            .all(|name| names.contains(name))));
+    }
+
+    #[test]
+    fn test_item_as_root_drops_wrapper() {
+        let options = GeneratorOptions {
+            root_name: "User".to_string(),
+            item_as_root: true,
+            ..Default::default()
+        };
+        let schema = parse_json(
+            r#"[{"id": 1, "tags": ["a"]}, {"id": 2, "email": "x"}]"#,
+            &options,
+        )
+        .unwrap();
+
+        assert_eq!(schema.root_name, "User");
+        assert_eq!(schema.types[0].name, "User");
+        assert!(schema
+            .types
+            .iter()
+            .all(|t| t.fields.iter().all(|f| f.name != "items")));
+        let user = schema.root_type().unwrap();
+        assert!(user.fields.iter().any(|f| f.name == "email" && f.optional));
+
+        // Arrays that aren't all objects keep the wrapper
+        let schema = parse_json("[1, 2]", &options).unwrap();
+        assert_eq!(schema.root_type().unwrap().fields[0].name, "items");
+        assert_eq!(schema.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_item_as_root_with_flatten_adds_list_type() {
+        let options = GeneratorOptions {
+            shared: SharedOptions {
+                flatten: true,
+                ..Default::default()
+            },
+            root_name: "User".to_string(),
+            item_as_root: true,
+            ..Default::default()
+        };
+        let schema = parse_json(r#"[{"id": 1}, {"id": 2}]"#, &options).unwrap();
+
+        let names: Vec<&str> = schema.types.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["User", "UserList"]);
+        assert_eq!(schema.root_name, "User");
+        let list = &schema.types[1];
+        assert_eq!(list.origin, TypeOrigin::ArrayWrapper);
+        assert_eq!(
+            list.fields[0].field_type,
+            FieldType::Array(Box::new(FieldType::Reference("User".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_nested_type_does_not_reuse_root_name() {
+        let json = r#"{"root": {"id": 1}}"#;
+        let schema = parse_json(json, &default_options()).unwrap();
+
+        let names: HashSet<&str> = schema.types.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names.len(), schema.types.len());
+    }
+
+    #[test]
+    fn test_max_union_members_collapses_to_any() {
+        let json = r#"{"values": ["a", 1, true, 1.5, {"x": 1}]}"#;
+        let options = GeneratorOptions {
+            max_union_members: 3,
+            ..Default::default()
+        };
+        let schema = parse_json(json, &options).unwrap();
+
+        let root = schema.root_type().unwrap();
+        assert_eq!(
+            root.fields[0].field_type,
+            FieldType::Array(Box::new(FieldType::Any))
+        );
+        // The object member went away with its union
+        assert_eq!(schema.types.len(), 1);
+        assert_eq!(
+            schema.warnings,
+            vec!["Field 'Root.values' had a union of 5 members, over --max-union-members 3; typed as Any"]
+        );
+
+        let schema = parse_json(json, &GeneratorOptions::default()).unwrap();
+        assert!(matches!(
+            &schema.root_type().unwrap().fields[0].field_type,
+            FieldType::Array(inner) if matches!(&**inner, FieldType::Union(members) if members.len() == 5)
+        ));
+    }
+
+    #[test]
+    fn test_tagged_union_splits_objects_by_tag() {
+        let json = r#"{"pets": [{"type": "cat", "lives": 9}, {"type": "dog", "good": true}, {"type": "cat", "lives": 7}]}"#;
+        let options = GeneratorOptions {
+            tagged_union: Some("type".to_string()),
+            ..Default::default()
+        };
+        let schema = parse_json(json, &options).unwrap();
+
+        assert_eq!(
+            schema.root_type().unwrap().fields[0].field_type,
+            FieldType::Array(Box::new(FieldType::Union(vec![
+                FieldType::Reference("Cat".to_string()),
+                FieldType::Reference("Dog".to_string()),
+            ])))
+        );
+        let cat = schema.types.iter().find(|t| t.name == "Cat").unwrap();
+        assert_eq!(cat.tag.as_deref(), Some("type"));
+        assert_eq!(
+            cat.fields[0].field_type,
+            FieldType::Enum(vec!["cat".to_string()])
+        );
+        assert!(cat.fields.iter().all(|f| !f.optional));
+
+        // Without the option the objects merge into one type
+        let schema = parse_json(json, &default_options()).unwrap();
+        assert_eq!(schema.types.len(), 2);
+    }
+
+    #[test]
+    fn test_max_depth_truncates_nested_types() {
+        let json = r#"{"a": {"b": {"c": {"d": 1}}, "list": [{"e": {"f": 1}}]}, "tags": [["x"]]}"#;
+        let options = GeneratorOptions {
+            max_depth: Some(1),
+            ..Default::default()
+        };
+        let schema = parse_json(json, &options).unwrap();
+
+        let names: Vec<&str> = schema.types.iter().map(|t| t.name.as_str()).collect();
+        assert!(names.contains(&"A"));
+        assert!(!names.contains(&"B"));
+        assert!(!names.contains(&"List"));
+
+        let a = schema.types.iter().find(|t| t.name == "A").unwrap();
+        assert!(a.fields.iter().all(|f| f.field_type == FieldType::Any));
+
+        // Arrays without objects are not nested types and are kept
+        let root = schema.root_type().unwrap();
+        let tags = root.fields.iter().find(|f| f.name == "tags").unwrap();
+        assert_eq!(
+            tags.field_type,
+            FieldType::Array(Box::new(FieldType::Array(Box::new(FieldType::String))))
+        );
+
+        assert!(schema
+            .warnings
+            .iter()
+            .any(|w| w.starts_with("Truncated 2 subtree(s)")));
+    }
+
+    #[test]
+    fn test_deep_nesting_is_a_clean_error() {
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+
+        // Far past any limit, the input is rejected instead of crashing.
+        // serde_json stops at 128 levels on its own, so the value is built
+        // directly to reach the check.
+        let mut value = JsonValue::Array(Vec::new());
+        for _ in 1..10_000 {
+            value = JsonValue::Array(vec![value]);
+        }
+        let mut context = InferenceContext::new(&default_options());
+        let err = infer_schema(&value, &mut context).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input structure: Input nests arrays and objects more than 128 levels deep (--recursion-limit)"
+        );
+        assert!(parse_json(&nested(10_000), &default_options()).is_err());
+
+        let options = GeneratorOptions {
+            recursion_limit: 20,
+            ..Default::default()
+        };
+        let err = parse_json(&nested(21), &options).unwrap_err();
+        assert!(matches!(
+            err,
+            AlchemistError::InvalidStructure(message) if message.contains("more than 20 levels")
+        ));
+        assert!(parse_json(&nested(20), &options).is_ok());
+        let toml = format!("a = {}", nested(21));
+        assert!(parse_toml(&toml, &options).is_err());
+    }
+
+    #[test]
+    fn test_iso_date_strings() {
+        let json = r#"{
+            "created": "2024-01-02T15:04:05Z",
+            "birthday": "1990-05-17",
+            "year": "2024",
+            "dates": ["2024-01-02", null],
+            "events": [{"at": "2024-01-02T15:04:05"}, {"at": "soon"}]
+        }"#;
+        let schema = parse_json(json, &default_options()).unwrap();
+        let field_type = |type_name: &str, field: &str| {
+            let type_def = schema.types.iter().find(|t| t.name == type_name).unwrap();
+            type_def
+                .fields
+                .iter()
+                .find(|f| f.name == field)
+                .unwrap()
+                .field_type
+                .clone()
+        };
+
+        assert_eq!(
+            field_type("Root", "created"),
+            FieldType::DateTime(DateTimeFormat::Rfc3339)
+        );
+        assert_eq!(
+            field_type("Root", "birthday"),
+            FieldType::DateTime(DateTimeFormat::Date)
+        );
+        assert_eq!(field_type("Root", "year"), FieldType::String);
+        assert_eq!(
+            field_type("Root", "dates"),
+            FieldType::Array(Box::new(FieldType::Optional(Box::new(
+                FieldType::DateTime(DateTimeFormat::Date)
+            ))))
+        );
+        // One value that is not a date keeps the field a string
+        assert_eq!(field_type("Events", "at"), FieldType::String);
+
+        let options = GeneratorOptions {
+            iso_dates: false,
+            ..Default::default()
+        };
+        let schema = parse_json(json, &options).unwrap();
+        let root = schema.root_type().unwrap();
+        assert_eq!(root.fields[0].field_type, FieldType::String);
+    }
+
+    #[test]
+    fn test_uuid_strings() {
+        let json = r#"{
+            "id": "550e8400-e29b-41d4-a716-446655440000",
+            "short": "550e8400-e29b-41d4-a716-44665544000",
+            "owners": ["6BA7B810-9DAD-11D1-80B4-00C04FD430C8", null],
+            "items": [{"ref": "550e8400-e29b-41d4-a716-446655440000"}, {"ref": "n/a"}]
+        }"#;
+        let schema = parse_json(json, &default_options()).unwrap();
+        let root = schema.root_type().unwrap();
+
+        assert_eq!(root.fields[0].field_type, FieldType::Uuid);
+        assert_eq!(root.fields[1].field_type, FieldType::String);
+        assert_eq!(
+            root.fields[2].field_type,
+            FieldType::Array(Box::new(FieldType::Optional(Box::new(FieldType::Uuid))))
+        );
+        // One value that is not a UUID keeps the field a string
+        let item = schema.types.iter().find(|t| t.name == "Items").unwrap();
+        assert_eq!(item.fields[0].field_type, FieldType::String);
+
+        let options = GeneratorOptions {
+            uuids: false,
+            ..Default::default()
+        };
+        let schema = parse_json(json, &options).unwrap();
+        let root = schema.root_type().unwrap();
+        assert_eq!(root.fields[0].field_type, FieldType::String);
+    }
+
+    #[test]
+    fn test_parse_samples_merges_roots() {
+        let options = default_options();
+        let schema = parse_samples(
+            &[
+                (r#"{"id": 1, "email": "a@x.io"}"#, InputFormat::Json),
+                ("id: 2\nphone: '555'\n", InputFormat::Yaml),
+            ],
+            &options,
+        )
+        .unwrap();
+        let root = schema.root_type().unwrap();
+        let fields: Vec<(&str, bool)> = root
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.optional))
+            .collect();
+        assert_eq!(fields, [("id", false), ("email", true), ("phone", true)]);
+
+        let schema = parse_samples(
+            &[
+                (r#"[{"a": 1}]"#, InputFormat::Json),
+                (r#"[{"b": 2}]"#, InputFormat::Json),
+            ],
+            &options,
+        )
+        .unwrap();
+        let item = schema.types.iter().find(|t| t.name == "RootItem").unwrap();
+        assert!(item.fields.iter().all(|f| f.optional));
+
+        let error = parse_samples(
+            &[
+                (r#"{"a": 1}"#, InputFormat::Json),
+                ("[1]", InputFormat::Json),
+            ],
+            &options,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid input structure: Cannot merge inputs: the root of input 1 is an object but the root of input 2 is an array"
+        );
+    }
+
+    #[test]
+    fn test_parse_samples_merges_nested_objects() {
+        let schema = parse_samples(
+            &[
+                (
+                    r#"{"o": {"a": 1, "p": {"x": 1}}, "xs": [{"c": 1}]}"#,
+                    InputFormat::Json,
+                ),
+                (
+                    r#"{"o": {"b": 2, "p": {"y": 2}}, "xs": [{"d": 2}]}"#,
+                    InputFormat::Json,
+                ),
+            ],
+            &default_options(),
+        )
+        .unwrap();
+        let fields = |name: &str| -> Vec<(String, bool)> {
+            let type_def = schema.types.iter().find(|t| t.name == name).unwrap();
+            type_def
+                .fields
+                .iter()
+                .map(|f| (f.name.clone(), f.optional))
+                .collect()
+        };
+
+        let root = schema.root_type().unwrap();
+        assert_eq!(root.origin, TypeOrigin::Object);
+        assert_eq!(root.doc.as_deref(), Some("Auto-generated Root type"));
+        assert_eq!(
+            fields("O"),
+            [
+                ("a".to_string(), true),
+                ("p".to_string(), false),
+                ("b".to_string(), true)
+            ]
+        );
+        assert_eq!(
+            fields("P"),
+            [("x".to_string(), true), ("y".to_string(), true)]
+        );
+        assert_eq!(
+            fields("Xs"),
+            [("c".to_string(), true), ("d".to_string(), true)]
+        );
+    }
+
+    #[test]
+    fn test_string_enums_from_repeated_values() {
+        let json = r#"[
+            {"status": "open", "owner": "ann", "note": "a b", "code": "x"},
+            {"status": "closed", "owner": "bob", "note": "a b", "code": "x"},
+            {"status": "open", "owner": "cid", "note": "has: colon", "code": "y"},
+            {"status": null, "owner": "dan", "code": "z"}
+        ]"#;
+        let fields = |options: &GeneratorOptions| {
+            let schema = parse_json(json, options).unwrap();
+            let item = schema.types.iter().find(|t| t.name == "RootItem").unwrap();
+            item.fields
+                .iter()
+                .map(|f| (f.name.clone(), f.field_type.clone()))
+                .collect::<HashMap<_, _>>()
+        };
+
+        let inferred = fields(&default_options());
+        // Values keep the order they were first seen in
+        assert_eq!(
+            inferred["status"],
+            FieldType::Optional(Box::new(FieldType::Enum(vec![
+                "open".to_string(),
+                "closed".to_string()
+            ])))
+        );
+        // No value repeats
+        assert_eq!(inferred["owner"], FieldType::String);
+        // Not every value is a label
+        assert_eq!(inferred["note"], FieldType::String);
+
+        let limited = fields(&GeneratorOptions {
+            enum_threshold: 2,
+            ..Default::default()
+        });
+        assert_eq!(limited["code"], FieldType::String);
+        assert!(matches!(limited["status"], FieldType::Optional(_)));
+
+        let disabled = fields(&GeneratorOptions {
+            enum_threshold: 0,
+            ..Default::default()
+        });
+        assert_eq!(
+            disabled["status"],
+            FieldType::Optional(Box::new(FieldType::String))
+        );
+    }
+
+    #[test]
+    fn test_epoch_dates_require_flag_and_name_hint() {
+        let json = r#"{"created_at": 1700000000, "updatedAt": 1700000000000, "count": 1700000000, "expires_at": 42}"#;
+
+        let schema = parse_json(json, &default_options()).unwrap();
+        let root = schema.root_type().unwrap();
+        assert!(root
+            .fields
+            .iter()
+            .all(|f| f.field_type == FieldType::Integer));
+
+        let options = GeneratorOptions {
+            epoch_dates: true,
+            ..Default::default()
+        };
+        let schema = parse_json(json, &options).unwrap();
+        let root = schema.root_type().unwrap();
+        let field_type = |name: &str| {
+            &root
+                .fields
+                .iter()
+                .find(|f| f.name == name)
+                .unwrap()
+                .field_type
+        };
+
+        assert_eq!(
+            field_type("created_at"),
+            &FieldType::DateTime(DateTimeFormat::EpochSeconds)
+        );
+        assert_eq!(
+            field_type("updatedAt"),
+            &FieldType::DateTime(DateTimeFormat::EpochMillis)
+        );
+        // No name hint, and an implausible value with a hint
+        assert_eq!(field_type("count"), &FieldType::Integer);
+        assert_eq!(field_type("expires_at"), &FieldType::Integer);
+    }
+
+    #[test]
+    fn test_typed_map_keys() {
+        let entries: Vec<String> = (0..20).map(|i| format!(r#""{}": {}"#, i * 7, i)).collect();
+        let json = format!(r#"{{"scores": {{{}}}}}"#, entries.join(", "));
+        let scores = |options: &GeneratorOptions| {
+            let schema = parse_json(&json, options).unwrap();
+            schema.root_type().unwrap().fields[0].field_type.clone()
+        };
+
+        assert_eq!(
+            scores(&default_options()),
+            FieldType::Map(Box::new(FieldType::String), Box::new(FieldType::Integer))
+        );
+
+        let options = GeneratorOptions {
+            typed_map_keys: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            scores(&options),
+            FieldType::Map(Box::new(FieldType::Integer), Box::new(FieldType::Integer))
+        );
+    }
+
+    #[test]
+    fn test_detect_sets() {
+        let json = r#"{"tags": ["a", "b"], "ids": [1, 1], "scores": [0.5, 1.5], "name": ["x"]}"#;
+        let options = GeneratorOptions {
+            detect_sets: true,
+            ..Default::default()
+        };
+        let schema = parse_json(json, &options).unwrap();
+        let fields = &schema.root_type().unwrap().fields;
+
+        assert_eq!(
+            fields[0].field_type,
+            FieldType::Set(Box::new(FieldType::String))
+        );
+        // Repeated values, floats and singular names stay arrays
+        for field in &fields[1..] {
+            assert!(matches!(field.field_type, FieldType::Array(_)));
+        }
+
+        let schema = parse_json(json, &default_options()).unwrap();
+        assert_eq!(
+            schema.root_type().unwrap().fields[0].field_type,
+            FieldType::Array(Box::new(FieldType::String))
+        );
+    }
+
+    #[test]
+    fn test_max_fields_names_offending_type() {
+        let json = r#"{"id": 1, "profile": {"a": 1, "b": 2, "c": 3}}"#;
+        let options = GeneratorOptions {
+            max_fields: Some(2),
+            ..Default::default()
+        };
+
+        match parse_json(json, &options) {
+            Err(AlchemistError::TooManyFields {
+                type_name,
+                field_count,
+                limit,
+            }) => {
+                assert_eq!(type_name, "Profile");
+                assert_eq!(field_count, 3);
+                assert_eq!(limit, 2);
+            }
+            other => panic!("expected TooManyFields, got {:?}", other),
+        }
+
+        let relaxed = GeneratorOptions {
+            max_fields: Some(3),
+            ..Default::default()
+        };
+        assert!(parse_json(json, &relaxed).is_ok());
+    }
+
+    #[test]
+    fn test_field_ordering() {
+        let json = r#"[{"zeta": 1, "mid": "a", "alpha": true}, {"zeta": 2, "alpha": false}]"#;
+        let field_names = |field_ordering| {
+            let options = GeneratorOptions {
+                field_ordering,
+                ..Default::default()
+            };
+            let schema = parse_json(json, &options).unwrap();
+            let item = schema.types.iter().find(|t| t.name == "RootItem").unwrap();
+            item.fields
+                .iter()
+                .map(|f| f.name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(field_names(FieldOrdering::Source), ["zeta", "mid", "alpha"]);
+        assert_eq!(field_names(FieldOrdering::Alpha), ["alpha", "mid", "zeta"]);
+        assert_eq!(
+            field_names(FieldOrdering::RequiredFirst),
+            ["zeta", "alpha", "mid"]
+        );
+    }
+
+    #[test]
+    fn test_type_origin_follows_input_shape() {
+        let entries: Vec<String> = (0..20)
+            .map(|i| format!(r#""user{}": {{"id": {}}}"#, i, i))
+            .collect();
+        let json = format!(
+            r#"{{"owner": {{"id": 1}}, "items": [{{"a": 1}}, {{"b": 2}}], "users": {{{}}}}}"#,
+            entries.join(", ")
+        );
+        let schema = parse_json(&json, &default_options()).unwrap();
+        let origin = |name: &str| schema.types.iter().find(|t| t.name == name).unwrap().origin;
+
+        assert_eq!(origin("Root"), TypeOrigin::Object);
+        assert_eq!(origin("Owner"), TypeOrigin::Object);
+        assert_eq!(origin("Items"), TypeOrigin::MergedArray);
+        assert_eq!(origin("User"), TypeOrigin::Map);
+    }
+
+    #[test]
+    fn test_money_fields_become_decimals() {
+        let json = r#"{"amount": 10, "order": {"price": 9.99, "prices": [1.5, null], "name": "x", "amount": 2}}"#;
+        let options = GeneratorOptions {
+            money_fields: vec![
+                "order.price*".to_string(),
+                "amount".to_string(),
+                "*.name".to_string(),
+            ],
+            ..Default::default()
+        };
+        let schema = parse_json(json, &options).unwrap();
+        let field_type = |type_name: &str, field: &str| {
+            let type_def = schema.types.iter().find(|t| t.name == type_name).unwrap();
+            type_def
+                .fields
+                .iter()
+                .find(|f| f.name == field)
+                .unwrap()
+                .field_type
+                .clone()
+        };
+
+        assert_eq!(field_type("Root", "amount"), FieldType::Decimal);
+        assert_eq!(field_type("Order", "amount"), FieldType::Decimal);
+        assert_eq!(field_type("Order", "price"), FieldType::Decimal);
+        assert_eq!(
+            field_type("Order", "prices"),
+            FieldType::Array(Box::new(FieldType::Optional(Box::new(FieldType::Decimal))))
+        );
+        // Non-numeric fields keep their type
+        assert_eq!(field_type("Order", "name"), FieldType::String);
+    }
+
+    #[test]
+    fn test_optional_fields_override_warns() {
+        let json = r#"[{"id": 1, "name": "a"}, {"id": 2}]"#;
+        let options = GeneratorOptions {
+            shared: SharedOptions {
+                optional_fields: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let schema = parse_json(json, &options).unwrap();
+        assert!(schema.warnings.contains(
+            &"--optional-fields made 2 field(s) optional that were present in every sample"
+                .to_string()
+        ));
+
+        let schema = parse_json(json, &default_options()).unwrap();
+        assert!(schema
+            .warnings
+            .iter()
+            .all(|w| !w.contains("--optional-fields")));
+    }
+
+    #[test]
+    fn test_no_optional_inference_keeps_fields_required() {
+        let json = r#"{"items": [{"a": 1, "b": null, "c": null}, {"a": null, "c": null}]}"#;
+        for null_policy in [NullPolicy::Auto, NullPolicy::Optional, NullPolicy::Both] {
+            let options = GeneratorOptions {
+                optional_inference: false,
+                null_policy,
+                ..Default::default()
+            };
+            let schema = parse_json(json, &options).unwrap();
+            assert!(
+                schema
+                    .types
+                    .iter()
+                    .flat_map(|t| &t.fields)
+                    .all(|f| !f.optional),
+                "{:?} marked a field optional",
+                null_policy
+            );
+
+            let item = schema.types.iter().find(|t| t.name == "Items").unwrap();
+            let types: Vec<&FieldType> = item.fields.iter().map(|f| &f.field_type).collect();
+            assert_eq!(
+                types,
+                [
+                    &FieldType::Optional(Box::new(FieldType::Integer)),
+                    &FieldType::Any,
+                    &FieldType::Any
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_null_policy_for_sometimes_null_fields() {
+        let json = r#"[{"a": null, "b": 1}, {"a": 2, "b": 3}]"#;
+        let field_a = |null_policy| {
+            let options = GeneratorOptions {
+                null_policy,
+                ..Default::default()
+            };
+            let schema = parse_json(json, &options).unwrap();
+            let item = schema.types.iter().find(|t| t.name == "RootItem").unwrap();
+            assert!(!item.fields[1].optional);
+            let a = item.fields[0].clone();
+            (a.field_type, a.optional)
+        };
+        let nullable_int = FieldType::Optional(Box::new(FieldType::Integer));
+
+        // The first non-null sample decides the type
+        assert_eq!(field_a(NullPolicy::Optional), (FieldType::Integer, true));
+        assert_eq!(field_a(NullPolicy::Nullable), (nullable_int.clone(), false));
+        assert_eq!(field_a(NullPolicy::Both), (nullable_int, true));
+    }
+
+    #[test]
+    fn test_mixed_array_repr() {
+        let json = r#"{"values": [1, {"a": 1}], "ids": [1, "a"]}"#;
+        let options = GeneratorOptions {
+            mixed_array_repr: MixedArrayRepr::Any,
+            ..Default::default()
+        };
+        let schema = parse_json(json, &options).unwrap();
+        let root = schema.root_type().unwrap();
+
+        assert_eq!(
+            root.fields[0].field_type,
+            FieldType::Array(Box::new(FieldType::Any))
+        );
+        // Primitive-only unions are unaffected
+        assert!(
+            matches!(&root.fields[1].field_type, FieldType::Array(inner) if matches!(**inner, FieldType::Union(_)))
+        );
+        assert_eq!(schema.types.len(), 1);
+
+        let schema = parse_json(json, &default_options()).unwrap();
+        assert!(
+            matches!(&schema.root_type().unwrap().fields[0].field_type, FieldType::Array(inner) if matches!(**inner, FieldType::Union(_)))
+        );
+    }
+
+    #[test]
+    fn test_present_but_null_field_is_nullable_not_optional() {
+        let json = r#"[{"name": "a", "age": 30}, {"name": "b", "age": null}, {"name": "c", "age": 5, "nick": "x"}, {"name": "d", "age": 7, "nick": null}]"#;
+        let schema = parse_json(json, &default_options()).unwrap();
+        let item = schema.types.iter().find(|t| t.name == "RootItem").unwrap();
+        let field = |name: &str| item.fields.iter().find(|f| f.name == name).unwrap();
+
+        let nullable = |inner| FieldType::Optional(Box::new(inner));
+        // Present everywhere, null in one object
+        assert_eq!(field("age").field_type, nullable(FieldType::Integer));
+        assert!(!field("age").optional);
+        assert_eq!(field("name").field_type, FieldType::String);
+        assert!(!field("name").optional);
+        // Missing from some objects and null in another
+        assert_eq!(field("nick").field_type, nullable(FieldType::String));
+        assert!(field("nick").optional);
+    }
+
+    #[test]
+    fn test_identical_nested_objects_share_a_type() {
+        let json = r#"{
+            "billing": {"street": "a", "geo": {"lat": 1.0, "lng": 2.0}},
+            "shipping": {"geo": {"lat": 3.0, "lng": 4.0}, "street": "b"},
+            "stops": [{"street": "c", "geo": {"lat": 5.0, "lng": 6.0}}],
+            "office": {"street": "d", "floor": 2}
+        }"#;
+        let options = GeneratorOptions {
+            share_types: true,
+            ..Default::default()
+        };
+        let schema = parse_json(json, &options).unwrap();
+
+        // Array elements are merged, so they only share with each other
+        let names: Vec<&str> = schema.types.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["Root", "Geo", "Billing", "Stops", "Office"]);
+        let root = &schema.types[0];
+        assert_eq!(
+            root.fields[1].field_type,
+            FieldType::Reference("Billing".to_string())
+        );
+        assert_eq!(
+            root.fields[2].field_type,
+            FieldType::Array(Box::new(FieldType::Reference("Stops".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_identical_nested_objects_are_separate_types_by_default() {
+        let json = r#"{"billing": {"street": "a"}, "shipping": {"street": "b"}}"#;
+        let schema = parse_json(json, &default_options()).unwrap();
+
+        let names: Vec<&str> = schema.types.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["Root", "Billing", "Shipping"]);
+    }
+
+    #[test]
+    fn test_shared_type_gives_back_its_numbered_name() {
+        let json = r#"{"a": {"item": {"x": 1}}, "b": {"item": {"x": 1}, "y": 1}, "c": {"item": {"z": 1}}}"#;
+        let options = GeneratorOptions {
+            share_types: true,
+            ..Default::default()
+        };
+        let schema = parse_json(json, &options).unwrap();
+
+        // The second `Item` is shared, so the next distinct one is `Item1`
+        let names: Vec<&str> = schema.types.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["Root", "Item", "A", "B", "Item1", "C"]);
+    }
+
+    #[test]
+    fn test_infer_constraints_from_samples() {
+        let json =
+            r#"[{"age": 3, "name": "ab", "tags": ["a"]}, {"age": 40, "name": "abcd", "tags": []}]"#;
+        let schema = parse_json(json, &default_options()).unwrap();
+        assert!(schema.types[1].fields[0].constraints.is_empty());
+
+        let options = GeneratorOptions {
+            infer_constraints: true,
+            ..Default::default()
+        };
+        let schema = parse_json(json, &options).unwrap();
+        let fields = &schema.types[1].fields;
+        assert_eq!(
+            fields[0].constraints,
+            Constraints {
+                min: Some(3.0),
+                max: Some(40.0),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            fields[1].constraints,
+            Constraints {
+                min_length: Some(2),
+                max_length: Some(4),
+                ..Default::default()
+            }
+        );
+        assert!(fields[2].constraints.is_empty());
+    }
 }
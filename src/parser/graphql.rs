@@ -0,0 +1,716 @@
+//! GraphQL SDL input
+//!
+//! Reads the `type`, `input`, `interface`, `enum`, `union` and `scalar`
+//! definitions of a schema into the AST. Directives, field arguments and
+//! default values are skipped, as are `schema`, `directive` and `extend`
+//! definitions.
+
+use crate::ast::{Field, FieldType, Schema, TypeDef};
+use crate::error::{AlchemistError, Result};
+use crate::generators::GeneratorOptions;
+use crate::utils::to_safe_identifier;
+use std::collections::HashMap;
+
+/// Lexical token of GraphQL SDL
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Name(String),
+    /// String or block string contents, used as documentation
+    Str(String),
+    /// Number, only found in skipped arguments and default values
+    Number(String),
+    Punct(char),
+}
+
+/// Reference to a type in a field definition, e.g. `[User!]!`
+#[derive(Debug, Clone, PartialEq)]
+enum TypeRef {
+    Named(String),
+    List(Box<TypeRef>),
+    NonNull(Box<TypeRef>),
+}
+
+impl TypeRef {
+    /// Name of the type at the core of the list and non-null wrappers
+    fn base_name(&self) -> &str {
+        match self {
+            TypeRef::Named(name) => name,
+            TypeRef::List(inner) | TypeRef::NonNull(inner) => inner.base_name(),
+        }
+    }
+}
+
+/// Field of an object, input or interface type
+#[derive(Debug)]
+struct FieldDef {
+    name: String,
+    doc: Option<String>,
+    type_ref: TypeRef,
+}
+
+/// Named type definition of a schema
+#[derive(Debug)]
+enum Definition {
+    Object {
+        name: String,
+        doc: Option<String>,
+        fields: Vec<FieldDef>,
+    },
+    Enum {
+        name: String,
+        values: Vec<String>,
+    },
+    Union {
+        name: String,
+        members: Vec<String>,
+    },
+    Scalar {
+        name: String,
+    },
+}
+
+/// Parse GraphQL SDL into Schema AST
+///
+/// The root is the type named after `root_name` if there is one, otherwise
+/// the first object or input type.
+pub fn parse_sdl(input: &str, options: &GeneratorOptions) -> Result<Schema> {
+    let mut parser = SdlParser {
+        tokens: tokenize(input)?,
+        pos: 0,
+        recursion_limit: options.recursion_limit,
+    };
+    let mut definitions = Vec::new();
+    while parser.peek().is_some() {
+        definitions.extend(parser.definition()?);
+    }
+
+    let resolver = Resolver::new(&definitions);
+    let mut types = Vec::new();
+    for definition in &definitions {
+        if let Definition::Object { name, doc, fields } = definition {
+            let mut type_def = TypeDef::new(name);
+            type_def.doc = doc.clone();
+            for field in fields {
+                type_def.add_field(resolver.field(name, field, options)?);
+            }
+            options.field_ordering.apply(&mut type_def);
+            log::debug!(
+                "type created name={} fields={}",
+                type_def.name,
+                type_def.fields.len()
+            );
+            types.push(type_def);
+        }
+    }
+
+    if types.is_empty() {
+        return Err(AlchemistError::InvalidStructure(
+            "GraphQL schema has no object or input types".to_string(),
+        ));
+    }
+    let root_index = types
+        .iter()
+        .position(|t| t.name == options.root_name)
+        .unwrap_or_default();
+    let root = types.remove(root_index);
+    let mut schema = Schema::new(root.name.clone());
+    schema.add_type(root);
+    for type_def in types {
+        schema.add_type(type_def);
+    }
+
+    schema.warnings = super::cap_union_members(&mut schema, options.max_union_members);
+    schema.describe_fields(&options.descriptions);
+    if options.emit_partial {
+        schema.add_partial_types();
+    }
+    Ok(schema)
+}
+
+/// Split SDL into tokens, dropping whitespace, commas and comments
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while let Some(&c) = chars.get(i) {
+        match c {
+            c if c.is_whitespace() || c == ',' || c == '\u{feff}' => i += 1,
+            '#' => {
+                while chars.get(i).is_some_and(|&c| c != '\n') {
+                    i += 1;
+                }
+            }
+            '"' if chars[i..].starts_with(&['"', '"', '"']) => {
+                let start = i + 3;
+                // An escaped `\"""` does not end the string
+                let length = (start..chars.len())
+                    .position(|j| chars[j..].starts_with(&['"', '"', '"']) && chars[j - 1] != '\\')
+                    .ok_or_else(|| syntax_error("unterminated block string"))?;
+                let text: String = chars[start..start + length].iter().collect();
+                tokens.push(Token::Str(collapse_whitespace(
+                    &text.replace("\\\"\"\"", "\"\"\""),
+                )));
+                i = start + length + 3;
+            }
+            '"' => {
+                let mut text = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        Some('"') => break,
+                        Some('\\') => {
+                            text.push(match chars.get(i + 1) {
+                                Some('n' | 't' | 'r') => ' ',
+                                Some(&escaped) => escaped,
+                                None => return Err(syntax_error("unterminated string")),
+                            });
+                            i += 2;
+                        }
+                        Some('\n') | None => return Err(syntax_error("unterminated string")),
+                        Some(&c) => {
+                            text.push(c);
+                            i += 1;
+                        }
+                    }
+                }
+                tokens.push(Token::Str(collapse_whitespace(&text)));
+                i += 1;
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let start = i;
+                while chars
+                    .get(i)
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || *c == '_')
+                {
+                    i += 1;
+                }
+                tokens.push(Token::Name(chars[start..i].iter().collect()));
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let start = i;
+                i += 1;
+                while chars
+                    .get(i)
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '-'))
+                {
+                    i += 1;
+                }
+                tokens.push(Token::Number(chars[start..i].iter().collect()));
+            }
+            '.' if chars[i..].starts_with(&['.', '.', '.']) => {
+                tokens.push(Token::Punct('.'));
+                i += 3;
+            }
+            '!' | '$' | '&' | '(' | ')' | ':' | '=' | '@' | '[' | ']' | '{' | '|' | '}' => {
+                tokens.push(Token::Punct(c));
+                i += 1;
+            }
+            other => {
+                return Err(syntax_error(&format!("unexpected character '{}'", other)));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Description text on one line, as generated doc comments expect
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn syntax_error(message: &str) -> AlchemistError {
+    AlchemistError::InvalidStructure(format!("Invalid GraphQL schema: {}", message))
+}
+
+/// Recursive descent parser over SDL tokens
+struct SdlParser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Deepest nesting of list types accepted, see `type_ref`
+    recursion_limit: usize,
+}
+
+impl SdlParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Result<Token> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| syntax_error("unexpected end of input"))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    /// Whether the next token is the given punctuator
+    fn peek_punct(&self, punct: char) -> bool {
+        self.peek() == Some(&Token::Punct(punct))
+    }
+
+    /// Consume the given punctuator if it is next
+    fn eat_punct(&mut self, punct: char) -> bool {
+        let found = self.peek_punct(punct);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    /// Consume the given name if it is next
+    fn eat_name(&mut self, name: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Name(n)) if n == name);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_punct(&mut self, punct: char) -> Result<()> {
+        match self.next()? {
+            Token::Punct(c) if c == punct => Ok(()),
+            other => Err(unexpected(&other, &format!("'{}'", punct))),
+        }
+    }
+
+    fn expect_name(&mut self) -> Result<String> {
+        match self.next()? {
+            Token::Name(name) => Ok(name),
+            other => Err(unexpected(&other, "a name")),
+        }
+    }
+
+    /// A description string, if one comes next
+    fn description(&mut self) -> Option<String> {
+        match self.peek() {
+            Some(Token::Str(text)) => {
+                let text = text.clone();
+                self.pos += 1;
+                Some(text).filter(|text| !text.is_empty())
+            }
+            _ => None,
+        }
+    }
+
+    /// Parse one top-level definition, `None` for the kinds that are skipped
+    fn definition(&mut self) -> Result<Option<Definition>> {
+        let doc = self.description();
+        let keyword = self.expect_name()?;
+        match keyword.as_str() {
+            "type" | "input" | "interface" => {
+                let name = self.expect_name()?;
+                if self.eat_name("implements") {
+                    self.eat_punct('&');
+                    self.expect_name()?;
+                    while self.eat_punct('&') {
+                        self.expect_name()?;
+                    }
+                }
+                self.skip_directives()?;
+                let mut fields = Vec::new();
+                if self.eat_punct('{') {
+                    while !self.eat_punct('}') {
+                        fields.push(self.field()?);
+                    }
+                }
+                Ok(Some(Definition::Object { name, doc, fields }))
+            }
+            "enum" => {
+                let name = self.expect_name()?;
+                self.skip_directives()?;
+                let mut values = Vec::new();
+                if self.eat_punct('{') {
+                    while !self.eat_punct('}') {
+                        self.description();
+                        values.push(self.expect_name()?);
+                        self.skip_directives()?;
+                    }
+                }
+                Ok(Some(Definition::Enum { name, values }))
+            }
+            "union" => {
+                let name = self.expect_name()?;
+                self.skip_directives()?;
+                let mut members = Vec::new();
+                if self.eat_punct('=') {
+                    self.eat_punct('|');
+                    members.push(self.expect_name()?);
+                    while self.eat_punct('|') {
+                        members.push(self.expect_name()?);
+                    }
+                }
+                Ok(Some(Definition::Union { name, members }))
+            }
+            "scalar" => {
+                let name = self.expect_name()?;
+                self.skip_directives()?;
+                Ok(Some(Definition::Scalar { name }))
+            }
+            "schema" => {
+                self.skip_directives()?;
+                self.expect_punct('{')?;
+                self.skip_balanced()?;
+                Ok(None)
+            }
+            "extend" => {
+                // Extensions add to types defined elsewhere, so they are dropped
+                self.definition()?;
+                Ok(None)
+            }
+            "directive" => {
+                self.expect_punct('@')?;
+                self.expect_name()?;
+                if self.eat_punct('(') {
+                    self.skip_balanced()?;
+                }
+                self.eat_name("repeatable");
+                if !self.eat_name("on") {
+                    return Err(syntax_error("expected 'on' in directive definition"));
+                }
+                self.eat_punct('|');
+                self.expect_name()?;
+                while self.eat_punct('|') {
+                    self.expect_name()?;
+                }
+                Ok(None)
+            }
+            other => Err(syntax_error(&format!("unexpected '{}'", other))),
+        }
+    }
+
+    /// Parse a field, e.g. `"Docs" posts(first: Int = 10): [Post!]! @deprecated`
+    fn field(&mut self) -> Result<FieldDef> {
+        let doc = self.description();
+        let name = self.expect_name()?;
+        if self.eat_punct('(') {
+            self.skip_balanced()?;
+        }
+        self.expect_punct(':')?;
+        let type_ref = self.type_ref(1)?;
+        if self.eat_punct('=') {
+            self.skip_value()?;
+        }
+        self.skip_directives()?;
+        Ok(FieldDef {
+            name,
+            doc,
+            type_ref,
+        })
+    }
+
+    /// Parse a type reference nested `depth` levels deep in list types
+    ///
+    /// Lists are parsed recursively, so nesting past `recursion_limit` is an
+    /// error rather than a stack overflow.
+    fn type_ref(&mut self, depth: usize) -> Result<TypeRef> {
+        let type_ref = if self.eat_punct('[') {
+            if depth > self.recursion_limit {
+                return Err(AlchemistError::InvalidStructure(format!(
+                    "GraphQL type nests lists more than {} levels deep (--recursion-limit)",
+                    self.recursion_limit
+                )));
+            }
+            let inner = self.type_ref(depth + 1)?;
+            self.expect_punct(']')?;
+            TypeRef::List(Box::new(inner))
+        } else {
+            TypeRef::Named(self.expect_name()?)
+        };
+        if self.eat_punct('!') {
+            Ok(TypeRef::NonNull(Box::new(type_ref)))
+        } else {
+            Ok(type_ref)
+        }
+    }
+
+    /// Skip directives like `@deprecated(reason: "gone")`
+    fn skip_directives(&mut self) -> Result<()> {
+        while self.eat_punct('@') {
+            self.expect_name()?;
+            if self.eat_punct('(') {
+                self.skip_balanced()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Skip a default value, e.g. `10`, `ACTIVE` or `{ limit: [1, 2] }`
+    fn skip_value(&mut self) -> Result<()> {
+        match self.next()? {
+            Token::Punct('[' | '{') => self.skip_balanced(),
+            Token::Punct('$') => self.expect_name().map(drop),
+            Token::Punct(c) => Err(syntax_error(&format!("unexpected '{}' in value", c))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Skip to the bracket closing the one just consumed
+    fn skip_balanced(&mut self) -> Result<()> {
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next()? {
+                Token::Punct('(' | '[' | '{') => depth += 1,
+                Token::Punct(')' | ']' | '}') => depth -= 1,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+fn unexpected(token: &Token, expected: &str) -> AlchemistError {
+    let found = match token {
+        Token::Name(name) | Token::Number(name) => format!("'{}'", name),
+        Token::Str(_) => "a string".to_string(),
+        Token::Punct(c) => format!("'{}'", c),
+    };
+    syntax_error(&format!("expected {}, found {}", expected, found))
+}
+
+/// Turns type references into field types, knowing every named type
+struct Resolver<'a> {
+    definitions: HashMap<&'a str, &'a Definition>,
+}
+
+impl<'a> Resolver<'a> {
+    fn new(definitions: &'a [Definition]) -> Self {
+        let definitions = definitions
+            .iter()
+            .map(|definition| {
+                let name = match definition {
+                    Definition::Object { name, .. }
+                    | Definition::Enum { name, .. }
+                    | Definition::Union { name, .. }
+                    | Definition::Scalar { name } => name.as_str(),
+                };
+                (name, definition)
+            })
+            .collect();
+        Self { definitions }
+    }
+
+    /// Build the AST field for a field of `type_name`
+    ///
+    /// Nullable fields follow the null policy; enum values are listed in the
+    /// field's doc, as enums are typed as strings.
+    fn field(&self, type_name: &str, def: &FieldDef, options: &GeneratorOptions) -> Result<Field> {
+        let resolve = |type_ref| {
+            self.non_null(type_ref).map_err(|unknown| {
+                AlchemistError::InvalidStructure(format!(
+                    "Unknown GraphQL type '{}' in field '{}.{}'",
+                    unknown, type_name, def.name
+                ))
+            })
+        };
+
+        let mut field = match &def.type_ref {
+            TypeRef::NonNull(inner) => Field::new(def.name.clone(), resolve(inner)?),
+            nullable => options
+                .null_policy
+                .apply(Field::new(def.name.clone(), resolve(nullable)?)),
+        };
+
+        let enum_values = match self.definitions.get(def.type_ref.base_name()) {
+            Some(Definition::Enum { values, .. }) => Some(format!("One of: {}", values.join(", "))),
+            _ => None,
+        };
+        let doc = match (&def.doc, enum_values) {
+            (Some(doc), Some(values)) => Some(format!("{} ({})", doc, values)),
+            (doc, values) => doc.clone().or(values),
+        };
+        if let Some(doc) = doc {
+            field = field.with_doc(doc);
+        }
+
+        let safe_name = to_safe_identifier(&def.name);
+        if safe_name != def.name {
+            field = field.with_safe_name(safe_name);
+        }
+        Ok(field)
+    }
+
+    /// Field type of a reference that may be null, e.g. a list element
+    fn nullable(&self, type_ref: &TypeRef) -> std::result::Result<FieldType, String> {
+        match type_ref {
+            TypeRef::NonNull(inner) => self.non_null(inner),
+            other => Ok(FieldType::Optional(Box::new(self.non_null(other)?))),
+        }
+    }
+
+    /// Field type of a reference ignoring its own nullability, or the name of
+    /// an undefined type it uses
+    fn non_null(&self, type_ref: &TypeRef) -> std::result::Result<FieldType, String> {
+        match type_ref {
+            TypeRef::NonNull(inner) => self.non_null(inner),
+            TypeRef::List(inner) => Ok(FieldType::Array(Box::new(self.nullable(inner)?))),
+            TypeRef::Named(name) => match name.as_str() {
+                "Int" => Ok(FieldType::Integer),
+                "Float" => Ok(FieldType::Float),
+                "String" | "ID" => Ok(FieldType::String),
+                "Boolean" => Ok(FieldType::Boolean),
+                _ => match self.definitions.get(name.as_str()) {
+                    Some(Definition::Object { .. }) => Ok(FieldType::Reference(name.clone())),
+                    Some(Definition::Enum { .. }) => Ok(FieldType::String),
+                    Some(Definition::Union { members, .. }) => Ok(FieldType::Union(
+                        members
+                            .iter()
+                            .map(|member| FieldType::Reference(member.clone()))
+                            .collect(),
+                    )),
+                    // Custom scalars have no known representation
+                    Some(Definition::Scalar { .. }) => Ok(FieldType::Any),
+                    None => Err(name.clone()),
+                },
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SDL: &str = r#"
+        # Blog schema
+        scalar DateTime
+
+        """
+        A blog post
+        """
+        type Post implements Node & Entry @key(fields: "id") {
+          id: ID!
+          "Post title"
+          title: String!
+          tags: [String!]!
+          scores: [Int]
+          status: Status!
+          author: User
+          published: DateTime
+          related(first: Int = 10): [SearchResult!]! @deprecated(reason: "use links")
+        }
+
+        type User {
+          name: String!
+          type: String
+        }
+
+        input PostFilter {
+          status: Status = DRAFT
+          limit: Int = 20
+        }
+
+        enum Status { DRAFT PUBLISHED @deprecated }
+
+        union SearchResult = | Post | User
+
+        directive @key(fields: String!) repeatable on OBJECT | INTERFACE
+        schema { query: Post }
+        extend type User { age: Int }
+    "#;
+
+    fn field<'a>(schema: &'a Schema, type_name: &str, name: &str) -> &'a Field {
+        schema
+            .types
+            .iter()
+            .find(|t| t.name == type_name)
+            .and_then(|t| t.fields.iter().find(|f| f.name == name))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_sdl_types() {
+        let schema = parse_sdl(SDL, &GeneratorOptions::default()).unwrap();
+
+        let names: Vec<&str> = schema.types.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["Post", "User", "PostFilter"]);
+        assert_eq!(schema.root_name, "Post");
+        assert_eq!(schema.types[0].doc.as_deref(), Some("A blog post"));
+
+        let type_of = |f: &Field| f.field_type.clone();
+        assert_eq!(type_of(field(&schema, "Post", "id")), FieldType::String);
+        assert_eq!(
+            field(&schema, "Post", "title").doc.as_deref(),
+            Some("Post title")
+        );
+        assert_eq!(
+            type_of(field(&schema, "Post", "tags")),
+            FieldType::Array(Box::new(FieldType::String))
+        );
+        assert_eq!(
+            type_of(field(&schema, "Post", "scores")),
+            FieldType::Optional(Box::new(FieldType::Array(Box::new(FieldType::Optional(
+                Box::new(FieldType::Integer)
+            )))))
+        );
+        assert_eq!(type_of(field(&schema, "Post", "status")), FieldType::String);
+        assert_eq!(
+            field(&schema, "Post", "status").doc.as_deref(),
+            Some("One of: DRAFT, PUBLISHED")
+        );
+        assert_eq!(
+            type_of(field(&schema, "Post", "author")),
+            FieldType::Optional(Box::new(FieldType::Reference("User".to_string())))
+        );
+        assert_eq!(type_of(field(&schema, "Post", "published")), FieldType::Any);
+        assert_eq!(
+            type_of(field(&schema, "Post", "related")),
+            FieldType::Array(Box::new(FieldType::Union(vec![
+                FieldType::Reference("Post".to_string()),
+                FieldType::Reference("User".to_string()),
+            ])))
+        );
+        // Extensions are not merged in
+        assert_eq!(schema.types[1].fields.len(), 2);
+    }
+
+    #[test]
+    fn test_root_name_selects_root_type() {
+        let options = GeneratorOptions {
+            root_name: "User".to_string(),
+            ..Default::default()
+        };
+        let schema = parse_sdl(SDL, &options).unwrap();
+
+        assert_eq!(schema.root_name, "User");
+        assert_eq!(schema.types[0].name, "User");
+    }
+
+    #[test]
+    fn test_unknown_type_is_an_error() {
+        let error = parse_sdl("type A { b: Missing! }", &GeneratorOptions::default()).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Unknown GraphQL type 'Missing' in field 'A.b'"));
+
+        let error = parse_sdl("type A { b Int }", &GeneratorOptions::default()).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Invalid GraphQL schema: expected ':', found 'Int'"));
+    }
+
+    #[test]
+    fn test_deeply_nested_list_is_a_clean_error() {
+        let depth = 100_000;
+        let sdl = format!(
+            "type A {{ f: {}Int{} }}",
+            "[".repeat(depth),
+            "]".repeat(depth)
+        );
+        let error = parse_sdl(&sdl, &GeneratorOptions::default()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid input structure: GraphQL type nests lists more than 128 levels deep (--recursion-limit)"
+        );
+
+        let sdl = "type A { f: [[Int!]!] }";
+        let options = GeneratorOptions {
+            recursion_limit: 2,
+            ..Default::default()
+        };
+        assert!(parse_sdl(sdl, &options).is_ok());
+    }
+}
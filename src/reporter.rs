@@ -3,14 +3,22 @@
 //! Uses owo-colors to create colorful, informative reports about
 //! the conversion process.
 
-use crate::ast::{FieldType, Schema};
-use owo_colors::OwoColorize;
+use alchemist::ast::{FieldType, Schema, TypeOrigin};
+use alchemist::error::AlchemistError;
+use alchemist::generators::rust::RenameCoverage;
+use clap::ValueEnum;
+use owo_colors::{AnsiColors, OwoColorize};
+use serde::Serialize;
+use std::io::{self, Write};
+use std::path::Path;
 use std::time::Duration;
+use unicode_width::UnicodeWidthStr;
 
 /// Statistics collected during the conversion process
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct ConversionStats {
     /// Total time taken for the conversion
+    #[serde(rename = "duration_ms", serialize_with = "serialize_millis")]
     pub duration: Duration,
     /// Number of types generated
     pub types_count: usize,
@@ -46,7 +54,12 @@ impl ConversionStats {
         let mut array_fields_count = 0;
         let mut max_depth = 0;
 
-        for type_def in &schema.types {
+        // The `items` field wrapping a root array is not one of the data's fields
+        let data_types = schema
+            .types
+            .iter()
+            .filter(|t| t.origin != TypeOrigin::ArrayWrapper);
+        for type_def in data_types {
             fields_count += type_def.fields.len();
 
             for field in &type_def.fields {
@@ -113,6 +126,78 @@ impl ConversionStats {
     }
 }
 
+/// Write a duration as fractional milliseconds
+fn serialize_millis<S: serde::Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64() * 1000.0)
+}
+
+/// The `--report-json` document: the statistics, complexity and each type's
+/// field counts
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    #[serde(flatten)]
+    stats: &'a ConversionStats,
+    complexity_score: u8,
+    types: Vec<TypeSummary<'a>>,
+}
+
+/// Field counts of one type, as listed by `Reporter::print_types_summary`
+#[derive(Serialize)]
+struct TypeSummary<'a> {
+    name: &'a str,
+    field_count: usize,
+    optional_count: usize,
+}
+
+/// One file's result in the `--summary-json` report of a `--batch` run
+#[derive(Debug, Clone, Serialize)]
+pub struct FileSummary {
+    /// Input file path
+    pub path: String,
+    /// Whether code was generated and written for the file
+    pub success: bool,
+    /// Number of types generated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub types_count: Option<usize>,
+    /// Total number of fields across all types
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields_count: Option<usize>,
+    /// Time taken for the file in milliseconds
+    pub duration_ms: f64,
+    /// Why the file failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl FileSummary {
+    /// Summary of a file converted successfully
+    pub fn success(path: &Path, stats: &ConversionStats) -> Self {
+        Self {
+            path: path.display().to_string(),
+            success: true,
+            types_count: Some(stats.types_count),
+            fields_count: Some(stats.fields_count),
+            duration_ms: stats.duration.as_secs_f64() * 1000.0,
+            error: None,
+        }
+    }
+
+    /// Summary of a file that failed with an error
+    pub fn failure(path: &Path, duration: Duration, error: &AlchemistError) -> Self {
+        Self {
+            path: path.display().to_string(),
+            success: false,
+            types_count: None,
+            fields_count: None,
+            duration_ms: duration.as_secs_f64() * 1000.0,
+            error: Some(error.to_string()),
+        }
+    }
+}
+
 /// Calculate the nesting depth of a field type
 fn calculate_type_depth(field_type: &FieldType) -> usize {
     // Use AST helper methods to resolve "unused method" warnings
@@ -139,59 +224,180 @@ fn calculate_type_depth(field_type: &FieldType) -> usize {
     }
 }
 
+/// Visual style of the report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ReportTheme {
+    /// Unicode boxes with emoji icons
+    #[default]
+    Fancy,
+    /// `+--+` borders and no emoji, for terminals without Unicode support
+    Ascii,
+    /// Plain `key: value` lines, for logs
+    Minimal,
+}
+
+/// Horizontal border position within a box
+#[derive(Debug, Clone, Copy)]
+enum Edge {
+    Top,
+    Middle,
+    Bottom,
+}
+
 /// Reporter for displaying conversion results
-pub struct Reporter;
+pub struct Reporter {
+    /// Total width of the report boxes, borders included
+    width: usize,
+    /// Visual style
+    theme: ReportTheme,
+}
 
 impl Reporter {
+    /// Box width used on wide terminals or when the terminal size is unknown
+    pub const DEFAULT_WIDTH: usize = 59;
+    /// Narrowest box the rows can still be laid out in
+    pub const MIN_WIDTH: usize = 52;
+
+    /// Create a reporter drawing boxes `width` columns wide
+    pub fn new(width: usize, theme: ReportTheme) -> Self {
+        Self {
+            width: width.max(Self::MIN_WIDTH),
+            theme,
+        }
+    }
+
+    /// Create a reporter sized for the terminal
+    ///
+    /// An explicit width wins; otherwise the default width shrinks to fit
+    /// narrower terminals.
+    pub fn for_terminal(requested_width: Option<usize>, theme: ReportTheme) -> Self {
+        let width = requested_width.unwrap_or_else(|| {
+            terminal_size::terminal_size()
+                .map(|(terminal_size::Width(columns), _)| usize::from(columns))
+                .map_or(Self::DEFAULT_WIDTH, |columns| {
+                    columns.min(Self::DEFAULT_WIDTH)
+                })
+        });
+        Self::new(width, theme)
+    }
+
+    /// Width available between the left and right borders
+    fn inner_width(&self) -> usize {
+        self.width - 2
+    }
+
+    /// Whether output is drawn in boxes
+    fn boxed(&self) -> bool {
+        self.theme != ReportTheme::Minimal
+    }
+
+    /// Prefix a label with its icon, unless the theme has no emoji
+    fn with_icon(&self, icon: &str, label: &str) -> String {
+        match self.theme {
+            ReportTheme::Fancy => format!("{} {}", icon, label),
+            ReportTheme::Ascii | ReportTheme::Minimal => label.to_string(),
+        }
+    }
+
+    /// Draw a horizontal border such as `┌────┐`
+    fn rule(&self, edge: Edge, heavy: bool) -> String {
+        let (left, fill, right) = match (self.theme, heavy, edge) {
+            (ReportTheme::Fancy, false, Edge::Top) => ('┌', '─', '┐'),
+            (ReportTheme::Fancy, false, Edge::Middle) => ('├', '─', '┤'),
+            (ReportTheme::Fancy, false, Edge::Bottom) => ('└', '─', '┘'),
+            (ReportTheme::Fancy, true, Edge::Top) => ('╔', '═', '╗'),
+            (ReportTheme::Fancy, true, Edge::Middle) => ('╠', '═', '╣'),
+            (ReportTheme::Fancy, true, Edge::Bottom) => ('╚', '═', '╝'),
+            (_, false, _) => ('+', '-', '+'),
+            (_, true, _) => ('+', '=', '+'),
+        };
+        format!(
+            "{}{}{}",
+            left,
+            fill.to_string().repeat(self.inner_width()),
+            right
+        )
+    }
+
+    /// Print a horizontal border; nothing in the minimal theme
+    fn print_rule(&self, edge: Edge, heavy: bool, color: AnsiColors) {
+        if self.boxed() {
+            println!("{}", self.rule(edge, heavy).color(color));
+        }
+    }
+
+    /// Lay out a line of content between the side borders
+    fn boxed_line(&self, content: &str, heavy: bool, color: AnsiColors) -> String {
+        if !self.boxed() {
+            return content.to_string();
+        }
+
+        let border = match (self.theme, heavy) {
+            (ReportTheme::Fancy, false) => "│",
+            (ReportTheme::Fancy, true) => "║",
+            _ => "|",
+        };
+        let padding = self
+            .inner_width()
+            .saturating_sub(2 + display_width(content));
+
+        format!(
+            "{}  {}{}{}",
+            border.color(color),
+            content,
+            " ".repeat(padding),
+            border.color(color)
+        )
+    }
+
+    /// Print a line of content between the side borders
+    fn print_line(&self, content: &str, heavy: bool, color: AnsiColors) {
+        println!("{}", self.boxed_line(content, heavy, color));
+    }
+
+    /// Print the title line and separator of a box; nothing in the minimal theme
+    fn print_title(&self, icon: &str, title: &str, color: AnsiColors) {
+        if self.boxed() {
+            self.print_rule(Edge::Top, false, color);
+            let title = self.with_icon(icon, title);
+            self.print_line(&title.bright_white().bold().to_string(), false, color);
+            self.print_rule(Edge::Middle, false, color);
+        }
+    }
+
+    /// Print the blank line separating boxes
+    fn print_gap(&self) {
+        if self.boxed() {
+            println!();
+        }
+    }
+
     /// Print a beautiful header
-    pub fn print_header() {
+    pub fn print_header(&self) {
+        if !self.boxed() {
+            return;
+        }
+
+        let color = AnsiColors::BrightMagenta;
+        let title = self.with_icon("🧪", "ALCHEMIST - Type Transformation Complete");
+
         println!();
-        println!(
-            "{}",
-            "╔═════════════════════════════════════════════════════════╗".bright_magenta()
-        );
-        println!(
-            "{}",
-            "║                                                         ║".bright_magenta()
-        );
-        println!(
-            "{}  {}           {}",
-            "║".bright_magenta(),
-            "🧪 ALCHEMIST - Type Transformation Complete"
-                .bright_cyan()
-                .bold(),
-            "║".bright_magenta()
-        );
-        println!(
-            "{}",
-            "║                                                         ║".bright_magenta()
-        );
-        println!(
-            "{}",
-            "╚═════════════════════════════════════════════════════════╝".bright_magenta()
-        );
+        self.print_rule(Edge::Top, true, color);
+        self.print_line("", true, color);
+        self.print_line(&title.bright_cyan().bold().to_string(), true, color);
+        self.print_line("", true, color);
+        self.print_rule(Edge::Bottom, true, color);
         println!();
     }
 
     /// Print the conversion statistics as a beautiful table
-    pub fn print_stats(stats: &ConversionStats, output_format: &str) {
-        Self::print_header();
+    pub fn print_stats(&self, stats: &ConversionStats, output_format: &str) {
+        let color = AnsiColors::BrightBlue;
+
+        self.print_header();
 
         // Stats table
-        println!(
-            "{}",
-            "┌─────────────────────────────────────────────────────────┐".bright_blue()
-        );
-        println!(
-            "{}  {}                               {}",
-            "│".bright_blue(),
-            "📊 Conversion Statistics".bright_white().bold(),
-            "│".bright_blue()
-        );
-        println!(
-            "{}",
-            "├─────────────────────────────────────────────────────────┤".bright_blue()
-        );
+        self.print_title("📊", "Conversion Statistics", color);
 
         // Time elapsed
         let time_ms = stats.duration.as_secs_f64() * 1000.0;
@@ -204,7 +410,7 @@ impl Reporter {
         } else {
             format!("{:.2} s", time_ms / 1000.0).red().to_string()
         };
-        Self::print_row("⏱️  Time Elapsed", &time_display.to_string());
+        self.print_row("⏱️", "Time Elapsed", &time_display.to_string());
 
         // Output format
         let format_icon = match output_format {
@@ -213,32 +419,33 @@ impl Reporter {
             "zod" | "Zod" => "🛡️",
             _ => "📄",
         };
-        Self::print_row(
-            &format!("{}  Output Format", format_icon),
+        self.print_row(
+            format_icon,
+            "Output Format",
             &output_format.bright_cyan().to_string(),
         );
 
-        println!(
-            "{}",
-            "├─────────────────────────────────────────────────────────┤".bright_blue()
-        );
+        self.print_rule(Edge::Middle, false, color);
 
         // Types generated
-        Self::print_row(
-            "📦 Types Generated",
+        self.print_row(
+            "📦",
+            "Types Generated",
             &stats.types_count.to_string().bright_yellow().to_string(),
         );
 
         // Fields analyzed
-        Self::print_row(
-            "📝 Fields Analyzed",
+        self.print_row(
+            "📝",
+            "Fields Analyzed",
             &stats.fields_count.to_string().bright_yellow().to_string(),
         );
 
         // Optional fields
         if stats.optional_fields_count > 0 {
-            Self::print_row(
-                "❓ Optional Fields",
+            self.print_row(
+                "❓",
+                "Optional Fields",
                 &stats
                     .optional_fields_count
                     .to_string()
@@ -249,8 +456,9 @@ impl Reporter {
 
         // Array fields
         if stats.array_fields_count > 0 {
-            Self::print_row(
-                "📚 Array Fields",
+            self.print_row(
+                "📚",
+                "Array Fields",
                 &stats
                     .array_fields_count
                     .to_string()
@@ -261,8 +469,9 @@ impl Reporter {
 
         // Nested types
         if stats.nested_types_count > 0 {
-            Self::print_row(
-                "🔗 Nested Types",
+            self.print_row(
+                "🔗",
+                "Nested Types",
                 &stats
                     .nested_types_count
                     .to_string()
@@ -271,32 +480,35 @@ impl Reporter {
             );
         }
 
-        println!(
-            "{}",
-            "├─────────────────────────────────────────────────────────┤".bright_blue()
-        );
+        self.print_rule(Edge::Middle, false, color);
 
         // Complexity
-        let complexity_bar = Self::complexity_bar(stats.complexity_score());
-        Self::print_row(
-            "🎯 Complexity",
-            &format!("{} {}", complexity_bar, stats.complexity_label()),
-        );
+        let complexity = match self.theme {
+            ReportTheme::Minimal => format!(
+                "{}/10 {}",
+                stats.complexity_score(),
+                stats.complexity_label()
+            ),
+            _ => format!(
+                "{} {}",
+                self.complexity_bar(stats.complexity_score()),
+                stats.complexity_label()
+            ),
+        };
+        self.print_row("🎯", "Complexity", &complexity);
 
         // Max depth
-        Self::print_row(
-            "📐 Max Nesting Depth",
+        self.print_row(
+            "📐",
+            "Max Nesting Depth",
             &stats.max_depth.to_string().dimmed().to_string(),
         );
 
-        println!(
-            "{}",
-            "├─────────────────────────────────────────────────────────┤".bright_blue()
-        );
+        self.print_rule(Edge::Middle, false, color);
 
         // Sizes
-        Self::print_row("📥 Input Size", &Self::format_bytes(stats.input_size));
-        Self::print_row("📤 Output Size", &Self::format_bytes(stats.output_size));
+        self.print_row("📥", "Input Size", &Self::format_bytes(stats.input_size));
+        self.print_row("📤", "Output Size", &Self::format_bytes(stats.output_size));
 
         // Compression ratio
         if stats.input_size > 0 {
@@ -309,32 +521,18 @@ impl Reporter {
             } else {
                 ratio_str.bright_red().to_string()
             };
-            Self::print_row("📊 Size Ratio", &colored);
+            self.print_row("📊", "Size Ratio", &colored);
         }
 
-        println!(
-            "{}",
-            "└─────────────────────────────────────────────────────────┘".bright_blue()
-        );
-        println!();
+        self.print_rule(Edge::Bottom, false, color);
+        self.print_gap();
     }
 
     /// Print types summary
-    pub fn print_types_summary(schema: &Schema) {
-        println!(
-            "{}",
-            "┌─────────────────────────────────────────────────────────┐".bright_green()
-        );
-        println!(
-            "{}  {}                                     {}",
-            "│".bright_green(),
-            "📋 Generated Types".bright_white().bold(),
-            "│".bright_green()
-        );
-        println!(
-            "{}",
-            "├─────────────────────────────────────────────────────────┤".bright_green()
-        );
+    pub fn print_types_summary(&self, schema: &Schema) {
+        let color = AnsiColors::BrightGreen;
+
+        self.print_title("📋", "Generated Types", color);
 
         // Use root_type() to verify root existence (activates unused method)
         let root_name = schema
@@ -356,143 +554,248 @@ impl Reporter {
                     type_def.fields.len().to_string().bright_yellow()
                 )
             };
-
-            let icon = if &type_def.name == root_name {
-                "🌟"
-            } else {
-                "  "
+            let is_root = &type_def.name == root_name;
+
+            let line = match self.theme {
+                ReportTheme::Minimal => format!(
+                    "{}: {}{}",
+                    type_def.name,
+                    fields_info,
+                    if is_root { " (root)" } else { "" }
+                ),
+                _ => {
+                    let (icon, arrow) = match (self.theme, is_root) {
+                        (ReportTheme::Fancy, true) => ("🌟", "→"),
+                        (ReportTheme::Fancy, false) => ("  ", "→"),
+                        (_, true) => ("* ", "->"),
+                        (_, false) => ("  ", "->"),
+                    };
+                    let name_padding = 20_usize.saturating_sub(display_width(&type_def.name));
+                    format!(
+                        "{} {}{} {} {}",
+                        icon,
+                        type_def.name.bright_cyan().bold(),
+                        " ".repeat(name_padding),
+                        arrow,
+                        fields_info
+                    )
+                }
             };
-
-            // Calculate padding
-            let info_len = strip_ansi_len(&fields_info);
-            let padding = 29_usize.saturating_sub(info_len);
-
-            println!(
-                "{}  {} {:<20} → {}{}{}",
-                "│".bright_green(),
-                icon,
-                type_def.name.bright_cyan().bold(),
-                fields_info,
-                " ".repeat(padding),
-                "│".bright_green()
-            );
+            self.print_line(&line, false, color);
         }
 
-        println!(
-            "{}",
-            "└─────────────────────────────────────────────────────────┘".bright_green()
+        self.print_rule(Edge::Bottom, false, color);
+        self.print_gap();
+    }
+
+    /// Write the statistics and per-type field counts as a JSON document,
+    /// for CI checks that should not scrape the visual report
+    pub fn write_json_report(
+        &self,
+        stats: &ConversionStats,
+        schema: &Schema,
+        mut writer: impl Write,
+    ) -> io::Result<()> {
+        let report = JsonReport {
+            stats,
+            complexity_score: stats.complexity_score(),
+            types: schema
+                .types
+                .iter()
+                .map(|type_def| TypeSummary {
+                    name: &type_def.name,
+                    field_count: type_def.fields.len(),
+                    optional_count: type_def.fields.iter().filter(|f| f.optional).count(),
+                })
+                .collect(),
+        };
+        serde_json::to_writer_pretty(&mut writer, &report)?;
+        writeln!(writer)
+    }
+
+    /// Print how many fields a `rename_all` convention covers
+    pub fn print_rename_coverage(&self, convention: &str, coverage: RenameCoverage) {
+        let color = AnsiColors::BrightCyan;
+
+        self.print_title("🔤", "Field Renames", color);
+        self.print_line(
+            &format!(
+                "{} field(s) covered by rename_all = \"{}\"",
+                coverage.covered.to_string().bright_green(),
+                convention
+            ),
+            false,
+            color,
         );
-        println!();
+        self.print_line(
+            &format!(
+                "{} field(s) still need #[serde(rename)]",
+                coverage.renamed.to_string().bright_yellow()
+            ),
+            false,
+            color,
+        );
+        self.print_rule(Edge::Bottom, false, color);
+        self.print_gap();
+    }
+
+    /// Print warnings collected during inference
+    pub fn print_warnings(&self, warnings: &[String]) {
+        if warnings.is_empty() {
+            return;
+        }
+
+        let color = AnsiColors::BrightYellow;
+
+        self.print_title("🔔", "Warnings", color);
+
+        for warning in warnings {
+            if !self.boxed() {
+                println!("{}: {}", "Warning".yellow(), warning);
+                continue;
+            }
+
+            let bullet = match self.theme {
+                ReportTheme::Fancy => "•",
+                _ => "-",
+            };
+            for (i, line) in textwrap(warning, self.inner_width() - 6)
+                .into_iter()
+                .enumerate()
+            {
+                let bullet = if i == 0 { bullet } else { " " };
+                self.print_line(&format!("{} {}", bullet.yellow(), line), false, color);
+            }
+        }
+
+        self.print_rule(Edge::Bottom, false, color);
+        self.print_gap();
     }
 
     /// Print success message
-    pub fn print_success(output_path: Option<&str>) {
+    pub fn print_success(&self, output_path: Option<&str>) {
+        let check = match self.theme {
+            ReportTheme::Fancy => format!("  {} ", "✅".green()),
+            ReportTheme::Ascii => "  ".to_string(),
+            ReportTheme::Minimal => String::new(),
+        };
+
         match output_path {
             Some(path) => {
                 println!(
-                    "  {} {} {}",
-                    "✅".green(),
+                    "{}{} {}",
+                    check,
                     "Output written to:".bright_white(),
                     path.bright_cyan().underline()
                 );
             }
             None => {
-                println!(
-                    "  {} {}",
-                    "✅".green(),
-                    "Output written to stdout".bright_white()
-                );
+                println!("{}{}", check, "Output written to stdout".bright_white());
             }
         }
+        self.print_gap();
+    }
+
+    /// Print the one-line note of a `--watch` regeneration
+    pub fn print_regenerated(&self, stats: &ConversionStats, output_path: &str) {
+        let check = match self.theme {
+            ReportTheme::Fancy => format!("{} ", "🔁".green()),
+            ReportTheme::Ascii | ReportTheme::Minimal => String::new(),
+        };
+        println!(
+            "{}{} {} {}",
+            check,
+            "Regenerated".bright_white(),
+            output_path.bright_cyan(),
+            format!(
+                "({} types, {:.3} ms)",
+                stats.types_count,
+                stats.duration.as_secs_f64() * 1000.0
+            )
+            .dimmed()
+        );
+    }
+
+    /// Print the line separating the report from the generated code
+    pub fn print_separator(&self) {
+        let line = match self.theme {
+            ReportTheme::Fancy => "─",
+            _ => "-",
+        };
+        println!("{}", line.repeat(self.width));
         println!();
     }
 
     /// Print error message
-    pub fn print_error(message: &str) {
+    pub fn print_error(&self, message: &str) {
+        if !self.boxed() {
+            println!("{}: {}", "Error".bright_red().bold(), message);
+            return;
+        }
+
+        let color = AnsiColors::Red;
+        let title = self.with_icon(&"❌".red().to_string(), "Error");
+
         println!();
-        println!(
-            "{}",
-            "╔═══════════════════════════════════════════════════════════╗".red()
-        );
-        println!(
-            "{}  {} {:<51} {}",
-            "║".red(),
-            "❌".red(),
-            "Error".bright_red().bold(),
-            "║".red()
-        );
-        println!(
-            "{}",
-            "╠═══════════════════════════════════════════════════════════╣".red()
-        );
+        self.print_rule(Edge::Top, true, color);
+        self.print_line(&title.bright_red().bold().to_string(), true, color);
+        self.print_rule(Edge::Middle, true, color);
 
         // Wrap long messages
-        for line in textwrap(message, 55) {
-            println!("{}  {:<55} {}", "║".red(), line, "║".red());
+        for line in textwrap(message, self.inner_width() - 3) {
+            self.print_line(&line, true, color);
         }
 
-        println!(
-            "{}",
-            "╚═══════════════════════════════════════════════════════════╝".red()
-        );
+        self.print_rule(Edge::Bottom, true, color);
         println!();
     }
 
     /// Print a single row in the table
-    fn print_row(label: &str, value: &str) {
-        let target_label_width: usize = 24;
-        let label_visible_len = strip_ansi_len(label);
-        let mut label_padding = target_label_width.saturating_sub(label_visible_len);
-
-        // Manual fix for Time Elapsed emoji width inconsistency
-        if label.contains("Time") {
-            label_padding += 2;
-        }
-
-        let value_visible_len = strip_ansi_len(value);
-        let mut total_width: usize = 57; // Total inner width available
+    fn print_row(&self, icon: &str, label: &str, value: &str) {
+        println!("{}", self.format_row(icon, label, value));
+    }
 
-        // Manual fix for right border alignment on Time row
-        if label.contains("Time") {
-            total_width += 2;
+    /// Lay out a row with the label in a fixed column and the value after it
+    fn format_row(&self, icon: &str, label: &str, value: &str) -> String {
+        if !self.boxed() {
+            return format!("{}: {}", label, value);
         }
 
-        // Calculate inner usage to determine final padding needed to reach the right border
-        let inner_used = 2 + label_visible_len + label_padding + 1 + value_visible_len;
-        let final_padding = total_width.saturating_sub(inner_used);
-
-        println!(
-            "{}  {}{}{}{}{}",
-            "│".bright_blue(),
-            label.dimmed(),
-            " ".repeat(label_padding),
-            value,
-            " ".repeat(final_padding),
-            "│".bright_blue()
-        );
+        let target_label_width: usize = 24;
+        let label = self.with_icon(icon, label);
+        let label_padding = target_label_width.saturating_sub(display_width(&label));
+
+        self.boxed_line(
+            &format!("{}{}{}", label.dimmed(), " ".repeat(label_padding), value),
+            false,
+            AnsiColors::BrightBlue,
+        )
     }
 
     /// Create a visual complexity bar
-    fn complexity_bar(score: u8) -> String {
+    fn complexity_bar(&self, score: u8) -> String {
+        let (full, empty) = match self.theme {
+            ReportTheme::Fancy => ("█", "░"),
+            _ => ("#", "."),
+        };
         let filled = score as usize;
-        let empty = 10 - filled;
 
         let bar: String = (0..filled)
             .map(|i| {
                 if i < 3 {
-                    "█".green().to_string()
+                    full.green().to_string()
                 } else if i < 6 {
-                    "█".yellow().to_string()
+                    full.yellow().to_string()
                 } else if i < 9 {
-                    "█".bright_red().to_string()
+                    full.bright_red().to_string()
                 } else {
-                    "█".red().to_string()
+                    full.red().to_string()
                 }
             })
             .collect::<Vec<_>>()
             .join("");
 
-        let empty_bar = "░".repeat(empty).dimmed().to_string();
+        let empty_bar = empty.repeat(10 - filled).dimmed().to_string();
 
         format!("[{}{}]", bar, empty_bar)
     }
@@ -521,7 +824,7 @@ fn textwrap(text: &str, max_width: usize) -> Vec<String> {
     for word in text.split_whitespace() {
         if current_line.is_empty() {
             current_line = word.to_string();
-        } else if current_line.len() + 1 + word.len() <= max_width {
+        } else if display_width(&current_line) + 1 + display_width(word) <= max_width {
             current_line.push(' ');
             current_line.push_str(word);
         } else {
@@ -541,9 +844,11 @@ fn textwrap(text: &str, max_width: usize) -> Vec<String> {
     lines
 }
 
-/// Get length of string without ANSI codes
-fn strip_ansi_len(s: &str) -> usize {
-    let mut len = 0;
+/// Get the terminal display width of a string, ignoring ANSI codes
+///
+/// Emoji and other wide characters take two columns.
+fn display_width(s: &str) -> usize {
+    let mut visible = String::with_capacity(s.len());
     let mut in_escape = false;
 
     for c in s.chars() {
@@ -554,17 +859,17 @@ fn strip_ansi_len(s: &str) -> usize {
                 in_escape = false;
             }
         } else {
-            len += 1;
+            visible.push(c);
         }
     }
 
-    len
+    visible.width()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ast::{Field, TypeDef};
+    use alchemist::ast::{Field, TypeDef};
 
     #[test]
     fn test_complexity_score() {
@@ -581,6 +886,134 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_stats_skip_root_array_wrapper() {
+        let mut schema = Schema::new("Root");
+        let mut root = TypeDef::new("Root");
+        root.origin = TypeOrigin::ArrayWrapper;
+        root.add_field(Field::new(
+            "items",
+            FieldType::Array(Box::new(FieldType::Reference("RootItem".into()))),
+        ));
+        schema.add_type(root);
+        let mut item = TypeDef::new("RootItem");
+        item.add_field(Field::new("id", FieldType::Integer));
+        schema.add_type(item);
+
+        let stats = ConversionStats::from_schema(&schema, Duration::ZERO, 10, 20);
+        assert_eq!(stats.types_count, 2);
+        assert_eq!(stats.fields_count, 1);
+        assert_eq!(stats.array_fields_count, 0);
+    }
+
+    #[test]
+    fn test_file_summary_json() {
+        let mut schema = Schema::new("Root");
+        let mut type_def = TypeDef::new("Root");
+        type_def.add_field(Field::new("name", FieldType::String));
+        schema.add_type(type_def);
+        let stats = ConversionStats::from_schema(&schema, Duration::from_millis(2), 10, 20);
+        let error = AlchemistError::GenerationError("empty".to_string());
+
+        let summaries = [
+            FileSummary::success(Path::new("a.json"), &stats),
+            FileSummary::failure(Path::new("b.json"), Duration::ZERO, &error),
+        ];
+        assert_eq!(
+            serde_json::to_value(&summaries).unwrap(),
+            serde_json::json!([
+                {"path": "a.json", "success": true, "types_count": 1, "fields_count": 1, "duration_ms": 2.0},
+                {"path": "b.json", "success": false, "duration_ms": 0.0, "error": "Code generation failed: empty"}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_json_report() {
+        let mut schema = Schema::new("Root");
+        let mut root = TypeDef::new("Root");
+        root.add_field(Field::new("id", FieldType::Integer));
+        root.add_field(Field::new("owner", FieldType::Reference("Owner".into())).optional());
+        schema.add_type(root);
+        schema.add_type(TypeDef::new("Owner"));
+        let stats = ConversionStats::from_schema(&schema, Duration::from_millis(3), 10, 20);
+
+        let mut output = Vec::new();
+        Reporter::new(60, ReportTheme::Fancy)
+            .write_json_report(&stats, &schema, &mut output)
+            .unwrap();
+        let report: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(
+            report,
+            serde_json::json!({
+                "duration_ms": 3.0,
+                "types_count": 2,
+                "fields_count": 2,
+                "optional_fields_count": 1,
+                "nested_types_count": 1,
+                "max_depth": 1,
+                "array_fields_count": 0,
+                "input_size": 10,
+                "output_size": 20,
+                "complexity_score": stats.complexity_score(),
+                "types": [
+                    {"name": "Root", "field_count": 2, "optional_count": 1},
+                    {"name": "Owner", "field_count": 0, "optional_count": 0}
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_report_width_has_minimum() {
+        let reporter = Reporter::for_terminal(Some(80), ReportTheme::Fancy);
+        assert_eq!(reporter.rule(Edge::Top, false).chars().count(), 80);
+
+        let reporter = Reporter::for_terminal(Some(10), ReportTheme::Fancy);
+        assert_eq!(
+            reporter.rule(Edge::Top, false).chars().count(),
+            Reporter::MIN_WIDTH
+        );
+    }
+
+    #[test]
+    fn test_rows_align_regardless_of_emoji() {
+        let reporter = Reporter::new(Reporter::DEFAULT_WIDTH, ReportTheme::Fancy);
+        let labels = [
+            ("⏱️", "Time Elapsed"),
+            ("🛡️", "Output Format"),
+            ("❓", "Optional Fields"),
+            ("📊", "Size Ratio"),
+        ];
+
+        for (icon, label) in labels {
+            let row = reporter.format_row(icon, label, &"12.8x".green().to_string());
+            assert_eq!(display_width(&row), Reporter::DEFAULT_WIDTH, "{}", label);
+        }
+    }
+
+    #[test]
+    fn test_ascii_and_minimal_themes() {
+        let ascii = Reporter::new(Reporter::DEFAULT_WIDTH, ReportTheme::Ascii);
+        let rule = ascii.rule(Edge::Top, false);
+        assert!(rule.starts_with("+--") && rule.ends_with("-+"));
+        let row = ascii.format_row("📦", "Types Generated", "2");
+        assert!(row.is_ascii());
+        assert_eq!(display_width(&row), Reporter::DEFAULT_WIDTH);
+
+        let minimal = Reporter::new(Reporter::DEFAULT_WIDTH, ReportTheme::Minimal);
+        assert_eq!(
+            minimal.format_row("📦", "Types Generated", "2"),
+            "Types Generated: 2"
+        );
+    }
+
+    #[test]
+    fn test_display_width_ignores_ansi_codes() {
+        assert_eq!(display_width(&"abc".red().to_string()), 3);
+        assert_eq!(display_width("🔔 Warnings"), 11);
+    }
+
     #[test]
     fn test_format_bytes() {
         assert!(Reporter::format_bytes(500).contains("500 B"));
@@ -0,0 +1,90 @@
+//! In-memory conversion from input text to generated code
+//!
+//! This is the whole pipeline without the CLI: no filesystem, stdin or
+//! stdout access, which keeps it usable from WebAssembly.
+
+use crate::error::{AlchemistError, Result};
+use crate::formats::{InputFormat, OutputFormat};
+use crate::generators::{self, GeneratorOptions};
+use crate::parser;
+use clap::ValueEnum;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+/// Convert input text into generated code
+pub fn transform(
+    input: &str,
+    input_format: InputFormat,
+    output_format: OutputFormat,
+    options: &GeneratorOptions,
+) -> Result<String> {
+    let schema = parser::parse(input, input_format, options)?;
+    schema.validate()?;
+    generators::for_format(output_format, options.clone()).generate(&schema)
+}
+
+/// String-typed entry point for JavaScript callers
+///
+/// Formats use the CLI names (`json`, `typescript`, ...) and `options_json`
+/// is a JSON object with `GeneratorOptions` fields; missing fields and an
+/// empty string use the defaults.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(js_name = transform))]
+pub fn transform_with_json_options(
+    input: &str,
+    input_format: &str,
+    output_format: &str,
+    options_json: &str,
+) -> std::result::Result<String, String> {
+    let input_format = InputFormat::from_str(input_format, true)
+        .map_err(|_| format!("Unknown input format '{}'", input_format))?;
+    let output_format = OutputFormat::from_str(output_format, true)
+        .map_err(|_| format!("Unknown output format '{}'", output_format))?;
+    let options = options_from_json(options_json).map_err(|e| e.to_string())?;
+
+    transform(input, input_format, output_format, &options).map_err(|e| e.to_string())
+}
+
+/// Parse generator options from JSON, falling back to defaults
+pub fn options_from_json(options_json: &str) -> Result<GeneratorOptions> {
+    if options_json.trim().is_empty() {
+        return Ok(GeneratorOptions::default());
+    }
+    serde_json::from_str(options_json)
+        .map_err(|e| AlchemistError::InvalidStructure(format!("Invalid options: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transform_json_to_typescript() {
+        let output = transform(
+            r#"{"name": "Ada"}"#,
+            InputFormat::Json,
+            OutputFormat::Typescript,
+            &GeneratorOptions::default(),
+        )
+        .unwrap();
+
+        assert!(output.contains("export interface Root {"));
+        assert!(output.contains("  name: string;"));
+    }
+
+    #[test]
+    fn test_transform_with_json_options() {
+        let output = transform_with_json_options(
+            "name: Ada",
+            "yaml",
+            "python",
+            r#"{"root_name": "User", "python_target": "3.10"}"#,
+        )
+        .unwrap();
+
+        assert!(output.contains("class User(BaseModel):"));
+        assert!(output.starts_with("from __future__ import annotations"));
+
+        let error = transform_with_json_options("{}", "json", "cobol", "").unwrap_err();
+        assert_eq!(error, "Unknown output format 'cobol'");
+    }
+}
@@ -20,29 +20,137 @@ pub fn to_pascal_case(s: &str) -> String {
 }
 
 /// Convert a string to snake_case
+///
+/// A word starts at an uppercase letter following a lowercase letter or a
+/// digit, and at the last letter of an uppercase run followed by lowercase,
+/// so acronyms stay one word: `HTMLParser` becomes `html_parser` and
+/// `userID` becomes `user_id`. Digits stay with the word before them.
 pub fn to_snake_case(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
     let mut result = String::new();
-    let mut last_was_upper = false;
 
-    for (i, c) in s.chars().enumerate() {
-        if c.is_ascii_uppercase() {
-            if i > 0 && !last_was_upper {
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '-' || c == ' ' {
+            result.push('_');
+            continue;
+        }
+        if c.is_ascii_uppercase() && i > 0 && !result.ends_with('_') {
+            let previous = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase());
+            if previous.is_ascii_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_ascii_uppercase() && next_is_lower)
+            {
                 result.push('_');
             }
-            result.push(c.to_ascii_lowercase());
-            last_was_upper = true;
-        } else if c == '-' || c == ' ' {
-            result.push('_');
-            last_was_upper = false;
-        } else {
-            result.push(c);
-            last_was_upper = false;
         }
+        result.push(c.to_ascii_lowercase());
     }
 
     result
 }
 
+/// Plurals the suffix rules of `to_singular` get wrong, with their singular
+const IRREGULAR_PLURALS: &[(&str, &str)] = &[
+    ("analyses", "analysis"),
+    ("calories", "calorie"),
+    ("children", "child"),
+    ("cookies", "cookie"),
+    ("criteria", "criterion"),
+    ("feet", "foot"),
+    ("geese", "goose"),
+    ("indices", "index"),
+    ("matrices", "matrix"),
+    ("men", "man"),
+    ("mice", "mouse"),
+    ("movies", "movie"),
+    ("people", "person"),
+    ("pies", "pie"),
+    ("teeth", "tooth"),
+    ("ties", "tie"),
+    ("vertices", "vertex"),
+    ("women", "woman"),
+    ("zombies", "zombie"),
+];
+
+/// Words ending in `s` that are the same in the singular
+const UNCOUNTABLE: &[&str] = &[
+    "alias",
+    "analytics",
+    "atlas",
+    "bias",
+    "canvas",
+    "chaos",
+    "economics",
+    "gas",
+    "headquarters",
+    "lens",
+    "means",
+    "news",
+    "physics",
+    "series",
+    "species",
+    "statistics",
+];
+
+/// Naively convert a plural English word to its singular, e.g. `servers` to
+/// `server` and `categories` to `category`
+///
+/// Only the last word of a compound name changes, so `topMovies` becomes
+/// `topMovie`. Words that don't look plural, like `status` or `address`,
+/// and uncountable ones like `news` are unchanged.
+pub fn to_singular(s: &str) -> String {
+    let (prefix, word) = s.split_at(last_word_start(s));
+    let lower = word.to_ascii_lowercase();
+
+    let singular = if UNCOUNTABLE.contains(&lower.as_str()) {
+        word.to_string()
+    } else if let Some((_, singular)) = IRREGULAR_PLURALS
+        .iter()
+        .find(|(plural, _)| *plural == lower)
+    {
+        // Keep a capitalized word capitalized
+        let mut singular = singular.to_string();
+        if word.starts_with(|c: char| c.is_ascii_uppercase()) {
+            singular[..1].make_ascii_uppercase();
+        }
+        singular
+    } else if let Some(stem) = word.strip_suffix("ies").filter(|stem| !stem.is_empty()) {
+        format!("{}y", stem)
+    } else if let Some(stem) = word.strip_suffix("sses") {
+        format!("{}ss", stem)
+    } else if ["ss", "us", "is"]
+        .iter()
+        .any(|suffix| word.ends_with(suffix))
+        || word.len() < 2
+    {
+        word.to_string()
+    } else if let Some(stem) = word.strip_suffix('s') {
+        stem.to_string()
+    } else {
+        word.to_string()
+    };
+    format!("{}{}", prefix, singular)
+}
+
+/// Byte index where the last word of a snake_case, kebab-case or camelCase
+/// name starts
+fn last_word_start(s: &str) -> usize {
+    let mut start = 0;
+    let mut previous: Option<char> = None;
+    for (i, c) in s.char_indices() {
+        match previous {
+            Some('_' | '-' | ' ') => start = i,
+            Some(p) if c.is_ascii_uppercase() && (p.is_ascii_lowercase() || p.is_ascii_digit()) => {
+                start = i
+            }
+            _ => {}
+        }
+        previous = Some(c);
+    }
+    start
+}
+
 /// Convert a string to a safe identifier (handling keywords and invalid chars)
 pub fn to_safe_identifier(name: &str) -> String {
     let mut safe = name.replace('-', "_");
@@ -78,6 +186,28 @@ mod tests {
     fn test_to_snake_case() {
         assert_eq!(to_snake_case("UserName"), "user_name");
         assert_eq!(to_snake_case("first-name"), "first_name");
-        assert_eq!(to_snake_case("HTMLParser"), "htmlparser"); // basic implementation
+        assert_eq!(to_snake_case("HTMLParser"), "html_parser");
+        assert_eq!(to_snake_case("userID"), "user_id");
+        assert_eq!(to_snake_case("parseHTML"), "parse_html");
+        assert_eq!(to_snake_case("v2Api"), "v2_api");
+        assert_eq!(to_snake_case("parseJSON2"), "parse_json2");
+        assert_eq!(to_snake_case("user_ID"), "user_id");
+    }
+
+    #[test]
+    fn test_to_singular() {
+        assert_eq!(to_singular("servers"), "server");
+        assert_eq!(to_singular("categories"), "category");
+        assert_eq!(to_singular("addresses"), "address");
+        assert_eq!(to_singular("status"), "status");
+        assert_eq!(to_singular("data"), "data");
+        assert_eq!(to_singular("movies"), "movie");
+        assert_eq!(to_singular("series"), "series");
+        assert_eq!(to_singular("news"), "news");
+        assert_eq!(to_singular("people"), "person");
+        assert_eq!(to_singular("topMovies"), "topMovie");
+        assert_eq!(to_singular("tv_series"), "tv_series");
+        assert_eq!(to_singular("BreakingNews"), "BreakingNews");
+        assert_eq!(to_singular("user_ids"), "user_id");
     }
 }
@@ -0,0 +1,99 @@
+//! Compile check of generated Rust with the local `rustc` (`--verify-compile`)
+
+use alchemist::error::{AlchemistError, Result};
+use std::fs;
+use std::io::ErrorKind;
+use std::process::Command;
+
+/// Crates the generated code may use but `rustc` alone cannot resolve
+const EXTERNAL_CRATES: &[&str] = &["serde", "serde_json", "chrono", "rust_decimal"];
+
+/// Type-check generated Rust as a library crate
+///
+/// Returns `Ok(false)` without checking when `rustc` is not installed. The
+/// generated code's dependencies are not available to a bare `rustc`, so
+/// errors about them are ignored; everything else, like recursive types
+/// without a `Box` or keywords used as names, is reported.
+pub fn verify_rust(code: &str) -> Result<bool> {
+    let dir = std::env::temp_dir().join(format!("alchemist-verify-{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let source = dir.join("generated.rs");
+    fs::write(&source, code)?;
+
+    let output = Command::new("rustc")
+        .args([
+            "--edition",
+            "2021",
+            "--crate-type",
+            "lib",
+            "--emit=metadata",
+        ])
+        .args(["--cap-lints", "allow", "--error-format=json", "--out-dir"])
+        .arg(&dir)
+        .arg(&source)
+        .output();
+    fs::remove_dir_all(&dir)?;
+
+    let output = match output {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e.into()),
+    };
+    if output.status.success() {
+        return Ok(true);
+    }
+
+    let errors: Vec<String> = String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|diagnostic| diagnostic["level"] == "error")
+        .filter(|diagnostic| {
+            let message = diagnostic["message"].as_str().unwrap_or_default();
+            !message.starts_with("aborting due to") && !mentions_external_crate(message)
+        })
+        .filter_map(|diagnostic| diagnostic["rendered"].as_str().map(str::to_string))
+        .collect();
+
+    if errors.is_empty() {
+        Ok(true)
+    } else {
+        Err(AlchemistError::CompileError(errors.concat()))
+    }
+}
+
+/// Whether a diagnostic is about one of the generated code's dependencies
+fn mentions_external_crate(message: &str) -> bool {
+    EXTERNAL_CRATES
+        .iter()
+        .any(|name| message.contains(&format!("`{}`", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alchemist::generators::rust::RustGenerator;
+    use alchemist::generators::{CodeGenerator, GeneratorOptions};
+    use alchemist::parser::parse_json;
+
+    #[test]
+    fn test_verify_rust_reports_real_errors_only() {
+        let options = GeneratorOptions::default();
+        let schema = parse_json(
+            r#"{"id": 1, "type": "a", "tags": ["a"], "owner": {"name": "x"}, "extra": null}"#,
+            &options,
+        )
+        .unwrap();
+        let code = RustGenerator::new(options.rust, options.shared)
+            .generate(&schema)
+            .unwrap();
+
+        // Skipped where no toolchain is installed
+        if !verify_rust(&code).unwrap() {
+            return;
+        }
+        let error = verify_rust("pub struct Node { pub next: Node }").unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("recursive type `Node` has infinite size"));
+    }
+}